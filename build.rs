@@ -0,0 +1,22 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    write_header();
+}
+
+/// Writes C header of the `ffi` module to `death.h` in `OUT_DIR`, the
+/// `ffi` tests check that `include/death.h` is the same.
+#[cfg(feature = "ffi")]
+fn write_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file("cbindgen.toml")
+        .expect("cbindgen.toml is valid");
+    let out_dir = std::env::var_os("OUT_DIR").expect("cargo sets OUT_DIR");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()
+        .expect("C header is generated")
+        .write_to_file(std::path::Path::new(&out_dir).join("death.h"));
+}
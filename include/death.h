@@ -0,0 +1,76 @@
+#ifndef DEATH_H
+#define DEATH_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Death reasons, made by `death_reasons_default` or `death_reasons_load`.
+typedef struct DeathReasons DeathReasons;
+
+// User to predict for, made by `death_user_new`.
+typedef struct DeathUser DeathUser;
+
+// Returns message of the last error in the thread, or null if there was
+// none. It is valid until the next error and must not be freed.
+const char *death_last_error(void);
+
+// Returns default death reasons in English. Free them with
+// `death_reasons_free`.
+DeathReasons *death_reasons_default(void);
+
+// Returns death reasons read from the file in any supported format, or
+// null if cannot read or parse it. Only files are read, `-` is not stdin
+// and names of installed packs are not looked up. Free them with
+// `death_reasons_free`.
+//
+// # Safety
+//
+// `path` must point to a C string.
+DeathReasons *death_reasons_load(const char *path);
+
+// Frees death reasons. Null is ignored.
+//
+// # Safety
+//
+// `reasons` must be null or made by this library and not freed yet.
+void death_reasons_free(DeathReasons *reasons);
+
+// Returns user with the name and the birthday with day, month and year,
+// e.g. `01.02.1990`, or null if the birthday is invalid. Birthday may be
+// null if it is unknown. Death reasons are copied, default ones are used
+// if they are null. Free the user with `death_user_free`.
+//
+// # Safety
+//
+// `name` must point to a C string, `birthday` must be null or point to a
+// C string and `reasons` must be null or made by this library.
+DeathUser *death_user_new(const char *name, const char *birthday, const DeathReasons *reasons);
+
+// Frees the user. Null is ignored.
+//
+// # Safety
+//
+// `user` must be null or made by this library and not freed yet.
+void death_user_free(DeathUser *user);
+
+// Returns prediction for the user as JSON object with the same fields as
+// `death --output json` prints, or null if the user is null. Free it with
+// `death_string_free`.
+//
+// # Safety
+//
+// `user` must be null or made by this library.
+char *death_predict(const DeathUser *user);
+
+// Frees the string returned by this library. Null is ignored.
+//
+// # Safety
+//
+// `text` must be null or returned by `death_predict` and not freed yet.
+void death_string_free(char *text);
+
+#endif  /* DEATH_H */
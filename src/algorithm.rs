@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Formula used to calculate how many years are left.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Algorithm {
+    /// Smaller values are returned more often than larger values
+    #[default]
+    Exponential,
+    /// Every value has the same chance
+    Linear,
+    /// Name's letter values and birthday's digits decide the year
+    Numerology,
+}
@@ -0,0 +1,125 @@
+use crate::prediction::Prediction;
+
+/// Height of letters in lines.
+pub const HEIGHT: usize = 5;
+
+/// Letters of the font. Lowercase letters are drawn as uppercase ones.
+const FONT: [(char, [&str; HEIGHT]); 41] = [
+    ('A', [" ### ", "#   #", "#####", "#   #", "#   #"]),
+    ('B', ["#### ", "#   #", "#### ", "#   #", "#### "]),
+    ('C', [" ####", "#    ", "#    ", "#    ", " ####"]),
+    ('D', ["#### ", "#   #", "#   #", "#   #", "#### "]),
+    ('E', ["#####", "#    ", "#### ", "#    ", "#####"]),
+    ('F', ["#####", "#    ", "#### ", "#    ", "#    "]),
+    ('G', [" ####", "#    ", "#  ##", "#   #", " ####"]),
+    ('H', ["#   #", "#   #", "#####", "#   #", "#   #"]),
+    ('I', ["###", " # ", " # ", " # ", "###"]),
+    ('J', ["  ###", "    #", "    #", "#   #", " ### "]),
+    ('K', ["#   #", "#  # ", "###  ", "#  # ", "#   #"]),
+    ('L', ["#    ", "#    ", "#    ", "#    ", "#####"]),
+    ('M', ["#   #", "## ##", "# # #", "#   #", "#   #"]),
+    ('N', ["#   #", "##  #", "# # #", "#  ##", "#   #"]),
+    ('O', [" ### ", "#   #", "#   #", "#   #", " ### "]),
+    ('P', ["#### ", "#   #", "#### ", "#    ", "#    "]),
+    ('Q', [" ### ", "#   #", "# # #", "#  # ", " ## #"]),
+    ('R', ["#### ", "#   #", "#### ", "#  # ", "#   #"]),
+    ('S', [" ####", "#    ", " ### ", "    #", "#### "]),
+    ('T', ["#####", "  #  ", "  #  ", "  #  ", "  #  "]),
+    ('U', ["#   #", "#   #", "#   #", "#   #", " ### "]),
+    ('V', ["#   #", "#   #", "#   #", " # # ", "  #  "]),
+    ('W', ["#   #", "#   #", "# # #", "## ##", "#   #"]),
+    ('X', ["#   #", " # # ", "  #  ", " # # ", "#   #"]),
+    ('Y', ["#   #", " # # ", "  #  ", "  #  ", "  #  "]),
+    ('Z', ["#####", "   # ", "  #  ", " #   ", "#####"]),
+    ('0', [" ### ", "#  ##", "# # #", "##  #", " ### "]),
+    ('1', [" # ", "## ", " # ", " # ", "###"]),
+    ('2', [" ### ", "#   #", "  ## ", " #   ", "#####"]),
+    ('3', ["#### ", "    #", " ### ", "    #", "#### "]),
+    ('4', ["#   #", "#   #", "#####", "    #", "    #"]),
+    ('5', ["#####", "#    ", "#### ", "    #", "#### "]),
+    ('6', [" ### ", "#    ", "#### ", "#   #", " ### "]),
+    ('7', ["#####", "    #", "   # ", "  #  ", "  #  "]),
+    ('8', [" ### ", "#   #", " ### ", "#   #", " ### "]),
+    ('9', [" ### ", "#   #", " ####", "    #", " ### "]),
+    ('-', ["    ", "    ", "####", "    ", "    "]),
+    (',', ["  ", "  ", "  ", " #", "# "]),
+    ('.', [" ", " ", " ", " ", "#"]),
+    (':', [" ", "#", " ", "#", " "]),
+    (' ', ["  ", "  ", "  ", "  ", "  "]),
+];
+
+/// Returns the text in big letters, [`None`] if the font has no letter for
+/// some character.
+///
+/// # Example
+///
+/// ```
+/// use death::banner;
+///
+/// let text = banner::render("RIP").unwrap();
+///
+/// assert_eq!(text.lines().next(), Some("####  ### ####"));
+/// assert_eq!(text.lines().count(), banner::HEIGHT);
+/// assert_eq!(banner::render("R.I.P. ☠"), None);
+/// ```
+pub fn render(text: &str) -> Option<String> {
+    let letters = text.chars()
+        .map(|c| FONT.iter()
+            .find(|(letter, _)| *letter == c.to_ascii_uppercase())
+            .map(|(_, rows)| rows))
+        .collect::<Option<Vec<_>>>()?;
+
+    let lines: Vec<String> = (0..HEIGHT)
+        .map(|i| letters.iter()
+            .map(|rows| rows[i])
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end()
+            .to_string())
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// Returns width of the text in big letters in characters.
+fn width(text: &str) -> usize {
+    text.lines().map(|l| l.chars().count()).max().unwrap_or(0)
+}
+
+/// Returns the death date with time, if it is predicted, in big letters.
+/// The date is written in words if the font has all its letters and it
+/// fits into `width` characters, in ISO format otherwise.
+pub fn date(prediction: &Prediction, width: usize) -> String {
+    let time = prediction.death_time
+        .map(|t| format!(" {}", t))
+        .unwrap_or_default();
+    let long = format!("{}{}", prediction.death_date, time);
+    if let Some(text) = render(&long).filter(|t| self::width(t) <= width) {
+        return text;
+    }
+    let iso = format!("{}{}", prediction.death_date.to_iso(), time);
+    render(&iso).unwrap_or(iso)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Algorithm;
+    use crate::date::Date;
+    use crate::reasons::ReasonSet;
+    use crate::user::User;
+
+    #[test]
+    fn dates() {
+        assert!(FONT.iter().all(|(_, rows)| rows.iter()
+            .all(|r| r.len() == rows[0].len())));
+
+        let mut user = User::new(1234567890, 0, ReasonSet::default());
+        user.set_birthday(Date::build(2000, 1, 2).unwrap());
+        let prediction = Prediction::new(&user, Algorithm::Linear, false);
+
+        let wide = date(&prediction, 200);
+        let narrow = date(&prediction, 10);
+        assert_ne!(wide, narrow);
+        assert_eq!(narrow, render(&prediction.death_date.to_iso()).unwrap());
+    }
+}
@@ -0,0 +1,419 @@
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::date::{self, Date, DateOrder};
+use crate::error::DeathError;
+use crate::prediction::Prediction;
+use crate::reasons::{self, Format, ReasonSet};
+use crate::user::User;
+
+/// Person to predict for in batch mode.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Deserialize)]
+pub struct Person {
+    /// Name of the person
+    pub name: String,
+    /// Birthday as it is written in the file, e.g. `31.12.1990`
+    #[serde(default)]
+    pub birthday: Option<String>,
+    /// Age if birthday is unknown
+    #[serde(default)]
+    pub age: Option<u8>,
+    /// Id of the person in the file, e.g. GEDCOM individual `@I1@`
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+impl Person {
+    /// Returns a new person without birthday and age.
+    pub fn new(name: &str) -> Person {
+        Person {
+            name: name.to_string(), birthday: None, age: None, id: None,
+        }
+    }
+
+    /// Returns a person from `name,birthday` text. Birthday may be omitted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::batch::Person;
+    ///
+    /// let person = Person::parse("Alice, 01.02.1990");
+    ///
+    /// assert_eq!(person.name, "Alice");
+    /// assert_eq!(person.birthday.as_deref(), Some("01.02.1990"));
+    /// assert_eq!(Person::parse("Bob").birthday, None);
+    /// ```
+    pub fn parse(text: &str) -> Person {
+        let (name, birthday) = match text.split_once(',') {
+            Some((name, birthday)) => (name, Some(birthday.trim())),
+            None => (text, None),
+        };
+        Person {
+            name: name.trim().to_string(),
+            birthday: birthday.filter(|b| !b.is_empty()).map(String::from),
+            age: None,
+            id: None,
+        }
+    }
+
+    /// Returns user to predict for with the person's name and birthday
+    /// written with day, month and year in the order, or age.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeathError`] if birthday or age is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::batch::Person;
+    /// use death::date::DateOrder;
+    /// use death::reasons::{Reason, ReasonSet};
+    ///
+    /// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+    /// let mut person = Person::new("Alice");
+    /// person.age = Some(30);
+    ///
+    /// let user = person.to_user(reasons.clone(), 120, DateOrder::Dmy);
+    /// assert_eq!(user.unwrap().age(), 30);
+    ///
+    /// person.age = Some(130);
+    /// assert!(person.to_user(reasons, 120, DateOrder::Dmy).is_err());
+    /// ```
+    pub fn to_user(
+        &self, death_reasons: ReasonSet, max_age: u16, order: DateOrder
+    ) -> Result<User, DeathError> {
+        let mut user = User::new(0, 0, death_reasons);
+        user.set_max_age(max_age);
+        user.set_name(&self.name);
+        match (&self.birthday, self.age) {
+            (Some(birthday), _) => user.set_birthday(
+                date::parse_birthday(birthday, max_age, order)?
+            ),
+            (None, Some(age)) if age as u16 >= max_age => {
+                let age = age as u16;
+                return Err(DeathError::AgeTooBig { age, max_age });
+            },
+            (None, Some(age)) => user.set_age(age),
+            (None, None) => (),
+        }
+        Ok(user)
+    }
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Returns people from CSV rows of `name,birthday`. Birthday may be omitted
+/// and the first row may be a header.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot parse the CSV.
+///
+/// # Example
+///
+/// ```
+/// use death::batch::parse_csv;
+///
+/// let people = parse_csv("name,birthday\nAlice,01.02.1990\nBob\n").unwrap();
+///
+/// assert_eq!(people.len(), 2);
+/// assert_eq!(people[0].birthday.as_deref(), Some("01.02.1990"));
+/// assert_eq!(people[1].birthday, None);
+/// ```
+pub fn parse_csv(contents: &str) -> Result<Vec<Person>, Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+
+    let mut res = vec![];
+
+    for (i, record) in reader.records().enumerate() {
+        let record = record
+            .map_err(|e| invalid_data(format!("Invalid CSV: {}", e)))?;
+
+        let name = record.get(0).unwrap_or("");
+        let is_header = i == 0 && name.eq_ignore_ascii_case("name");
+        if name.is_empty() || is_header {
+            continue;
+        }
+
+        res.push(Person {
+            name: name.to_string(),
+            birthday: record.get(1)
+                .filter(|b| !b.is_empty())
+                .map(String::from),
+            age: None,
+            id: None,
+        });
+    }
+
+    Ok(res)
+}
+
+/// Returns people from JSON array of objects with `name` and optional
+/// `birthday` or `age` fields.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot parse the JSON.
+pub fn parse_json(contents: &str) -> Result<Vec<Person>, Error> {
+    serde_json::from_str(contents)
+        .map_err(|e| invalid_data(format!("Invalid JSON: {}", e)))
+}
+
+/// Month names in GEDCOM dates.
+const GEDCOM_MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN",
+    "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Tags of GEDCOM events that mean the individual is not living.
+const GEDCOM_DEATH_TAGS: [&str; 3] = ["DEAT", "BURI", "CREM"];
+
+/// Returns birthday written in the order and age from GEDCOM date, e.g.
+/// `12 MAR 1990`. Only age is known if the date is approximate or has no
+/// day, e.g. `ABT 1990` or `MAR 1990`.
+fn parse_gedcom_date(date: &str, order: DateOrder)
+-> (Option<String>, Option<u8>) {
+    let parts: Vec<&str> = date.split_whitespace().collect();
+    let year = match parts.last().and_then(|y| y.parse::<u16>().ok()) {
+        Some(v) => v,
+        None => return (None, None),
+    };
+    if let [day, month, _] = parts.as_slice() {
+        let month = GEDCOM_MONTHS.iter()
+            .position(|m| month.eq_ignore_ascii_case(m));
+        let date = match (day.parse::<u8>(), month) {
+            (Ok(day), Some(month)) => {
+                Date::build(year, month as u8 + 1, day).ok()
+            },
+            _ => None,
+        };
+        if let Some(date) = date {
+            return (Some(order.format(&date)), None);
+        }
+    }
+    let age = Date::today().year().checked_sub(year)
+        .and_then(|age| u8::try_from(age).ok());
+    (None, age)
+}
+
+/// Returns living individuals from GEDCOM family tree with their names,
+/// birthdays written in the order and ids. Individuals with death, burial
+/// or cremation records are skipped. Surname slashes are removed from
+/// names, individuals without names are called by their ids.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if the file has no GEDCOM header.
+///
+/// # Example
+///
+/// ```
+/// use death::batch::parse_gedcom;
+/// use death::date::DateOrder;
+///
+/// let tree = "0 HEAD\n\
+///     0 @I1@ INDI\n1 NAME Alice /Smith/\n1 BIRT\n2 DATE 1 FEB 1990\n\
+///     0 @I2@ INDI\n1 NAME Bob\n1 DEAT Y\n\
+///     0 TRLR\n";
+/// let people = parse_gedcom(tree, DateOrder::Dmy).unwrap();
+///
+/// assert_eq!(people.len(), 1);
+/// assert_eq!(people[0].name, "Alice Smith");
+/// assert_eq!(people[0].birthday.as_deref(), Some("01/02/1990"));
+/// assert_eq!(people[0].id.as_deref(), Some("@I1@"));
+/// ```
+pub fn parse_gedcom(contents: &str, order: DateOrder)
+-> Result<Vec<Person>, Error> {
+    let mut lines = contents.trim_start_matches('\u{feff}').lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .peekable();
+    if lines.peek().is_none_or(|l| *l != "0 HEAD") {
+        return Err(invalid_data(String::from("Invalid GEDCOM: no header")));
+    }
+
+    let mut res = vec![];
+    // Individual being read, and whether it is living
+    let mut current: Option<(Person, bool)> = None;
+    let mut event = "";
+    for line in lines {
+        let mut parts = line.splitn(3, ' ');
+        let level = parts.next().unwrap_or("");
+        let (tag, value) = (parts.next().unwrap_or(""), parts.next());
+
+        if level == "0" {
+            if let Some((person, true)) = current.take() {
+                res.push(person);
+            }
+            if value == Some("INDI") {
+                let mut person = Person::new(tag);
+                person.id = Some(tag.to_string());
+                current = Some((person, true));
+            }
+            continue;
+        }
+        let (person, living) = match current.as_mut() {
+            Some((person, living)) => (person, living),
+            None => continue,
+        };
+        match (level, tag) {
+            ("1", "NAME") if person.id.as_deref() == Some(&person.name) => {
+                let name = value.unwrap_or("").replace('/', " ");
+                let name = name.split_whitespace().collect::<Vec<_>>();
+                if !name.is_empty() {
+                    person.name = name.join(" ");
+                }
+            },
+            ("1", tag) => {
+                event = if tag == "BIRT" { "BIRT" } else { "" };
+                if GEDCOM_DEATH_TAGS.contains(&tag) {
+                    *living = false;
+                }
+            },
+            ("2", "DATE") if event == "BIRT" => {
+                let date = value.unwrap_or("");
+                (person.birthday, person.age) = parse_gedcom_date(date, order);
+            },
+            _ => (),
+        }
+    }
+    if let Some((person, true)) = current {
+        res.push(person);
+    }
+
+    Ok(res)
+}
+
+/// Returns `true` if the file has `.ged` extension of GEDCOM family trees.
+pub fn is_gedcom(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ged"))
+}
+
+/// Returns people from file. JSON files and GEDCOM family trees are detected
+/// by extension, other ones are read as CSV. (see [`reasons::read_contents`])
+/// Birthdays from GEDCOM are written in the order.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file, or it has
+/// nobody in it.
+pub fn read_people(file_path: &Path, order: DateOrder)
+-> Result<Vec<Person>, Error> {
+    let (contents, format) = reasons::read_contents(file_path)?;
+    let res = match format {
+        _ if is_gedcom(file_path) => parse_gedcom(&contents, order)?,
+        Format::Json => parse_json(&contents)?,
+        _ => parse_csv(&contents)?,
+    };
+
+    if res.is_empty() {
+        return Err(invalid_data(String::from("No people found")));
+    }
+
+    Ok(res)
+}
+
+/// What batch predictions are sorted by.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SortKey {
+    /// Death date, the first one to die is the first
+    Date,
+    /// Name in alphabetical order, ignoring case
+    Name,
+    /// Full years left, the fewest are the first
+    YearsLeft,
+}
+
+impl SortKey {
+    /// Sorts the predictions by the key. Predictions with the same key keep
+    /// their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::batch::SortKey;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::ReasonSet;
+    /// use death::user::User;
+    ///
+    /// let mut predictions: Vec<Prediction> = ["bob", "Alice"].iter()
+    ///     .map(|name| {
+    ///         let mut user = User::new(0, 30, ReasonSet::default());
+    ///         user.set_name(name);
+    ///         Prediction::new(&user, Algorithm::Linear, false)
+    ///     })
+    ///     .collect();
+    ///
+    /// SortKey::Name.sort(&mut predictions);
+    ///
+    /// assert_eq!(predictions[0].name, "Alice");
+    /// ```
+    pub fn sort(&self, predictions: &mut [Prediction]) {
+        match self {
+            SortKey::Date => predictions.sort_by_key(|p| p.death_date),
+            SortKey::Name => {
+                predictions.sort_by_key(|p| p.name.to_lowercase())
+            },
+            SortKey::YearsLeft => predictions.sort_by_key(|p| p.years_left),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_files() {
+        let reference = vec![
+            Person {
+                name: String::from("Alice"),
+                birthday: Some(String::from("01.02.1990")),
+                age: None,
+                id: None,
+            },
+            Person::new("Bob"),
+            Person {
+                name: String::from("Carol Smith"),
+                birthday: Some(String::from("31-12-1985")),
+                age: None,
+                id: None,
+            },
+        ];
+
+        let read = |path| read_people(Path::new(path), DateOrder::Dmy);
+        assert_eq!(read("tests/people.csv").unwrap(), reference);
+        assert_eq!(read("tests/people.json").unwrap(), reference);
+        assert!(read("tests/empty.txt").is_err());
+
+        let tree = read("tests/family.ged").unwrap();
+        let ids: Vec<_> = tree.iter().map(|p| p.id.as_deref()).collect();
+        assert_eq!(ids, [Some("@I1@"), Some("@I2@"), Some("@I4@")]);
+        assert_eq!(tree[0].birthday.as_deref(), Some("01/02/1990"));
+        assert_eq!(tree[1].name, "Bob Smith");
+        assert_eq!(tree[1].birthday, None);
+        assert!(tree[1].age.is_some());
+        assert_eq!(tree[2].name, "@I4@");
+        assert!(parse_gedcom("0 @I1@ INDI\n", DateOrder::Dmy).is_err());
+        assert!(parse_json(r#"{"name": "Alice"}"#).is_err());
+        let people = parse_json(r#"[{"name": "Dave", "age": 34}]"#).unwrap();
+        assert_eq!(people[0].age, Some(34));
+    }
+}
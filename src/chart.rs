@@ -0,0 +1,95 @@
+use crate::report;
+
+/// Character bars are drawn with.
+pub const BAR: char = '#';
+
+/// Narrowest bar in characters, unless the chart is narrower.
+const MIN_BAR_WIDTH: usize = 10;
+
+/// Returns horizontal bar chart no wider than `width` characters, if it
+/// fits. Every row is the label, the bar as long as the value compared to
+/// the largest one, and the text after it. Nonzero values have at least one
+/// character of the bar.
+///
+/// # Example
+///
+/// ```
+/// use death::chart;
+///
+/// let rows = vec![
+///     (String::from("cars"), 3.0, String::from("3")),
+///     (String::from("fire"), 1.0, String::from("1")),
+/// ];
+///
+/// assert_eq!(
+///     chart::bars(&rows, 24),
+///     "  cars  #############  3\n  fire  ####           1\n"
+/// );
+/// ```
+pub fn bars(rows: &[(String, f64, String)], width: usize) -> String {
+    let label_width = rows.iter()
+        .map(|(label, _, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let text_width = rows.iter()
+        .map(|(_, _, text)| text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let bar_width = width
+        .saturating_sub(label_width + text_width + 6)
+        .max(MIN_BAR_WIDTH);
+    let max = rows.iter().map(|(_, value, _)| *value).fold(0.0, f64::max);
+
+    let mut res = String::new();
+    for (label, value, text) in rows {
+        let mut len = match max > 0.0 {
+            true => (value / max * bar_width as f64).round() as usize,
+            false => 0,
+        };
+        if *value > 0.0 {
+            len = len.max(1);
+        }
+        let bar: String = std::iter::repeat_n(BAR, len).collect();
+        let line = format!(
+            "  {:label_width$}  {:bar_width$}  {}", label, bar, text
+        );
+        res.push_str(line.trim_end());
+        res.push('\n');
+    }
+    res
+}
+
+/// Returns the rows as bar chart no wider than `chart` characters
+/// (see [`bars`]), or as columns of labels and texts if it is [`None`].
+pub fn table(rows: &[(String, f64, String)], chart: Option<usize>)
+-> String {
+    match chart {
+        Some(width) => bars(rows, width),
+        None => report::columns(&rows.iter()
+            .map(|(label, _, text)| vec![label.clone(), text.clone()])
+            .collect::<Vec<_>>()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths() {
+        let rows = vec![
+            (String::from("2030"), 50.0, String::from("50.0%")),
+            (String::from("2031"), 0.5, String::from("0.5%")),
+            (String::from("2032"), 0.0, String::from("0.0%")),
+        ];
+        let chart = bars(&rows, 40);
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines[0].chars().count(), 40);
+        assert_eq!(lines[1], format!("  2031  #{}  0.5%", " ".repeat(24)));
+        assert!(!lines[2].contains(BAR));
+        assert_eq!(bars(&rows, 0).lines().next().unwrap().len(), 25);
+        assert_eq!(bars(&[], 40), "");
+        assert_eq!(table(&rows, None).lines().nth(1), Some("  2031  0.5%"));
+    }
+}
@@ -1,16 +1,479 @@
 use std::path::PathBuf;
-use std::{fmt, process};
-use std::io::{self, Write};
+use std::fmt;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::date::{self, Date, ParseError};
+pub use crate::date::parse_birthday;
+pub use crate::palette::ColorChoice;
+use crate::date::{Date, DateOrder, TimeFormat};
+use crate::error::DeathError;
+use crate::batch::SortKey;
+use crate::algorithm::Algorithm;
+use crate::species::Species;
+use crate::locale::{self, fill, tr, Language, Message};
+use crate::palette::{self, paint, Palette, Role};
+use crate::theme::Theme;
+use crate::reasons::Dedup;
+use crate::config::Config;
 
-use clap::Parser;
-use colored::*;
+use clap::{
+    ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand,
+    ValueEnum,
+};
+use clap::parser::ValueSource;
+use clap_complete::Shell;
 
 /// A program that predicts your death date
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    /// Arguments of `predict` command used if no command is passed
+    #[command(flatten)]
+    pub predict: PredictArgs,
+
+    /// Use downloaded death reasons files for this number of seconds
+    /// without downloading them again
+    #[arg(long, global = true, value_name = "SECONDS", default_value_t = 86400)]
+    pub cache_ttl: u64,
+
+    /// Never download death reasons files, use cached ones
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// When to use colors in messages
+    #[arg(long, global = true, value_enum, value_name = "WHEN",
+        default_value_t)]
+    pub color: ColorChoice,
+
+    /// Never use colors, same as `--color never`
+    #[arg(long, global = true, conflicts_with = "color")]
+    pub no_color: bool,
+
+    /// Colors of the output [default: doom]
+    #[arg(long, global = true, value_enum, value_name = "PALETTE")]
+    pub theme_color: Option<Palette>,
+
+    /// Language of messages and default death reasons [default: from
+    /// system locale]
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Language>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Returns settings of the prediction if the command predicts.
+    pub fn settings(&self) -> Option<&PredictionSettings> {
+        match &self.command {
+            None => Some(&self.predict.options.settings),
+            Some(Command::Predict(args)) => Some(&args.options.settings),
+            Some(Command::Batch(args)) => Some(&args.options.settings),
+            Some(Command::Import(args)) => Some(&args.options.settings),
+            Some(Command::Serve(args)) => Some(&args.settings),
+            Some(Command::Compare(args)) => Some(&args.settings),
+            Some(Command::Wizard(args)) => Some(&args.settings),
+            Some(Command::Remind(args)) => Some(&args.settings),
+            Some(Command::Group(args)) => Some(&args.settings),
+            Some(Command::Stats(args)) => Some(&args.settings),
+            Some(Command::Simulate(args)) => Some(&args.settings),
+            Some(Command::Page(args)) => Some(&args.settings),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns mutable settings of the prediction. (see [`Cli::settings`])
+    pub fn settings_mut(&mut self) -> Option<&mut PredictionSettings> {
+        match &mut self.command {
+            None => Some(&mut self.predict.options.settings),
+            Some(Command::Predict(args)) => Some(&mut args.options.settings),
+            Some(Command::Batch(args)) => Some(&mut args.options.settings),
+            Some(Command::Import(args)) => Some(&mut args.options.settings),
+            Some(Command::Serve(args)) => Some(&mut args.settings),
+            Some(Command::Compare(args)) => Some(&mut args.settings),
+            Some(Command::Wizard(args)) => Some(&mut args.settings),
+            Some(Command::Remind(args)) => Some(&mut args.settings),
+            Some(Command::Group(args)) => Some(&mut args.settings),
+            Some(Command::Stats(args)) => Some(&mut args.settings),
+            Some(Command::Simulate(args)) => Some(&mut args.settings),
+            Some(Command::Page(args)) => Some(&mut args.settings),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns arguments that choose death reasons if the command has them.
+    pub fn reasons_args(&self) -> Option<&ReasonsArgs> {
+        match &self.command {
+            Some(Command::Fortune(args)) => Some(&args.reasons),
+            Some(Command::Reasons {
+                command: ReasonsCommand::List { reasons }
+            }) => Some(reasons),
+            _ => self.settings().map(|settings| &settings.reasons),
+        }
+    }
+
+    /// Returns prediction options of `predict`, `batch` or `import` command.
+    /// Returns [`None`] if other command is passed.
+    pub fn predict_options(&self) -> Option<&PredictOptions> {
+        match &self.command {
+            Some(Command::Batch(args)) => Some(&args.options),
+            Some(Command::Import(args)) => Some(&args.options),
+            _ => self.predict_args().map(|args| &args.options),
+        }
+    }
+
+    /// Returns arguments of `predict` command, passed with the command name
+    /// or without it. Returns [`None`] if other command is passed.
+    pub fn predict_args(&self) -> Option<&PredictArgs> {
+        match &self.command {
+            Some(Command::Predict(args)) => Some(args),
+            Some(_) => None,
+            None => Some(&self.predict),
+        }
+    }
+}
+
+pub use crate::config::MAX_AGE_LIMIT;
+
+/// Returns `true` if the argument is passed in command line, not taken from
+/// its default value.
+fn is_passed(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+impl Cli {
+    /// Sets options that are not passed in command line from config.
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if !self.no_color && !is_passed(matches, "color") {
+            if let Some(color) = config.color {
+                self.color = color;
+            }
+        }
+        self.theme_color = self.theme_color.or(config.theme_color);
+        if let Some(Command::Serve(args)) = &mut self.command {
+            args.slack_secret = config.slack_secret.clone();
+        }
+
+        if let Some(settings) = self.settings_mut() {
+            settings.apply_config(config);
+            return;
+        }
+        match &mut self.command {
+            Some(Command::Fortune(args)) => args.reasons.apply_config(config),
+            Some(Command::Reasons {
+                command: ReasonsCommand::List { reasons }
+            }) => reasons.apply_config(config),
+            _ => (),
+        }
+    }
+}
+
+impl ReasonsArgs {
+    /// Sets options that are not passed in command line from config.
+    ///
+    /// Death reasons from config are used only if no other death reasons
+    /// are chosen by arguments or `DEATH_REASONS` environment variable.
+    pub fn apply_config(&mut self, config: &Config) {
+        let is_chosen = !self.death_reasons.is_empty() ||
+            self.theme.is_some() || self.pet.is_some() ||
+            env::var_os(crate::reasons::ENV_VAR).is_some();
+        if !is_chosen {
+            self.death_reasons = config.reasons.clone();
+        }
+    }
+}
+
+/// Arguments of `predict` command.
+#[derive(Args, Debug)]
+pub struct PredictArgs {
+    /// Names to predict for, a prediction is printed for each of them
+    #[arg(value_name = "NAME")]
+    pub names: Vec<String>,
+
+    /// Your name, repeat it to predict for several people
+    #[arg(short, long)]
+    pub name: Vec<String>,
+
+    /// Your birthday
+    #[arg(short, long)]
+    pub birthday: Option<String>,
+
+    /// Your age, if you don't want to tell your birthday
+    #[arg(long, conflicts_with = "birthday")]
+    pub age: Option<u8>,
+
+    /// Read names from stdin, one per line, and print a prediction for each
+    /// of them
+    #[arg(long, conflicts_with_all = ["name", "names"])]
+    pub stdin: bool,
+
+    /// Never ask for name or birthday, exit with an error if they are not
+    /// passed
+    #[arg(long)]
+    pub no_input: bool,
+
+    /// Use this number instead of the name's hash, so the same prediction
+    /// can be reproduced with any name
+    #[arg(long, value_name = "SEED", conflicts_with = "stdin")]
+    pub seed: Option<u64>,
+
+    /// Use linear formula to calculate the date. Deprecated, use
+    /// `--algorithm linear`
+    #[arg(short, long, hide = true)]
+    pub linear: Option<bool>,
+
+    #[command(flatten)]
+    pub options: PredictOptions,
+}
+
+impl PredictArgs {
+    /// Returns names passed as positional arguments and then with `--name`.
+    pub fn all_names(&self) -> Vec<&str> {
+        self.names.iter().chain(&self.name).map(String::as_str).collect()
+    }
+}
+
+/// Arguments of `batch` command.
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// CSV file with `name,birthday` rows, JSON array of objects with
+    /// `name` and `birthday` or `age` fields, or GEDCOM family tree with
+    /// `.ged` extension. Use `-` to read CSV from stdin
+    pub file: PathBuf,
+
+    /// Print predictions sorted by the key instead of in the order of the
+    /// file. They are printed when everyone is predicted
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub sort: Option<SortKey>,
+
+    /// Print sorted predictions in reverse order
+    #[arg(long, requires = "sort")]
+    pub reverse: bool,
+
+    #[command(flatten)]
+    pub options: PredictOptions,
+}
+
+/// Arguments of `import` command.
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Address book export: vCard file with `.vcf` extension or Google
+    /// Contacts CSV. Contacts without birthdays are skipped
+    pub file: PathBuf,
+
+    /// Print predictions sorted by the key instead of in the order of the
+    /// file. They are printed when everyone is predicted
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub sort: Option<SortKey>,
+
+    /// Print sorted predictions in reverse order
+    #[arg(long, requires = "sort")]
+    pub reverse: bool,
+
+    #[command(flatten)]
+    pub options: PredictOptions,
+}
+
+/// Arguments of `group` command.
+#[derive(Args, Debug)]
+pub struct GroupArgs {
+    /// Person in the group as `NAME,BIRTHDAY`, birthday may be omitted. Pass
+    /// it once for everyone
+    #[arg(short, long = "person", value_name = "NAME,BIRTHDAY",
+        required_unless_present = "from")]
+    pub people: Vec<String>,
+
+    /// File with more people in the group, in any format `batch` reads.
+    /// Use `-` to read CSV from stdin
+    #[arg(long, value_name = "FILE")]
+    pub from: Option<PathBuf>,
+
+    /// Also predict time of death
+    #[arg(long)]
+    pub time: bool,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `stats` command.
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// CSV file with `name,birthday` rows, JSON array of objects with
+    /// `name` and `birthday` or `age` fields, or GEDCOM family tree with
+    /// `.ged` extension. Use `-` to read CSV from stdin
+    pub file: PathBuf,
+
+    /// Print the statistics as JSON object
+    #[arg(long)]
+    pub json: bool,
+
+    /// Draw distributions as bar charts as wide as the terminal
+    #[arg(long, conflicts_with = "json")]
+    pub chart: bool,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `simulate` command.
+#[derive(Args, Debug)]
+pub struct SimulateArgs {
+    /// Number of generated users
+    #[arg(long, value_name = "N", default_value_t = 100000,
+        value_parser = clap::value_parser!(u64).range(1..))]
+    pub samples: u64,
+
+    /// Age of every user instead of random ones
+    #[arg(long)]
+    pub age: Option<u8>,
+
+    /// Print the results as JSON object
+    #[arg(long)]
+    pub json: bool,
+
+    /// Draw distributions as bar charts as wide as the terminal
+    #[arg(long, conflicts_with = "json")]
+    pub chart: bool,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `page` command.
+#[derive(Args, Debug)]
+pub struct PageArgs {
+    /// Name of the person [default: name of the system user]
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Birthday of the person
+    #[arg(short, long, value_name = "DATE")]
+    pub birthday: Option<String>,
+
+    /// Directory to write `index.html` to, created if it doesn't exist
+    #[arg(short, long, value_name = "DIR", default_value = "site")]
+    pub out: PathBuf,
+
+    /// Overwrite `index.html` if it already exists
+    #[arg(long)]
+    pub force: bool,
+
+    /// Also predict time of death, the countdown ends at it instead of
+    /// midnight
+    #[arg(long)]
+    pub time: bool,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `serve` command.
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Address to listen on, `0.0.0.0` accepts connections from other hosts
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Draw a tarot card to choose the death reason
+    #[arg(long)]
+    pub tarot: bool,
+
+    /// Answer at most this number of requests a minute from every client
+    /// address, the rest get `429 Too Many Requests`. Not limited by default
+    #[arg(long, value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..))]
+    pub rate_limit: Option<u32>,
+
+    /// Number of requests a client can make at once before the rate limit
+    /// slows it down [default: the rate limit]
+    #[arg(long, value_name = "N", requires = "rate_limit",
+        value_parser = clap::value_parser!(u32).range(1..))]
+    pub burst: Option<u32>,
+
+    /// Number of responses with predictions to keep, so repeated requests
+    /// are answered without predicting again. Use 0 to keep none
+    #[arg(long, value_name = "N", default_value_t = 1024)]
+    pub cache_size: usize,
+
+    /// PEM file with certificate chain to answer HTTPS instead of HTTP.
+    /// Needs `tls` feature
+    #[arg(long, value_name = "FILE", requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM file with PKCS#8 or RSA private key of the certificate
+    #[arg(long, value_name = "FILE", requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// Signing secret of Slack app from config, not passed in command line
+    /// to keep it out of process list
+    #[arg(skip)]
+    pub slack_secret: Option<String>,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `compare` command. Names and birthdays that are not passed
+/// are asked.
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// Name of the first person
+    #[arg(long, value_name = "NAME")]
+    pub a_name: Option<String>,
+
+    /// Birthday of the first person
+    #[arg(long, value_name = "DATE")]
+    pub a_birthday: Option<String>,
+
+    /// Name of the second person
+    #[arg(long, value_name = "NAME")]
+    pub b_name: Option<String>,
+
+    /// Birthday of the second person
+    #[arg(long, value_name = "DATE")]
+    pub b_birthday: Option<String>,
+
+    /// Also predict time of death
+    #[arg(long)]
+    pub time: bool,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `wizard` command. Species and theme are not asked if they
+/// are passed.
+#[derive(Args, Debug)]
+pub struct WizardArgs {
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
+
+/// Arguments of `fortune` command.
+#[derive(Args, Debug)]
+pub struct FortuneArgs {
+    /// Your name [default: name of the system user]
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Print the horoscope as JSON object
+    #[arg(long)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub reasons: ReasonsArgs,
+}
+
+/// Arguments of `remind` command.
+#[derive(Args, Debug)]
+pub struct RemindArgs {
     /// Your name
     #[arg(short, long)]
     pub name: Option<String>,
@@ -19,98 +482,792 @@ pub struct Cli {
     #[arg(short, long)]
     pub birthday: Option<String>,
 
-    /// Custom death reasons file
-    #[arg(short, long, value_name = "FILE")]
-    pub death_reasons: Option<PathBuf>,
+    #[command(flatten)]
+    pub settings: PredictionSettings,
+}
 
-    /// Use linear formula to calculate the date
+/// Arguments of `check` command.
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Check the latest prediction of the person with this name instead of
+    /// the latest one of anyone
     #[arg(short, long)]
-    pub linear: Option<bool>,
+    pub name: Option<String>,
+
+    /// Also show reached milestones in desktop notification
+    #[arg(long)]
+    pub notify: bool,
 }
 
-/// Parse command-line arguments.
-pub fn parse() -> Cli {
-    Cli::parse()
+/// Arguments that choose how to predict and print the prediction.
+#[derive(Args, Debug)]
+pub struct PredictOptions {
+    /// How to print the prediction
+    #[arg(short, long, value_enum, default_value_t)]
+    pub output: Output,
+
+    /// Print the prediction by template, e.g. "You die on {date} of
+    /// {reason}". Placeholders: {name}, {id}, {birthday}, {date},
+    /// {iso_date}, {year}, {month}, {day}, {weekday}, {time}, {years_left},
+    /// {days_left}, {age_at_death}, {likely_from}, {likely_to}, {reason},
+    /// {category}, {tip}, {emoji}, {card}, {algorithm}. Use {{ and }} for
+    /// braces
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format: Option<String>,
+
+    /// Print only the date and the reason separated by tab, never ask for
+    /// name or birthday and exit with an error if they are not passed
+    #[arg(short, long, conflicts_with_all = ["output", "format"])]
+    pub quiet: bool,
+
+    /// Print only the death date, e.g. `2061-03-14`
+    #[arg(long, conflicts_with_all = ["output", "format", "quiet"])]
+    pub date_only: bool,
+
+    /// Print only the death reason
+    #[arg(long,
+        conflicts_with_all = ["output", "format", "quiet", "date_only"])]
+    pub reason_only: bool,
+
+    /// Print only the date and the reason in one short line, e.g.
+    /// `☠ 2061-03-14 (cars)`, for shell prompts and status lines. Name and
+    /// birthday are never asked and must be passed
+    #[arg(long, conflicts_with_all = [
+        "output", "format", "quiet", "date_only", "reason_only",
+    ])]
+    pub oneline: bool,
+
+    /// Put emoji of the reason into the output, e.g. 🔥 for fire. Packs
+    /// can set `emoji` of their reasons
+    #[arg(long, conflicts_with = "no_emoji")]
+    pub emoji: bool,
+
+    /// Don't put emoji into the output
+    #[arg(long)]
+    pub no_emoji: bool,
+
+    /// Draw a tarot card to choose the death reason
+    #[arg(long)]
+    pub tarot: bool,
+
+    /// Also predict time of death
+    #[arg(long)]
+    pub time: bool,
+
+    /// Clock to write time of death with, 12-hour by default in English
+    /// and 24-hour in other languages
+    #[arg(long, value_enum)]
+    pub time_format: Option<TimeFormat>,
+
+    /// Print everything about the prediction: the date with time, the
+    /// reason, the epitaph, last words, zodiac sign, the countdown, the
+    /// risks and the timeline
+    #[arg(long, conflicts_with_all = [
+        "output", "format", "quiet", "date_only", "reason_only", "tombstone",
+        "oneline",
+    ])]
+    pub full_report: bool,
+
+    /// Draw the prediction as a tombstone that fits the terminal
+    #[arg(long, conflicts_with_all = [
+        "output", "format", "quiet", "date_only", "reason_only", "oneline",
+    ])]
+    pub tombstone: bool,
+
+    /// Write the death date in big letters, e.g. for a projector
+    #[arg(long, conflicts_with_all = [
+        "output", "format", "quiet", "date_only", "reason_only", "oneline",
+        "full_report", "tombstone",
+    ])]
+    pub big: bool,
+
+    /// Play the Grim Reaper animation before the prediction if it is
+    /// printed to a terminal
+    #[arg(long)]
+    pub reaper: bool,
+
+    /// Never play animations, e.g. if `--reaper` is in a shell alias
+    #[arg(long)]
+    pub no_animation: bool,
+
+    /// Also print QR code of the prediction summary, so it can be scanned
+    /// from the screen
+    #[arg(long, conflicts_with_all = [
+        "output", "format", "quiet", "date_only", "reason_only", "oneline",
+    ])]
+    pub qr: bool,
+
+    /// Also save QR code of the prediction summary to PNG image
+    #[arg(long, value_name = "FILE")]
+    pub qr_file: Option<PathBuf>,
+
+    /// Exit with the number of full years left, up to 125, instead of 0,
+    /// e.g. `death --exit-years; [ $? -lt 10 ] && echo hurry`
+    #[arg(long)]
+    pub exit_years: bool,
+
+    /// Only check config, birthday, death reasons, the template and people
+    /// in the file or stdin, report problems and predict nothing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Explain how the prediction is made, step by step, to stderr
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Write the prediction to the file instead of stdout
+    #[arg(long, value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long, requires = "output_file")]
+    pub force: bool,
+
+    /// Also send the prediction in JSON to the URL in POST request, e.g. to
+    /// chat's incoming webhook
+    #[arg(long, value_name = "URL")]
+    pub webhook: Option<String>,
+
+    /// Also show the prediction in desktop notification
+    #[arg(long)]
+    pub notify: bool,
+
+    /// Also copy the printed prediction to clipboard
+    #[arg(long)]
+    pub copy: bool,
+
+    /// Don't save the prediction to history file
+    #[arg(long)]
+    pub no_history: bool,
+
+    /// Also save the death date as an event to iCalendar file, so it can be
+    /// imported to a calendar app
+    #[arg(long, value_name = "FILE")]
+    pub ical: Option<PathBuf>,
+
+    /// Add reminder event to iCalendar file this number of days before the
+    /// death date, can be passed multiple times
+    #[arg(long, value_name = "DAYS", requires = "ical")]
+    pub reminder: Vec<u32>,
+
+    #[command(flatten)]
+    pub settings: PredictionSettings,
 }
 
-/// Print error to stderr.
+/// Arguments that choose how to predict, shared by every command that
+/// predicts.
+#[derive(Args, Debug, Clone)]
+pub struct PredictionSettings {
+    /// Formula to calculate the date [default: exponential]
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// Max age instead of the species' one, must be greater than your age
+    #[arg(long, value_name = "YEARS",
+        value_parser = clap::value_parser!(u16).range(1..=MAX_AGE_LIMIT))]
+    pub max_age: Option<u16>,
+
+    /// Order of day, month and year in birthdays [default: dmy]
+    #[arg(long, value_enum, value_name = "ORDER",
+        visible_alias = "date-format")]
+    pub date_order: Option<DateOrder>,
+
+    #[command(flatten)]
+    pub reasons: ReasonsArgs,
+}
+
+impl PredictionSettings {
+    /// Sets options that are not passed in command line from config.
+    pub fn apply_config(&mut self, config: &Config) {
+        self.algorithm = self.algorithm.or(config.algorithm);
+        self.max_age = self.max_age.or(config.max_age);
+        self.date_order = self.date_order.or(config.date_order);
+        self.reasons.apply_config(config);
+    }
+
+    /// Returns the formula, [`Algorithm::Exponential`] if it is not passed.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm.unwrap_or_default()
+    }
+
+    /// Returns max age, the species' one if it is not passed.
+    pub fn max_age(&self) -> u16 {
+        self.max_age.unwrap_or(self.reasons.pet.unwrap_or_default().max_age())
+    }
+
+    /// Returns order of day, month and year in birthdays, day first if it is
+    /// not passed.
+    pub fn date_order(&self) -> DateOrder {
+        self.date_order.unwrap_or_default()
+    }
+}
+
+/// Arguments that choose death reasons.
+#[derive(Args, Debug, Clone)]
+pub struct ReasonsArgs {
+    /// Custom death reasons file or installed pack, can be passed multiple
+    /// times. Use `-` to read from stdin
+    #[arg(short, long, visible_alias = "file", value_name = "FILE")]
+    pub death_reasons: Vec<PathBuf>,
+
+    /// Death reasons file or installed pack to add to the other death
+    /// reasons instead of replacing them, can be passed multiple times
+    #[arg(long, value_name = "FILE")]
+    pub extra_reasons: Vec<PathBuf>,
+
+    /// Predict for a pet of this species
+    #[arg(long, value_enum, value_name = "SPECIES")]
+    pub pet: Option<Species>,
+
+    /// Built-in death reasons pack
+    #[arg(long, value_enum)]
+    pub theme: Option<Theme>,
+
+    /// What to do with repeated death reasons
+    #[arg(long, value_enum, default_value_t)]
+    pub dedup: Dedup,
+
+    /// Use only death reasons of the category, e.g. `accident` or `natural`
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Never predict these death reasons or categories: file or
+    /// comma-separated list, can be passed multiple times
+    #[arg(long, value_name = "FILE|LIST")]
+    pub exclude_reasons: Vec<String>,
+
+    /// Read at most this number of death reasons
+    #[arg(long, value_name = "N")]
+    pub max_reasons: Option<usize>,
+}
+
+/// Format of the prediction output.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[derive(ValueEnum)]
+pub enum Output {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// JSON object, or array of them in batch mode
+    Json,
+    /// JSON object per line, printed as soon as the prediction is made
+    Jsonl,
+    /// Report with risk table, timeline and obituary in Markdown
+    Markdown,
+    /// Report with risk table, timeline and obituary in HTML page
+    Html,
+    /// Header and a row per prediction: name, birthday, death date, day of
+    /// week, reason and years left
+    Csv,
+    /// JSON object with days left for Waybar or i3status-rust custom
+    /// module
+    Waybar,
+}
+
+/// Subcommands of the program.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Predict death date and reason, the default command
+    Predict(PredictArgs),
+    /// Predict for everyone in the file
+    Batch(BatchArgs),
+    /// Predict for everyone with birthday in address book export
+    Import(ImportArgs),
+    /// Manage death reasons packs
+    Reasons {
+        #[command(subcommand)]
+        command: ReasonsCommand,
+    },
+    /// Show previous predictions saved to history file
+    #[command(args_conflicts_with_subcommands = true)]
+    History {
+        #[command(flatten)]
+        args: HistoryArgs,
+
+        #[command(subcommand)]
+        command: Option<HistoryCommand>,
+    },
+    /// Predict for a family or a group of friends and print who dies when,
+    /// who die on the same day and the last one standing
+    Group(GroupArgs),
+    /// Predict for everyone in the file and print statistics: death years,
+    /// the most common reasons, years left, the first and the last to die
+    Stats(StatsArgs),
+    /// Predict for generated users and print distribution of years left and
+    /// how often every reason is chosen, to check the formulas are fair
+    Simulate(SimulateArgs),
+    /// Write self-contained HTML page with the prediction, a countdown timer
+    /// and a tombstone, e.g. to host it on GitHub Pages
+    Page(PageArgs),
+    /// Answer HTTP requests with predictions in JSON, e.g.
+    /// `GET /predict?name=Alice&birthday=01.02.1990`, and Slack slash
+    /// commands sent to `/slack`
+    Serve(ServeArgs),
+    /// Compare predictions of two people and tell who outlives whom
+    Compare(CompareArgs),
+    /// Ask everything that can be asked step by step and print the full
+    /// report
+    Wizard(WizardArgs),
+    /// Print today's death horoscope, e.g. in shell startup file
+    Fortune(FortuneArgs),
+    /// Keep running and show desktop notification on every memento mori
+    /// milestone, e.g. when 10,000 days are left
+    Remind(RemindArgs),
+    /// Print memento mori milestones reached today by the latest prediction
+    /// in history and nothing otherwise, e.g. in daily cron job
+    Check(CheckArgs),
+    /// Print shell completion script
+    Completions {
+        /// Shell to print the script for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print man page in roff format
+    #[command(hide = true)]
+    Man,
+}
+
+/// Subcommands of `reasons` command.
+#[derive(Subcommand, Debug)]
+pub enum ReasonsCommand {
+    /// Install death reasons pack from URL or file, so it can be passed to
+    /// `--death-reasons` by name
+    Install {
+        /// URL or file of the pack
+        source: String,
+    },
+    /// List death reasons that are used for prediction and where they
+    /// come from
+    List {
+        #[command(flatten)]
+        reasons: ReasonsArgs,
+    },
+    /// List installed death reasons packs
+    Packs,
+    /// Remove installed death reasons pack
+    Remove {
+        /// Name of the pack
+        name: String,
+    },
+    /// Check death reasons file and print issues with line numbers, exit
+    /// with an error if there are any
+    Validate {
+        /// Death reasons file or installed pack name
+        file: PathBuf,
+    },
+}
+
+/// Arguments that choose which history records to show and how.
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+    /// Show only this number of the latest records
+    #[arg(long, value_name = "N")]
+    pub last: Option<usize>,
+
+    /// Show only records of the person with this name
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Print records as JSON array
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Subcommands of `history` command.
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// Remove all records from history file
+    Clear,
+    /// Show when someone's prediction changed, e.g. after an update
+    Diff {
+        #[command(flatten)]
+        args: HistoryArgs,
+    },
+}
+
+/// Parse command-line arguments. Colors are turned on or off by
+/// `--color` and `--no-color` options (see [`ColorChoice`]), cache of
+/// downloaded files is set by `--cache-ttl` and `--offline` options.
+///
+/// # Errors
 ///
-/// If `code` is not `0`, program will close with this code.
+/// Returns [`DeathError::Args`] if arguments are invalid or help or version
+/// is asked for, or [`DeathError::Config`] if config file is invalid.
+pub fn parse() -> Result<Cli, DeathError> {
+    let matches = Cli::command().try_get_matches()
+        .map_err(DeathError::Args)?;
+    let mut cli = Cli::from_arg_matches(&matches)
+        .map_err(DeathError::Args)?;
+    let is_json = cli.predict_options()
+        .is_some_and(|o| matches!(o.output, Output::Json | Output::Jsonl));
+    set_json_messages(is_json);
+    let config = Config::load().map_err(DeathError::Config)?;
+    cli.apply_config(&config, &matches);
+    let language = cli.lang.or(config.lang)
+        .unwrap_or_else(Language::detect);
+    locale::set_language(language);
+    if let Some(format) = cli.predict_options().and_then(|o| o.time_format) {
+        locale::set_time_format(format);
+    }
+    let choice = if cli.no_color { ColorChoice::Never } else { cli.color };
+    set_color(choice);
+    palette::set_palette(cli.theme_color.unwrap_or_default());
+    #[cfg(feature = "http")]
+    crate::remote::set_cache(crate::remote::Cache {
+        ttl: std::time::Duration::from_secs(cli.cache_ttl),
+        offline: cli.offline,
+    });
+    Ok(cli)
+}
+
+/// Decorate output on stdout with colors and headers if `true`.
+static DECORATED: AtomicBool = AtomicBool::new(false);
+
+/// Turn colors in messages on or off. Output on stdout is decorated the same
+/// way if it is a terminal. (see [`is_decorated`])
+pub fn set_color(choice: ColorChoice) {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = choice.enabled(no_color, io::stderr().is_terminal());
+    colored::control::set_override(enabled);
+    let decorated = choice.enabled(no_color, io::stdout().is_terminal());
+    DECORATED.store(decorated, Ordering::Relaxed);
+}
+
+/// Returns `true` if output for humans on stdout is decorated with colors
+/// and headers. It is plain if stdout is piped or redirected to a file, so
+/// other programs don't get escape codes. (see [`set_color`])
+pub fn is_decorated() -> bool {
+    DECORATED.load(Ordering::Relaxed)
+}
+
+/// Print shell completion script to stdout.
+pub fn print_completions(shell: Shell) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+}
+
+/// Sections of the man page that are not generated from arguments.
+const MAN_EXTRA: &str = r#".SH "DEATH REASONS FILES"
+Format of death reasons file is detected by extension. Files with
+\fB.gz\fR extension after it are decompressed.
+.TP
+\fB.txt\fR or other
+One reason per line. Lines starting with \fB#\fR and trailing comments after
+\fB #\fR are ignored, \fB\e#\fR starts a reason with \fB#\fR.
+Metadata is in \fB# key: value\fR comments at the top of the file.
+.TP
+\fB.json\fR, \fB.yaml\fR, \fB.yml\fR
+Array of strings or objects with \fBreason\fR, \fBweight\fR, \fBcategory\fR,
+\fBtip\fR and \fBemoji\fR fields, or object with metadata fields and
+\fBreasons\fR array. YAML needs \fByaml\fR feature.
+.TP
+\fB.toml\fR
+Metadata fields and \fB[[reason]]\fR tables with \fBtext\fR, \fBweight\fR,
+\fBcategory\fR, \fBtip\fR and \fBemoji\fR fields.
+.TP
+\fB.csv\fR
+\fBreason,weight[,category[,tip[,emoji]]]\fR rows with optional header.
+.PP
+Metadata fields are \fBname\fR, \fBauthor\fR, \fBlanguage\fR,
+\fBlicense\fR and \fBversion\fR.
+.SH FILES
+.TP
+\fI$XDG_CONFIG_HOME/death/config.toml\fR
+Default options: \fBreasons\fR, \fBalgorithm\fR, \fBmax_age\fR,
+\fBdate_order\fR, \fBlang\fR, \fBcolor\fR, \fBtheme_color\fR and
+\fBslack_secret\fR. Options passed in command line take precedence.
+.TP
+\fI$XDG_CONFIG_HOME/death/reasons.txt\fR
+Death reasons used if \fB\-\-death\-reasons\fR is not passed.
+.TP
+\fI$XDG_CONFIG_HOME/death/reasons.d/\fR
+Death reasons packs merged with \fIreasons.txt\fR.
+.TP
+\fI$XDG_DATA_HOME/death/packs/\fR
+Death reasons packs installed by \fBdeath reasons install\fR.
+.TP
+\fI$XDG_DATA_HOME/death/history.jsonl\fR
+Every prediction made without \fB\-\-no\-history\fR, one JSON object per
+line.
+.TP
+\fI$XDG_CACHE_HOME/death/remote/\fR
+Downloaded death reasons files.
+.SH ENVIRONMENT
+.TP
+\fBDEATH_REASONS\fR
+Death reasons file or comma-separated list of reasons.
+.TP
+\fBDEATH_SLACK_SECRET\fR
+Signing secret of Slack app to verify slash commands sent to \fBdeath
+serve\fR with. Takes precedence over \fBslack_secret\fR in config.
+.TP
+\fBNO_COLOR\fR
+Do not use colors in messages if set.
+.TP
+\fBLC_ALL\fR, \fBLC_MESSAGES\fR, \fBLANG\fR
+Language of messages and default death reasons.
+.SH "EXIT STATUS"
+.TP
+\fB0\fR
+Success.
+.TP
+\fB1\fR
+Other errors.
+.TP
+\fB2\fR
+Invalid arguments or config.
+.TP
+\fB3\fR
+Invalid birthday or age.
+.TP
+\fB4\fR
+Death reasons file can't be read or used.
+.TP
+\fB5\fR
+Other file can't be read or written.
+.TP
+\fB70\fR
+Internal error.
+.PP
+With \fB\-\-exit\-years\fR the status of successful prediction is the
+number of full years left, up to 125.
+.PP
+With \fB\-\-output json\fR or \fBjsonl\fR errors are printed to stderr as
+JSON objects with \fBerror\fR and \fBcode\fR fields.
+"#;
+
+/// Print man page in roff format to stdout.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot write to stdout.
+pub fn print_man() -> Result<(), io::Error> {
+    let man = clap_mangen::Man::new(Cli::command());
+    let mut out = io::stdout();
+    man.render(&mut out)?;
+    out.write_all(MAN_EXTRA.as_bytes())
+}
+
+/// Exit code of errors that don't fit other codes.
+pub const EXIT_ERROR: i32 = 1;
+/// Exit code of invalid arguments or config.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code of invalid birthday or age.
+pub const EXIT_DATE: i32 = 3;
+/// Exit code of death reasons files that can't be read or used.
+pub const EXIT_REASONS: i32 = 4;
+/// Exit code of other files that can't be read or written.
+pub const EXIT_IO: i32 = 5;
+/// Exit code of bugs in the program.
+pub const EXIT_INTERNAL: i32 = 70;
+/// Highest exit code of `--exit-years`, higher codes are reserved by shells.
+pub const MAX_EXIT_YEARS: u16 = 125;
+
+/// Print errors and warnings as JSON objects if `true`.
+static JSON_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// Print errors and warnings as JSON objects with `error` or `warning` and
+/// `code` fields instead of text, one object per line.
+pub fn set_json_messages(enabled: bool) {
+    JSON_MESSAGES.store(enabled, Ordering::Relaxed);
+}
+
+/// Print error to stderr. The code is the exit code the program closes
+/// with because of the error, `0` if it goes on.
 pub fn print_error<T: fmt::Display>(error: T, code: i32) {
-    eprintln!("{} {}", "error:".red(), error);
-    if code != 0 {
-        process::exit(code);
+    if JSON_MESSAGES.load(Ordering::Relaxed) {
+        let error = serde_json::json!({
+            "error": error.to_string(), "code": code,
+        });
+        eprintln!("{}", error);
+    } else {
+        eprintln!("{} {}", paint(tr(Message::Error), Role::Error), error);
+    }
+}
+
+/// Print warning to stderr.
+pub fn print_warning<T: fmt::Display>(warning: T) {
+    if JSON_MESSAGES.load(Ordering::Relaxed) {
+        let warning = serde_json::json!({ "warning": warning.to_string() });
+        eprintln!("{}", warning);
+    } else {
+        eprintln!(
+            "{} {}", paint(tr(Message::Warning), Role::Warning), warning
+        );
     }
 }
 
-/// Read string from console input.
-pub fn input() -> String {
+/// Read a line from console input without leading and trailing spaces.
+/// Returns [`None`] if input is closed.
+pub fn input() -> Option<String> {
     let mut s = String::new();
-    io::stdin().read_line(&mut s).unwrap();
-    s.trim().parse().unwrap()
+    match io::stdin().read_line(&mut s) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(s.trim().to_string()),
+    }
 }
 
-/// Ask user something in the same line as input.
-pub fn prompt(msg: &str) -> String {
+/// Ask user something in the same line as input. Returns [`None`] if input
+/// is closed.
+pub fn prompt(msg: &str) -> Option<String> {
     print!("{}: ", msg);
     let _ = io::stdout().flush();
     input()
 }
 
-/// Ask user's name.
-pub fn ask_name() -> String {
-    prompt("Your name")
+/// Ask user's name. Empty name is fine.
+///
+/// # Errors
+///
+/// Returns [`DeathError::InputClosed`] if input is closed.
+pub fn ask_name() -> Result<String, DeathError> {
+    ask(tr(Message::YourName))
 }
 
-/// Parse birthday from string.
+/// How many times user is asked again after invalid answer.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// Ask user's birthday with day, month and year in the order. Age must be
+/// less than `max_age`. Returns [`None`] if answer is empty.
 ///
 /// # Errors
 ///
-/// Returns a string containing the reason why parsing was failed.
-pub fn parse_birthday(string: &String) -> Result<Date, String> {
-    let today = Date::today();
-    let birthday = match Date::parse(string) {
-        Ok(d) => d,
-        Err(e) => {
-            let msg = match e {
-                ParseError::SeparatorNotFound =>
-                    "Use '/', or '.', or '-', or whitespace \
-                    as separator between day, month and year.",
-                ParseError::InvalidPartsCount =>
-                    "Invalid should be DD/MM/YYYY - day, month and year.",
-                ParseError::NumberConversionError => "Invalid number.",
-                ParseError::InvalidYear => "Invalid year.",
-                ParseError::InvalidMonth => "Invalid month.",
-                ParseError::InvalidDay => "Invalid day.",
-            };
-            return Err(String::from(msg));
-        }
-    };
-    if birthday.years_from(Date::today()) >= date::MAX_AGE {
-        return Err(
-            String::from("Your birthday cannot be so much in the past.")
-        );
-    }
-    if today < birthday {
-        return Err(String::from("Your birthday cannot be in the future."));
+/// Returns [`DeathError::InputClosed`] if input is closed or
+/// [`DeathError::TooManyAttempts`] if answer is invalid [`MAX_ATTEMPTS`]
+/// times.
+pub fn ask_birthday(max_age: u16, order: DateOrder)
+-> Result<Option<Date>, DeathError> {
+    let question = fill(tr(Message::EnterBirthday), &[&order.pattern()]);
+    ask_until(&question, |inp| match inp {
+        "" => Ok(None),
+        inp => parse_birthday(inp, max_age, order).map(Some),
+    })
+}
+
+/// Ask user something in the same line as input.
+///
+/// # Errors
+///
+/// Returns [`DeathError::InputClosed`] if input is closed.
+fn ask(msg: &str) -> Result<String, DeathError> {
+    match prompt(msg) {
+        Some(v) => Ok(v),
+        None => {
+            println!();
+            Err(DeathError::InputClosed)
+        },
     }
-    Ok(birthday)
 }
 
-/// Ask user's birthday
-pub fn ask_birthday() -> u8 {
-    let birthday;
-    loop {
-        let inp = prompt("Enter your birthday (DD/MM/YYYY)");
-        let _ = io::stdout().flush();
-        birthday = match parse_birthday(&inp) {
-            Ok(bday) => bday,
+/// Ask user something until the answer is valid. Errors of invalid answers
+/// are printed.
+///
+/// # Errors
+///
+/// Returns [`DeathError::InputClosed`] if input is closed or
+/// [`DeathError::TooManyAttempts`] with the last answer's error if answer
+/// is invalid [`MAX_ATTEMPTS`] times.
+fn ask_until<T, F>(msg: &str, answer: F) -> Result<T, DeathError>
+where
+    F: Fn(&str) -> Result<T, DeathError>,
+{
+    let mut last = None;
+    for _ in 0..MAX_ATTEMPTS {
+        match answer(&ask(msg)?) {
+            Ok(v) => return Ok(v),
             Err(e) => {
-                print_error(e, 0);
-                continue;
-            }
-        };
-        break;
+                print_error(&e, 0);
+                last = Some(e);
+            },
+        }
+    }
+    let last = last.expect("user is asked at least once");
+    Err(DeathError::TooManyAttempts(Box::new(last)))
+}
+
+/// Ask user to choose one of the values, they are shown with the question.
+/// Returns [`None`] if answer is empty.
+///
+/// # Errors
+///
+/// Returns [`DeathError::InputClosed`] if input is closed or
+/// [`DeathError::TooManyAttempts`] if answer is invalid [`MAX_ATTEMPTS`]
+/// times.
+pub fn ask_choice<T: ValueEnum>(msg: &str) -> Result<Option<T>, DeathError> {
+    let names: Vec<String> = T::value_variants().iter()
+        .filter_map(|v| v.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    let question = fill(
+        tr(Message::ChooseOrSkip), &[&msg, &names.join(", ")]
+    );
+    ask_until(&question, |inp| match inp {
+        "" => Ok(None),
+        inp => T::from_str(inp, true).map(Some).map_err(|_| {
+            DeathError::Invalid(format!("Unknown value `{}`", inp))
+        }),
+    })
+}
+
+/// What to do after interactive prediction.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Action {
+    /// Predict again with other salt
+    Reroll,
+    /// Compare the prediction with someone else's one
+    Compare,
+    /// Save the prediction to history
+    Save,
+    /// Stop asking
+    Quit,
+}
+
+/// Ask user what to do after the prediction. Empty answer and closed input
+/// are [`Action::Quit`].
+///
+/// # Errors
+///
+/// Returns [`DeathError::TooManyAttempts`] if answer is invalid
+/// [`MAX_ATTEMPTS`] times.
+pub fn ask_action() -> Result<Action, DeathError> {
+    let action = ask_until(tr(Message::WhatNext), |inp| {
+        match inp.to_lowercase().as_str() {
+            "r" | "reroll" => Ok(Action::Reroll),
+            "c" | "compare" => Ok(Action::Compare),
+            "s" | "save" => Ok(Action::Save),
+            "" | "q" | "quit" => Ok(Action::Quit),
+            inp => Err(DeathError::Invalid(
+                format!("Unknown action `{}`", inp)
+            )),
+        }
+    });
+    match action {
+        Err(DeathError::InputClosed) => Ok(Action::Quit),
+        action => action,
     }
-    birthday.years_from(Date::today()) as u8
+}
+
+/// Ask user a question with yes or no answer. Empty answer is no.
+///
+/// # Errors
+///
+/// Returns [`DeathError::InputClosed`] if input is closed or
+/// [`DeathError::TooManyAttempts`] if answer is invalid [`MAX_ATTEMPTS`]
+/// times.
+pub fn ask_yes_no(msg: &str) -> Result<bool, DeathError> {
+    ask_until(&format!("{} (y/N)", msg), |inp| {
+        match inp.to_lowercase().as_str() {
+            "y" | "yes" => Ok(true),
+            "" | "n" | "no" => Ok(false),
+            inp => Err(DeathError::Invalid(
+                format!("Unknown answer `{}`", inp)
+            )),
+        }
+    })
 }
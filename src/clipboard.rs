@@ -0,0 +1,24 @@
+use std::io::Error;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+
+/// How long to wait for clipboard manager to take the copied text on Linux.
+/// The text is lost when the program exits if nobody took it.
+#[cfg(target_os = "linux")]
+const WAIT: Duration = Duration::from_secs(2);
+
+/// Puts the text on the system clipboard.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if there is no clipboard, e.g. in SSH session.
+pub fn copy(text: &str) -> Result<(), Error> {
+    let mut clipboard = arboard::Clipboard::new().map_err(Error::other)?;
+    let set = clipboard.set();
+    #[cfg(target_os = "linux")]
+    let set = {
+        use arboard::SetExtLinux;
+        set.wait_until(Instant::now() + WAIT)
+    };
+    set.text(text).map_err(Error::other)
+}
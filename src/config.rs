@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::algorithm::Algorithm;
+use crate::date::DateOrder;
+use crate::locale::Language;
+use crate::palette::{ColorChoice, Palette};
+use crate::paths;
+
+/// Greatest max age that can be passed in command line or config.
+pub const MAX_AGE_LIMIT: i64 = 255;
+
+/// Default options from config file. Options passed in command line take
+/// precedence over them.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Death reasons files or installed packs, a single path or an array
+    #[serde(deserialize_with = "one_or_many")]
+    pub reasons: Vec<PathBuf>,
+    /// Formula to calculate the date
+    pub algorithm: Option<Algorithm>,
+    /// Max age instead of the species' one
+    pub max_age: Option<u16>,
+    /// Order of day, month and year in birthdays
+    #[serde(alias = "date_format")]
+    pub date_order: Option<DateOrder>,
+    /// Language of default death reasons
+    #[serde(alias = "locale")]
+    pub lang: Option<Language>,
+    /// When to use colors in messages
+    pub color: Option<ColorChoice>,
+    /// Colors of the output
+    pub theme_color: Option<Palette>,
+    /// Signing secret of Slack app answered by `death serve`
+    pub slack_secret: Option<String>,
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => vec![path],
+        OneOrMany::Many(paths) => paths,
+    })
+}
+
+impl Config {
+    /// Returns config from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if TOML is invalid or has unknown options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::config::Config;
+    ///
+    /// let config = Config::parse(
+    ///     "reasons = \"gothic\"\nalgorithm = \"linear\""
+    /// ).unwrap();
+    ///
+    /// assert_eq!(config.reasons.len(), 1);
+    /// assert_eq!(config.algorithm, Some(Algorithm::Linear));
+    /// assert_eq!(config.color, None);
+    /// ```
+    pub fn parse(contents: &str) -> Result<Config, Error> {
+        let invalid = |e: String| Error::new(
+            ErrorKind::InvalidData, format!("Invalid config: {}", e)
+        );
+        let config: Config = toml::from_str(contents)
+            .map_err(|e| invalid(e.to_string()))?;
+
+        let limit = 1..=MAX_AGE_LIMIT;
+        if config.max_age.is_some_and(|v| !limit.contains(&(v as i64))) {
+            return Err(invalid(format!(
+                "max_age must be from 1 to {}", MAX_AGE_LIMIT
+            )));
+        }
+
+        Ok(config)
+    }
+
+    /// Returns config from file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if cannot read or parse the file.
+    pub fn read(file_path: &Path) -> Result<Config, Error> {
+        fs::read_to_string(file_path)
+            .and_then(|contents| Config::parse(&contents))
+            .map_err(|e| Error::new(
+                e.kind(), format!("{}: {}", file_path.display(), e)
+            ))
+    }
+
+    /// Returns config from user's config file (see [`paths::config_file`]),
+    /// or default one if there is no such file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if cannot read or parse the file.
+    pub fn load() -> Result<Config, Error> {
+        match paths::config_file() {
+            Some(path) if path.is_file() => Config::read(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse(r#"
+            reasons = ["gothic", "reasons.txt"]
+            algorithm = "numerology"
+            max_age = 90
+            date_order = "ymd"
+            locale = "de"
+            color = "never"
+            theme_color = "hacker"
+            slack_secret = "8f742231b10e8888"
+        "#).unwrap();
+
+        assert_eq!(config, Config {
+            reasons: vec![PathBuf::from("gothic"), PathBuf::from("reasons.txt")],
+            algorithm: Some(Algorithm::Numerology),
+            max_age: Some(90),
+            date_order: Some(DateOrder::Ymd),
+            lang: Some(Language::De),
+            color: Some(ColorChoice::Never),
+            theme_color: Some(Palette::Hacker),
+            slack_secret: Some("8f742231b10e8888".to_string()),
+        });
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        let config = Config::parse("date_format = \"mdy\"").unwrap();
+        assert_eq!(config.date_order, Some(DateOrder::Mdy));
+        assert!(Config::parse("colour = \"never\"").is_err());
+        assert!(Config::parse("algorithm = \"random\"").is_err());
+        assert!(Config::parse("max_age = 0").is_err());
+    }
+}
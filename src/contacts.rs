@@ -0,0 +1,270 @@
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use crate::batch::Person;
+use crate::date::{Date, DateOrder};
+use crate::reasons;
+
+/// People from address book export.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Contacts {
+    /// Contacts with birthdays
+    pub people: Vec<Person>,
+    /// Number of contacts without birthdays or with birthdays without year
+    pub skipped: usize,
+}
+
+impl Contacts {
+    /// Adds the contact if it has birthday with year, counts it as skipped
+    /// otherwise. The birthday is written in the order.
+    fn add(&mut self, name: String, birthday: &str, order: DateOrder) {
+        match parse_birthday(birthday) {
+            Some(date) if !name.is_empty() => self.people.push(Person {
+                name,
+                birthday: Some(order.format(&date)),
+                age: None,
+                id: None,
+            }),
+            _ => self.skipped += 1,
+        }
+    }
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Returns birthday from `YYYY-MM-DD` or `YYYYMMDD` date, optionally with
+/// time after `T`. Returns [`None`] if the year is unknown, e.g. `--02-01`.
+///
+/// # Example
+///
+/// ```
+/// use death::contacts::parse_birthday;
+/// use death::date::Date;
+///
+/// let date = Date::build(1990, 2, 1).ok();
+///
+/// assert_eq!(parse_birthday("1990-02-01"), date);
+/// assert_eq!(parse_birthday("19900201T000000Z"), date);
+/// assert_eq!(parse_birthday("--0201"), None);
+/// ```
+pub fn parse_birthday(text: &str) -> Option<Date> {
+    let date = text.trim().split('T').next()?.replace('-', "");
+    if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Date::build(
+        date[..4].parse().ok()?, date[4..6].parse().ok()?,
+        date[6..].parse().ok()?,
+    ).ok()
+}
+
+/// Returns value of vCard property with escaped characters replaced.
+fn unescape(value: &str) -> String {
+    let mut res = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => res.push(' '),
+            Some(c) => res.push(c),
+            None => (),
+        }
+    }
+    res
+}
+
+/// Returns name from structured vCard `N` property:
+/// `family;given;additional;prefixes;suffixes`.
+fn structured_name(value: &str) -> String {
+    let parts: Vec<&str> = value.split(';').collect();
+    [1, 2, 0].iter()
+        .filter_map(|&i| parts.get(i))
+        .map(|p| unescape(p).trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns contacts from vCard file with their formatted names, or
+/// structured ones if there are none, and birthdays written in the order.
+///
+/// # Example
+///
+/// ```
+/// use death::contacts::parse_vcard;
+/// use death::date::DateOrder;
+///
+/// let vcard = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Alice Smith\r\n\
+///     BDAY:1990-02-01\r\nEND:VCARD\r\n\
+///     BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Bob;;;\r\nEND:VCARD\r\n";
+/// let contacts = parse_vcard(vcard, DateOrder::Dmy);
+///
+/// assert_eq!(contacts.people[0].name, "Alice Smith");
+/// assert_eq!(contacts.people[0].birthday.as_deref(), Some("01/02/1990"));
+/// assert_eq!(contacts.skipped, 1);
+/// ```
+pub fn parse_vcard(contents: &str, order: DateOrder) -> Contacts {
+    // Long lines are folded into several starting with a space or a tab
+    let mut lines: Vec<String> = vec![];
+    for line in contents.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut res = Contacts { people: vec![], skipped: 0 };
+    let (mut name, mut structured, mut birthday) = (None, None, None);
+    for line in &lines {
+        let (property, value) = match line.split_once(':') {
+            Some(v) => v,
+            None => continue,
+        };
+        // Properties may have a group before `.` and parameters after `;`
+        let property = property.split(';').next().unwrap_or("");
+        let property = property.rsplit('.').next().unwrap_or("");
+        match property.to_ascii_uppercase().as_str() {
+            "BEGIN" => (name, structured, birthday) = (None, None, None),
+            "FN" => name = Some(unescape(value).trim().to_string()),
+            "N" => structured = Some(structured_name(value)),
+            "BDAY" => birthday = Some(value.to_string()),
+            "END" => {
+                let name = name.take()
+                    .filter(|n| !n.is_empty())
+                    .or(structured.take())
+                    .unwrap_or_default();
+                res.add(name, birthday.as_deref().unwrap_or(""), order);
+            },
+            _ => (),
+        }
+    }
+    res
+}
+
+/// Returns contacts from Google Contacts CSV export with their names and
+/// birthdays written in the order. The name is taken from `Name` column, or
+/// first, middle and last name columns.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot parse the CSV or it has no
+/// `Birthday` column.
+///
+/// # Example
+///
+/// ```
+/// use death::contacts::parse_google_csv;
+/// use death::date::DateOrder;
+///
+/// let csv = "First Name,Last Name,Birthday\n\
+///     Alice,Smith,1990-02-01\nBob,Doe, --02-01\n";
+/// let contacts = parse_google_csv(csv, DateOrder::Ymd).unwrap();
+///
+/// assert_eq!(contacts.people[0].name, "Alice Smith");
+/// assert_eq!(contacts.people[0].birthday.as_deref(), Some("1990/02/01"));
+/// assert_eq!(contacts.skipped, 1);
+/// ```
+pub fn parse_google_csv(contents: &str, order: DateOrder)
+-> Result<Contacts, Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+    let headers = reader.headers()
+        .map_err(|e| invalid_data(format!("Invalid CSV: {}", e)))?
+        .clone();
+    let column = |names: &[&str]| headers.iter()
+        .position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)));
+
+    let birthday = column(&["Birthday"]).ok_or_else(|| {
+        invalid_data(String::from("No `Birthday` column found"))
+    })?;
+    let name = column(&["Name"]);
+    let parts: Vec<usize> = [
+        &["First Name", "Given Name"][..],
+        &["Middle Name", "Additional Name"],
+        &["Last Name", "Family Name"],
+    ].iter().filter_map(|names| column(names)).collect();
+
+    let mut res = Contacts { people: vec![], skipped: 0 };
+    for record in reader.records() {
+        let record = record
+            .map_err(|e| invalid_data(format!("Invalid CSV: {}", e)))?;
+        let get = |i: usize| record.get(i).unwrap_or("");
+        let full_name = match name.map(get).filter(|n| !n.is_empty()) {
+            Some(v) => v.to_string(),
+            None => parts.iter()
+                .map(|&i| get(i))
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        res.add(full_name, get(birthday), order);
+    }
+    Ok(res)
+}
+
+/// Returns `true` if the file has `.vcf` or `.vcard` extension.
+pub fn is_vcard(path: &Path) -> bool {
+    path.extension().is_some_and(|e| {
+        e.eq_ignore_ascii_case("vcf") || e.eq_ignore_ascii_case("vcard")
+    })
+}
+
+/// Returns contacts from vCard file or Google Contacts CSV export, detected
+/// by extension. (see [`reasons::read_contents`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file, or it has
+/// no contacts at all.
+pub fn read_contacts(file_path: &Path, order: DateOrder)
+-> Result<Contacts, Error> {
+    let (contents, _) = reasons::read_contents(file_path)?;
+    let res = match is_vcard(file_path) {
+        true => parse_vcard(&contents, order),
+        false => parse_google_csv(&contents, order)?,
+    };
+
+    if res.people.is_empty() && res.skipped == 0 {
+        return Err(invalid_data(String::from("No contacts found")));
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_files() {
+        let vcard = read_contacts(
+            Path::new("tests/contacts.vcf"), DateOrder::Dmy
+        ).unwrap();
+        let csv = read_contacts(
+            Path::new("tests/contacts.csv"), DateOrder::Dmy
+        ).unwrap();
+
+        assert_eq!(vcard, csv);
+        assert_eq!(vcard.skipped, 2);
+        let names: Vec<&str> = vcard.people.iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["Alice Smith", "Carol Jane Doe"]);
+        assert_eq!(vcard.people[1].birthday.as_deref(), Some("31/12/1985"));
+
+        assert_eq!(unescape("a\\, b\\;c\\nd"), "a, b;c d");
+        assert!(parse_google_csv("Name\nAlice\n", DateOrder::Dmy).is_err());
+        assert!(read_contacts(
+            Path::new("tests/empty.txt"), DateOrder::Dmy
+        ).is_err());
+    }
+}
@@ -1,6 +1,10 @@
-use chrono::{Local, Datelike};
+use chrono::{Local, Datelike, NaiveDate};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{cmp, fmt};
 
+use crate::error::DeathError;
+use crate::locale::{self, Message};
+
 #[derive(Debug)]
 #[derive(PartialEq)]
 pub enum ParseError {
@@ -12,6 +16,73 @@ pub enum ParseError {
     InvalidDay,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ParseError::SeparatorNotFound => "separator not found",
+            ParseError::InvalidPartsCount => "date must have 3 parts",
+            ParseError::NumberConversionError => "part is not a number",
+            ParseError::InvalidYear => "invalid year",
+            ParseError::InvalidMonth => "invalid month",
+            ParseError::InvalidDay => "invalid day",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Order of day, month and year in dates written by user.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateOrder {
+    /// Day, month, year, e.g. 27/10/2023
+    #[default]
+    Dmy,
+    /// Month, day, year, e.g. 10/27/2023
+    Mdy,
+    /// Year, month, day, e.g. 2023-10-27
+    Ymd,
+}
+
+impl DateOrder {
+    /// Returns the pattern to show user, e.g. `DD/MM/YYYY`.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            DateOrder::Dmy => "DD/MM/YYYY",
+            DateOrder::Mdy => "MM/DD/YYYY",
+            DateOrder::Ymd => "YYYY/MM/DD",
+        }
+    }
+
+    /// Returns the date written in the order, so it is parsed back the same.
+    /// (see [`Date::parse_in`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::{Date, DateOrder};
+    ///
+    /// let date = Date::build(2023, 10, 7).unwrap();
+    ///
+    /// assert_eq!(DateOrder::Mdy.format(&date), "10/07/2023");
+    /// assert_eq!(Date::parse_in("10/07/2023", DateOrder::Mdy), Ok(date));
+    /// ```
+    pub fn format(&self, date: &Date) -> String {
+        let (year, month, day) = (date.year(), date.month(), date.day());
+        match self {
+            DateOrder::Dmy => format!("{:02}/{:02}/{}", day, month, year),
+            DateOrder::Mdy => format!("{:02}/{:02}/{}", month, day, year),
+            DateOrder::Ymd => format!("{}/{:02}/{:02}", year, month, day),
+        }
+    }
+}
+
 #[derive(Debug)]
 #[derive(PartialEq)]
 #[derive(PartialOrd)]
@@ -59,7 +130,7 @@ impl Date {
             return Err(ParseError::InvalidYear);
         }
 
-        if month < 1 || month > 12 {
+        if !(1..=12).contains(&month) {
             return Err(ParseError::InvalidMonth);
         }
         
@@ -86,9 +157,30 @@ impl Date {
     ///
     /// assert_eq!(Date::build(2023, 10, 27), date);
     /// ```
-    pub fn parse(s: &String) -> Result<Date, ParseError> {
+    pub fn parse(s: &str) -> Result<Date, ParseError> {
+        Date::parse_in(s, DateOrder::Dmy)
+    }
+
+    /// Creates a new [`Date`] object from string with day, month and year in
+    /// the order. (see [`Date::parse`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::date::ParseError`] if string contains invalid date.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::{Date, DateOrder};
+    ///
+    /// assert_eq!(Date::parse_in("10/27/2023", DateOrder::Mdy),
+    ///     Date::build(2023, 10, 27));
+    /// assert_eq!(Date::parse_in("2023-10-27", DateOrder::Ymd),
+    ///     Date::build(2023, 10, 27));
+    /// ```
+    pub fn parse_in(s: &str, order: DateOrder) -> Result<Date, ParseError> {
         // Find a separator
-        let separators = vec!['.', '/', '-', ' '];
+        let separators = ['.', '/', '-', ' '];
         let mut sep: Option<char> = None;
         for &separator in separators.iter() {
             if s.contains(separator) {
@@ -121,7 +213,13 @@ impl Date {
             return Err(ParseError::InvalidPartsCount);
         }
 
-        Date::build(numbers[2], numbers[1] as u8, numbers[0] as u8)
+        let (year, month, day) = match order {
+            DateOrder::Dmy => (numbers[2], numbers[1], numbers[0]),
+            DateOrder::Mdy => (numbers[2], numbers[0], numbers[1]),
+            DateOrder::Ymd => (numbers[0], numbers[1], numbers[2]),
+        };
+
+        Date::build(year, month as u8, day as u8)
     }
 
     /// Returns `true` if the year is leap.
@@ -136,7 +234,8 @@ impl Date {
     /// assert!(!Date::is_leap_year(1900));
     /// ```
     pub fn is_leap_year(year: u16) -> bool {
-        year % 4 == 0 && year % 100 != 0 || year % 400 == 0
+        year.is_multiple_of(4) && !year.is_multiple_of(100) ||
+        year.is_multiple_of(400)
     }
 
     /// Returns `true` if the year of current date is leap.
@@ -157,7 +256,7 @@ impl Date {
     /// assert_eq!(Date::max_day_of(2016, 2), 29);
     /// ```
     pub fn max_day_of(year: u16, month: u8) -> u8 {
-        let a = vec![1, 3, 5, 7, 8, 10, 12];
+        let a = [1, 3, 5, 7, 8, 10, 12];
         
         if month == 2 {
             if Date::is_leap_year(year) {
@@ -178,7 +277,8 @@ impl Date {
         Date::max_day_of(self.year, self.month)
     }
 
-    /// Returns month name
+    /// Returns month name in language of messages.
+    /// (see [`locale::set_language`])
     ///
     /// # Example
     /// ```
@@ -189,11 +289,43 @@ impl Date {
     /// assert_eq!(date.get_month_name(), String::from("December"));
     /// ```
     pub fn get_month_name(&self) -> &str {
-        let months = vec![
-            "January", "February", "March", "April", "May", "June", "July",
-            "August", "September", "October", "November", "December"
-        ];
-        months[(self.month - 1) as usize]
+        locale::language().month_name(self.month)
+    }
+
+    /// Returns name of the day of the week in language of messages.
+    /// (see [`locale::set_language`])
+    ///
+    /// # Example
+    /// ```
+    /// use death::date::Date;
+    ///
+    /// let date = Date::build(2023, 10, 27).unwrap();
+    ///
+    /// assert_eq!(date.get_weekday_name(), "Friday");
+    /// ```
+    pub fn get_weekday_name(&self) -> &str {
+        let weekday = self.naive().weekday().num_days_from_monday();
+        locale::language().weekday_name(weekday as u8)
+    }
+
+    /// Returns zodiac sign of someone born on the date.
+    ///
+    /// # Example
+    /// ```
+    /// use death::date::Date;
+    ///
+    /// let date = Date::build(1990, 1, 1).unwrap();
+    ///
+    /// assert_eq!(date.zodiac_sign(), "Capricorn");
+    /// assert_eq!(date.add_days(79).zodiac_sign(), "Aries");
+    /// ```
+    pub fn zodiac_sign(&self) -> &'static str {
+        let i = self.month as usize - 1;
+        if self.day >= ZODIAC_FIRST_DAYS[i] {
+            ZODIAC_SIGNS[i]
+        } else {
+            ZODIAC_SIGNS[(i + 11) % 12]
+        }
     }
 
     /// Returns copy of [`Date`] object with month number increased.
@@ -298,8 +430,8 @@ impl Date {
     /// assert_eq!(a.years_from(c), 17);
     /// ```
     pub fn years_from(&self, other: Date) -> u16 {
-        let left = cmp::min(self.clone(), other.clone());
-        let right = cmp::max(self.clone(), other.clone());
+        let left = cmp::min(*self, other);
+        let right = cmp::max(*self, other);
         let mut diff = right.year() - left.year();
 
         if right.month() < left.month() ||
@@ -310,6 +442,64 @@ impl Date {
         diff
     }
 
+    /// Returns a number of days from the other date.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::Date;
+    ///
+    /// let a = Date::build(2015, 12, 30).unwrap();
+    /// let b = Date::build(2016, 3, 1).unwrap();
+    ///
+    /// assert_eq!(a.days_from(b), 62);
+    /// assert_eq!(b.days_from(a), 62);
+    /// ```
+    pub fn days_from(&self, other: Date) -> u32 {
+        let days = self.naive() - other.naive();
+        days.num_days().unsigned_abs() as u32
+    }
+
+    /// Returns the date `days` days later, or earlier if `days` is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::Date;
+    ///
+    /// let date = Date::build(2016, 2, 28).unwrap();
+    ///
+    /// assert_eq!(date.add_days(2), Date::build(2016, 3, 1).unwrap());
+    /// assert_eq!(date.add_days(-59), Date::build(2015, 12, 31).unwrap());
+    /// ```
+    pub fn add_days(&self, days: i64) -> Date {
+        let date = self.naive() + chrono::Duration::days(days);
+        Date {
+            year: date.year() as u16,
+            month: date.month() as u8,
+            day: date.day() as u8,
+        }
+    }
+
+    fn naive(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(
+            self.year as i32, self.month as u32, self.day as u32
+        ).unwrap()
+    }
+
+    /// Returns date in ISO 8601 format: `YYYY-MM-DD`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::Date;
+    ///
+    /// assert_eq!(Date::build(2015, 1, 7).unwrap().to_iso(), "2015-01-07");
+    /// ```
+    pub fn to_iso(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
     /// Returns year value.
     ///
     /// # Example
@@ -352,7 +542,152 @@ impl Date {
 
 impl fmt::Display for Date {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", self.day(), self.get_month_name(), self.year())
+        write!(f, "{}", locale::fill(
+            locale::tr(Message::Date),
+            &[&self.day(), &self.get_month_name(), &self.year()]
+        ))
+    }
+}
+
+impl Serialize for Date {
+    /// Serializes date in ISO 8601 format. (see [`Date::to_iso`])
+    fn serialize<S: Serializer>(&self, serializer: S)
+    -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_iso())
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    /// Deserializes date in ISO 8601 format. (see [`Date::to_iso`])
+    fn deserialize<D: Deserializer<'de>>(deserializer: D)
+    -> Result<Date, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Date::parse_in(&s, DateOrder::Ymd).map_err(|_| {
+            serde::de::Error::custom(format!("invalid date: {}", s))
+        })
+    }
+}
+
+/// Parse birthday from string with day, month and year in the order. Age
+/// must be less than `max_age`.
+///
+/// # Errors
+///
+/// Returns [`DeathError`] of the date, the age or the birthday in the
+/// future, its message is translated.
+pub fn parse_birthday(string: &str, max_age: u16, order: DateOrder)
+-> Result<Date, DeathError> {
+    let today = Date::today();
+    let birthday = Date::parse_in(string, order)
+        .map_err(|error| DeathError::Date { error, order })?;
+    let age = birthday.years_from(Date::today());
+    if age >= max_age {
+        return Err(DeathError::AgeTooBig { age, max_age });
+    }
+    if today < birthday {
+        return Err(DeathError::BirthdayInFuture);
+    }
+    Ok(birthday)
+}
+
+/// Signs of the zodiac starting in each month, from January.
+const ZODIAC_SIGNS: [&str; 12] = [
+    "Aquarius", "Pisces", "Aries", "Taurus", "Gemini", "Cancer", "Leo",
+    "Virgo", "Libra", "Scorpio", "Sagittarius", "Capricorn",
+];
+
+/// Days of month the signs of [`ZODIAC_SIGNS`] start on.
+const ZODIAC_FIRST_DAYS: [u8; 12] = [
+    20, 19, 21, 20, 21, 21, 23, 23, 23, 23, 22, 22,
+];
+
+/// How to write hours of time.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum TimeFormat {
+    /// 12-hour clock, e.g. 3:47 AM
+    #[cfg_attr(feature = "cli", value(name = "12"))]
+    H12,
+    /// 24-hour clock, e.g. 03:47
+    #[cfg_attr(feature = "cli", value(name = "24"))]
+    H24,
+}
+
+/// Time of day with minute precision.
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Clone, Copy)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    /// Returns [`Time`] object with given values if they are valid.
+    ///
+    /// # Example
+    /// ```
+    /// use death::date::Time;
+    ///
+    /// assert!(Time::build(23, 59).is_some());
+    /// assert!(Time::build(24, 0).is_none());
+    /// ```
+    pub fn build(hour: u8, minute: u8) -> Option<Time> {
+        (hour < 24 && minute < 60).then_some(Time { hour, minute })
+    }
+
+    /// Returns hour value from 0 to 23.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns minute value from 0 to 59.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns time written in the format, e.g. `03:47` or `3:47 AM`.
+    ///
+    /// # Example
+    /// ```
+    /// use death::date::{Time, TimeFormat};
+    ///
+    /// let time = Time::build(15, 7).unwrap();
+    ///
+    /// assert_eq!(time.format(TimeFormat::H24), "15:07");
+    /// assert_eq!(time.format(TimeFormat::H12), "3:07 PM");
+    /// ```
+    pub fn format(&self, format: TimeFormat) -> String {
+        match format {
+            TimeFormat::H24 => format!("{:02}:{:02}", self.hour, self.minute),
+            TimeFormat::H12 => {
+                let hour = match self.hour % 12 {
+                    0 => 12,
+                    hour => hour,
+                };
+                let suffix = if self.hour < 12 { "AM" } else { "PM" };
+                format!("{}:{:02} {}", hour, self.minute, suffix)
+            },
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    /// Formats time in format of messages. (see [`locale::time_format`])
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(locale::time_format()))
+    }
+}
+
+impl Serialize for Time {
+    /// Serializes time in format of messages.
+    /// (see [`locale::time_format`])
+    fn serialize<S: Serializer>(&self, serializer: S)
+    -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
     }
 }
 
@@ -0,0 +1,167 @@
+use std::{error, fmt, io};
+
+use crate::date::{DateOrder, ParseError};
+use crate::locale::{self, Message};
+
+/// Error of predictions and everything they are made from.
+///
+/// Library users can match on the kind of failure, the binary maps every
+/// kind to its exit code. Messages of birthdays and ages are translated.
+///
+/// # Example
+///
+/// ```
+/// use death::date::{self, DateOrder, ParseError};
+/// use death::error::DeathError;
+///
+/// match date::parse_birthday("01.13.1990", 120, DateOrder::Dmy) {
+///     Err(DeathError::Date { error: ParseError::InvalidMonth, .. }) => (),
+///     other => panic!("unexpected {:?}", other),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum DeathError {
+    /// Birthday that can't be parsed with day, month and year in the order
+    Date { error: ParseError, order: DateOrder },
+    /// Age that is not less than the max age
+    AgeTooBig { age: u16, max_age: u16 },
+    /// Birthday after today
+    BirthdayInFuture,
+    /// Death reasons that can't be read or used
+    Reasons(io::Error),
+    /// Other files that can't be read or written
+    Io(io::Error),
+    /// Config file that can't be read or parsed
+    Config(io::Error),
+    /// Invalid input, e.g. query or body of a request
+    Invalid(String),
+    /// Input that is closed while asking
+    InputClosed,
+    /// Answers that are invalid too many times, with the last one's error
+    TooManyAttempts(Box<DeathError>),
+    /// Command-line arguments that can't be parsed, or help and version
+    /// that are asked for
+    #[cfg(feature = "cli")]
+    Args(clap::Error),
+}
+
+impl DeathError {
+    /// Returns exit code of the binary for the error.
+    #[cfg(feature = "cli")]
+    pub fn exit_code(&self) -> i32 {
+        use crate::cli;
+
+        match self {
+            DeathError::Date { .. } | DeathError::AgeTooBig { .. }
+            | DeathError::BirthdayInFuture => cli::EXIT_DATE,
+            DeathError::Reasons(_) => cli::EXIT_REASONS,
+            DeathError::Io(_) => cli::EXIT_IO,
+            DeathError::Config(_) | DeathError::Invalid(_)
+            | DeathError::InputClosed => cli::EXIT_USAGE,
+            DeathError::TooManyAttempts(e) => e.exit_code(),
+            DeathError::Args(e) => e.exit_code(),
+        }
+    }
+}
+
+impl fmt::Display for DeathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            DeathError::Date {
+                error: ParseError::InvalidPartsCount, order
+            } => locale::fill(
+                locale::tr(Message::InvalidPartsCount), &[&order.pattern()]
+            ),
+            DeathError::Date { error, .. } => locale::tr(match error {
+                ParseError::SeparatorNotFound => Message::InvalidSeparator,
+                ParseError::NumberConversionError => Message::InvalidNumber,
+                ParseError::InvalidYear => Message::InvalidYear,
+                ParseError::InvalidMonth => Message::InvalidMonth,
+                ParseError::InvalidDay | ParseError::InvalidPartsCount => {
+                    Message::InvalidDay
+                },
+            }).to_string(),
+            DeathError::AgeTooBig { age, max_age } => locale::fill(
+                locale::tr(Message::AgeTooBig), &[age, max_age]
+            ),
+            DeathError::BirthdayInFuture => {
+                locale::tr(Message::BirthdayInFuture).to_string()
+            },
+            DeathError::Reasons(e) | DeathError::Io(e)
+            | DeathError::Config(e) => e.to_string(),
+            DeathError::Invalid(message) => message.clone(),
+            DeathError::InputClosed => {
+                locale::tr(Message::InputClosed).to_string()
+            },
+            DeathError::TooManyAttempts(_) => {
+                locale::tr(Message::TooManyAttempts).to_string()
+            },
+            #[cfg(feature = "cli")]
+            DeathError::Args(e) => e.to_string(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl error::Error for DeathError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DeathError::Date { error, .. } => Some(error),
+            DeathError::Reasons(e) | DeathError::Io(e)
+            | DeathError::Config(e) => Some(e),
+            DeathError::TooManyAttempts(e) => Some(e.as_ref()),
+            #[cfg(feature = "cli")]
+            DeathError::Args(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for DeathError {
+    /// Returns error of the date written in the default order.
+    fn from(error: ParseError) -> DeathError {
+        DeathError::Date { error, order: DateOrder::default() }
+    }
+}
+
+impl From<io::Error> for DeathError {
+    fn from(error: io::Error) -> DeathError {
+        DeathError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn messages_and_sources() {
+        let error = DeathError::Date {
+            error: ParseError::InvalidPartsCount, order: DateOrder::Ymd,
+        };
+        assert!(error.to_string().contains("YYYY/MM/DD"));
+        assert!(error.source().is_some());
+
+        let error = DeathError::from(ParseError::InvalidDay);
+        assert_eq!(error.to_string(), "Invalid day.");
+
+        let error = DeathError::AgeTooBig { age: 130, max_age: 120 };
+        assert_eq!(
+            error.to_string(), "Your age 130 must be less than max age 120."
+        );
+        assert!(error.source().is_none());
+
+        let error = DeathError::from(io::Error::other("File is empty"));
+        assert!(matches!(error, DeathError::Io(_)));
+        assert_eq!(error.to_string(), "File is empty");
+        assert!(error.source().is_some());
+
+        let last = DeathError::Invalid("Unknown answer `maybe`".to_string());
+        let error = DeathError::TooManyAttempts(Box::new(last));
+        assert_eq!(error.to_string(), "Too many invalid attempts.");
+        assert_eq!(
+            error.source().unwrap().to_string(), "Unknown answer `maybe`"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+use crate::algorithm::Algorithm;
+use crate::batch::Person;
+use crate::date::{self, DateOrder};
+use crate::error::DeathError;
+use crate::locale::Language;
+use crate::prediction::Prediction;
+use crate::reasons::{self, ReasonSet};
+use crate::user::User;
+
+/// User to predict for, made by `death_user_new`.
+pub struct DeathUser(User);
+
+/// Death reasons, made by `death_reasons_default` or `death_reasons_load`.
+pub struct DeathReasons(ReasonSet);
+
+thread_local! {
+    /// Message of the last error in the thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const {
+        RefCell::new(None)
+    };
+}
+
+/// Keeps the message for `death_last_error`.
+fn set_error(message: &str) {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Keeps message of the error for `death_last_error` and returns null.
+fn fail<T>(error: DeathError) -> *mut T {
+    set_error(&error.to_string());
+    ptr::null_mut()
+}
+
+/// Returns what the body of exported function returns, or `sentinel` if it
+/// panics, so panics never unwind into C.
+fn guard<R>(sentinel: R, body: impl FnOnce() -> R) -> R {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        set_error("Internal error");
+        sentinel
+    })
+}
+
+/// Returns text of the C string, [`None`] if the pointer is null.
+///
+/// # Safety
+///
+/// `text` must be null or point to a C string.
+unsafe fn text<'a>(text: *const c_char)
+-> Result<Option<&'a str>, DeathError> {
+    if text.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(text).to_str().map(Some)
+        .map_err(|_| DeathError::Invalid("Text is not valid UTF-8".into()))
+}
+
+/// Returns message of the last error in the thread, or null if there was
+/// none. It is valid until the next error and must not be freed.
+#[no_mangle]
+pub extern "C" fn death_last_error() -> *const c_char {
+    guard(ptr::null(), || LAST_ERROR.with(|e| {
+        e.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr())
+    }))
+}
+
+/// Returns default death reasons in English. Free them with
+/// `death_reasons_free`.
+#[no_mangle]
+pub extern "C" fn death_reasons_default() -> *mut DeathReasons {
+    guard(ptr::null_mut(), || {
+        let reasons = ReasonSet::defaults(Language::En);
+        Box::into_raw(Box::new(DeathReasons(reasons)))
+    })
+}
+
+/// Returns death reasons read from the file in any supported format, or
+/// null if cannot read or parse it. Only files are read, `-` is not stdin
+/// and names of installed packs are not looked up. Free them with
+/// `death_reasons_free`.
+///
+/// # Safety
+///
+/// `path` must point to a C string.
+#[no_mangle]
+pub unsafe extern "C" fn death_reasons_load(path: *const c_char)
+-> *mut DeathReasons {
+    guard(ptr::null_mut(), || {
+        let path = match text(path) {
+            Ok(Some(v)) => Path::new(v),
+            Ok(None) => return fail(DeathError::Invalid("No path".into())),
+            Err(e) => return fail(e),
+        };
+        match reasons::read_file(path) {
+            Ok(v) => Box::into_raw(Box::new(DeathReasons(v))),
+            Err(e) => fail(DeathError::Reasons(e)),
+        }
+    })
+}
+
+/// Frees death reasons. Null is ignored.
+///
+/// # Safety
+///
+/// `reasons` must be null or made by this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn death_reasons_free(reasons: *mut DeathReasons) {
+    guard((), || if !reasons.is_null() {
+        drop(Box::from_raw(reasons));
+    })
+}
+
+/// Returns user with the name and the birthday with day, month and year,
+/// e.g. `01.02.1990`, or null if the birthday is invalid. Birthday may be
+/// null if it is unknown. Death reasons are copied, default ones are used
+/// if they are null. Free the user with `death_user_free`.
+///
+/// # Safety
+///
+/// `name` must point to a C string, `birthday` must be null or point to a
+/// C string and `reasons` must be null or made by this library.
+#[no_mangle]
+pub unsafe extern "C" fn death_user_new(
+    name: *const c_char, birthday: *const c_char,
+    reasons: *const DeathReasons,
+) -> *mut DeathUser {
+    guard(ptr::null_mut(), || {
+        let (name, birthday) = match (text(name), text(birthday)) {
+            (Ok(Some(name)), Ok(birthday)) => (name, birthday),
+            (Ok(None), _) => {
+                return fail(DeathError::Invalid("No name".into()))
+            },
+            (Err(e), _) | (_, Err(e)) => return fail(e),
+        };
+        let reasons = match reasons.as_ref() {
+            Some(reasons) => reasons.0.clone(),
+            None => ReasonSet::defaults(Language::En),
+        };
+        let person = Person {
+            name: name.to_string(),
+            birthday: birthday.map(String::from),
+            age: None,
+            id: None,
+        };
+        match person.to_user(reasons, date::MAX_AGE, DateOrder::Dmy) {
+            Ok(v) => Box::into_raw(Box::new(DeathUser(v))),
+            Err(e) => fail(e),
+        }
+    })
+}
+
+/// Frees the user. Null is ignored.
+///
+/// # Safety
+///
+/// `user` must be null or made by this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn death_user_free(user: *mut DeathUser) {
+    guard((), || if !user.is_null() {
+        drop(Box::from_raw(user));
+    })
+}
+
+/// Returns prediction for the user as JSON object with the same fields as
+/// `death --output json` prints, or null if the user is null. Free it with
+/// `death_string_free`.
+///
+/// # Safety
+///
+/// `user` must be null or made by this library.
+#[no_mangle]
+pub unsafe extern "C" fn death_predict(user: *const DeathUser)
+-> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(user) = user.as_ref() else {
+            return fail(DeathError::Invalid("No user".into()));
+        };
+        let prediction = Prediction::new(
+            &user.0, Algorithm::default(), false
+        );
+        match CString::new(prediction.to_json()) {
+            Ok(v) => v.into_raw(),
+            Err(e) => fail(DeathError::Invalid(e.to_string())),
+        }
+    })
+}
+
+/// Frees the string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `text` must be null or returned by `death_predict` and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn death_string_free(text: *mut c_char) {
+    guard((), || if !text.is_null() {
+        drop(CString::from_raw(text));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predict_for_user() {
+        let name = CString::new("Alice").unwrap();
+        let birthday = CString::new("01.02.1990").unwrap();
+        let invalid = CString::new("31.02.1990").unwrap();
+        let path = CString::new("tests/read_file.txt").unwrap();
+
+        let reasons = reasons::read_many(&["tests/read_file.txt".into()])
+            .unwrap();
+        let mut person = Person::new("Alice");
+        person.birthday = Some("01.02.1990".to_string());
+        let user = person.to_user(reasons, date::MAX_AGE, DateOrder::Dmy)
+            .unwrap();
+        let expected = Prediction::new(&user, Algorithm::default(), false);
+
+        unsafe {
+            let reasons = death_reasons_load(path.as_ptr());
+            assert!(!reasons.is_null());
+            let user = death_user_new(
+                name.as_ptr(), birthday.as_ptr(), reasons
+            );
+            death_reasons_free(reasons);
+            assert!(!user.is_null());
+
+            let json = death_predict(user);
+            let text = CStr::from_ptr(json).to_str().unwrap();
+            assert_eq!(text, expected.to_json());
+            death_string_free(json);
+            death_user_free(user);
+
+            let user = death_user_new(
+                name.as_ptr(), invalid.as_ptr(), ptr::null()
+            );
+            assert!(user.is_null());
+            let error = CStr::from_ptr(death_last_error()).to_str().unwrap();
+            assert_eq!(error, "Invalid day.");
+
+            let stdin = CString::new("-").unwrap();
+            assert!(death_reasons_load(stdin.as_ptr()).is_null());
+            let error = CStr::from_ptr(death_last_error()).to_str().unwrap();
+            assert!(error.ends_with("-: No such file"), "{}", error);
+        }
+
+        let panicked = guard(ptr::null_mut::<DeathUser>(), || panic!("test"));
+        assert!(panicked.is_null());
+        let error = unsafe { CStr::from_ptr(death_last_error()) };
+        assert_eq!(error.to_str().unwrap(), "Internal error");
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/death.h"));
+        assert_eq!(
+            include_str!("../include/death.h"), generated,
+            "copy {}/death.h to include/death.h", env!("OUT_DIR")
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use serde::Serialize;
+
+use crate::date::Date;
+use crate::reasons::Reason;
+use crate::user::User;
+
+/// Highest threat level.
+pub const MAX_THREAT: u8 = 5;
+
+/// Things that may save your life today.
+pub const LUCKY_ITEMS: [&str; 16] = [
+    "a bicycle helmet",
+    "an umbrella",
+    "a fire extinguisher",
+    "a rubber duck",
+    "a flashlight",
+    "a first aid kit",
+    "garlic",
+    "a whistle",
+    "sensible shoes",
+    "a spare sock",
+    "a lucky coin",
+    "a rope",
+    "sunscreen",
+    "a teapot",
+    "an oven mitt",
+    "a life jacket",
+];
+
+/// Daily death horoscope.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct Fortune {
+    /// User's name
+    pub name: String,
+    /// Day of the horoscope
+    pub date: Date,
+    /// How dangerous the day is, from 1 to [`MAX_THREAT`]
+    pub threat: u8,
+    /// Death reason to beware of today
+    #[serde(flatten)]
+    pub reason: Reason,
+    /// Thing that may save your life today
+    pub item: &'static str,
+}
+
+impl Fortune {
+    /// Returns horoscope of the user for the day. It is the same for the
+    /// same user all day long and changes the next day.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date::Date;
+    /// use death::fortune::{Fortune, MAX_THREAT};
+    /// use death::reasons::ReasonSet;
+    /// use death::user::User;
+    ///
+    /// let user = User::new(1234567890, 30, ReasonSet::default());
+    /// let date = Date::build(2024, 1, 1).unwrap();
+    ///
+    /// let fortune = Fortune::of_day(&user, date);
+    ///
+    /// assert_eq!(fortune, Fortune::of_day(&user, date));
+    /// assert!((1..=MAX_THREAT).contains(&fortune.threat));
+    /// ```
+    pub fn of_day(user: &User, date: Date) -> Fortune {
+        let mut hasher = DefaultHasher::new();
+        (user.id(), date.to_iso()).hash(&mut hasher);
+        let seed = hasher.finish();
+
+        Fortune {
+            name: user.name().to_string(),
+            date,
+            threat: (seed % MAX_THREAT as u64) as u8 + 1,
+            reason: user.death_reasons().pick(seed.rotate_left(16)).clone(),
+            item: LUCKY_ITEMS[
+                (seed.rotate_right(32) % LUCKY_ITEMS.len() as u64) as usize
+            ],
+        }
+    }
+
+    /// Returns the threat level drawn with skulls, e.g. `☠☠☠··`.
+    pub fn threat_meter(&self) -> String {
+        let threat = self.threat.min(MAX_THREAT) as usize;
+        format!(
+            "{}{}", "☠".repeat(threat),
+            "·".repeat(MAX_THREAT as usize - threat)
+        )
+    }
+}
+
+impl fmt::Display for Fortune {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name.as_str() {
+            "" => writeln!(f, "Death horoscope for {}", self.date)?,
+            name => writeln!(
+                f, "Death horoscope of {} for {}", name, self.date
+            )?,
+        }
+        writeln!(
+            f, "Threat level: {} ({}/{})", self.threat_meter(), self.threat,
+            MAX_THREAT
+        )?;
+        writeln!(f, "Reason of the day: {}", self.reason)?;
+        write!(f, "Lucky survival item: {}", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reasons::ReasonSet;
+
+    #[test]
+    fn daily() {
+        let mut user = User::new(0, 30, ReasonSet::default());
+        user.set_name("Alice");
+        let date = Date::build(2024, 1, 1).unwrap();
+        let fortunes: Vec<Fortune> = (0..30)
+            .map(|i| Fortune::of_day(&user, date.add_days(i)))
+            .collect();
+        assert!(fortunes.iter().any(|f| f.threat != fortunes[0].threat));
+
+        let text = fortunes[0].to_string();
+        assert!(text.starts_with("Death horoscope of Alice for 1 January"));
+        assert_eq!(text.lines().count(), 4);
+        assert_eq!(fortunes[0].threat_meter().chars().count(), 5);
+    }
+}
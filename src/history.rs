@@ -0,0 +1,214 @@
+use std::fs::{self, OpenOptions};
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::Algorithm;
+use crate::date::Date;
+use crate::prediction::Prediction;
+use crate::user::User;
+
+/// Prediction saved to history file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Record {
+    /// When the prediction was made, in RFC 3339 format
+    pub timestamp: String,
+    /// User's name
+    pub name: String,
+    /// User's id the prediction is derived from
+    pub id: u64,
+    /// User's birthday if it is known
+    #[serde(default)]
+    pub birthday: Option<Date>,
+    /// User's age
+    pub age: u8,
+    /// Predicted death date
+    pub death_date: Date,
+    /// Predicted death reason
+    pub reason: String,
+    /// Formula the date was calculated with
+    pub algorithm: Algorithm,
+    /// Version of the program that made the prediction
+    pub version: String,
+}
+
+impl Record {
+    /// Returns a record of the prediction made now for the user.
+    pub fn new(user: &User, prediction: &Prediction) -> Record {
+        Record {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            name: prediction.name.clone(),
+            id: prediction.id,
+            birthday: prediction.birthday,
+            age: user.age(),
+            death_date: prediction.death_date,
+            reason: prediction.reason.text.clone(),
+            algorithm: prediction.algorithm,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Appends records to the history file, one JSON object per line. The file
+/// and its directory are created if needed.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot write the file.
+pub fn append(file_path: &Path, records: &[Record]) -> Result<(), Error> {
+    if let Some(dir) = file_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut contents = String::new();
+    for record in records {
+        contents.push_str(&serde_json::to_string(record)?);
+        contents.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Returns records from the history file, oldest first. Returns nothing if
+/// there is no such file.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read the file or it has invalid
+/// records.
+pub fn read(file_path: &Path) -> Result<Vec<Record>, Error> {
+    let contents = match fs::read_to_string(file_path) {
+        Ok(v) => v,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut res = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line).map_err(|e| Error::new(
+            ErrorKind::InvalidData, format!("Line {}: {}", i + 1, e)
+        ))?;
+        res.push(record);
+    }
+    Ok(res)
+}
+
+/// Removes the history file if it exists.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot remove the file.
+pub fn clear(file_path: &Path) -> Result<(), Error> {
+    match fs::remove_file(file_path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Change of someone's prediction between two records.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Serialize)]
+pub struct Change<'a> {
+    /// Record with the previous prediction
+    pub before: &'a Record,
+    /// Record with the changed prediction
+    pub after: &'a Record,
+}
+
+/// Returns changes of predictions in the records, oldest first. A change is
+/// a different death date or reason from the previous record with the same
+/// name, birthday and algorithm.
+///
+/// # Example
+///
+/// ```
+/// use death::history::{self, Record};
+/// use death::algorithm::Algorithm;
+/// use death::date::Date;
+///
+/// let record = Record {
+///     timestamp: String::from("2024-01-01T00:00:00Z"),
+///     name: String::from("Alice"),
+///     id: 1,
+///     birthday: None,
+///     age: 30,
+///     death_date: Date::build(2070, 1, 1).unwrap(),
+///     reason: String::from("lego"),
+///     algorithm: Algorithm::Linear,
+///     version: String::from("0.2.0"),
+/// };
+/// let mut changed = record.clone();
+/// changed.reason = String::from("fire");
+///
+/// let records = [record.clone(), record, changed];
+/// let changes = history::changes(&records);
+///
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].before.reason, "lego");
+/// assert_eq!(changes[0].after.reason, "fire");
+/// ```
+pub fn changes(records: &[Record]) -> Vec<Change<'_>> {
+    let mut res = vec![];
+    for (i, after) in records.iter().enumerate() {
+        let before = records[..i].iter().rev().find(|r| {
+            r.name == after.name && r.birthday == after.birthday &&
+                r.algorithm == after.algorithm
+        });
+        if let Some(before) = before {
+            let is_changed = before.death_date != after.death_date ||
+                before.reason != after.reason;
+            if is_changed {
+                res.push(Change { before, after });
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use crate::reasons::{Reason, ReasonSet};
+
+    #[test]
+    fn append_and_read() {
+        let file_path = env::temp_dir()
+            .join(format!("death-history-{}", std::process::id()))
+            .join("history.jsonl");
+        assert_eq!(read(&file_path).unwrap(), vec![]);
+
+        let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+        let mut user = User::new(0, 0, reasons);
+        user.set_name("Alice");
+        user.set_birthday(Date::build(1990, 2, 1).unwrap());
+        let prediction = Prediction::new(&user, Algorithm::Linear, false);
+        let record = Record::new(&user, &prediction);
+        assert_eq!(record.reason, "lego");
+
+        let records = vec![record.clone(), record];
+        append(&file_path, &records).unwrap();
+        append(&file_path, &records[..1]).unwrap();
+        assert_eq!(read(&file_path).unwrap(), vec![records[0].clone(); 3]);
+
+        fs::write(&file_path, "{}\n").unwrap();
+        assert!(read(&file_path).is_err());
+
+        clear(&file_path).unwrap();
+        assert!(!file_path.exists());
+        clear(&file_path).unwrap();
+
+        fs::remove_dir_all(file_path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::Utc;
+
+use crate::date::Date;
+use crate::prediction::Prediction;
+
+/// Max length of a content line in octets, longer ones are folded.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Returns text with commas, semicolons, backslashes and line breaks
+/// escaped.
+fn escape(text: &str) -> String {
+    let mut res = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                res.push('\\');
+                res.push(c);
+            },
+            '\n' => res.push_str("\\n"),
+            '\r' => (),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+/// Returns content line folded to lines of at most [`MAX_LINE_LENGTH`]
+/// octets, each ending with CRLF. Continuation lines start with a space.
+fn fold(line: &str) -> String {
+    let mut res = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            res.push_str("\r\n ");
+            length = 1;
+        }
+        res.push(c);
+        length += c.len_utf8();
+    }
+    res.push_str("\r\n");
+    res
+}
+
+fn date_value(date: Date) -> String {
+    format!("{:04}{:02}{:02}", date.year(), date.month(), date.day())
+}
+
+/// All-day event.
+struct Event {
+    uid: String,
+    date: Date,
+    summary: String,
+    description: String,
+}
+
+impl Event {
+    fn write(&self, out: &mut String, stamp: &str) {
+        let lines = [
+            String::from("BEGIN:VEVENT"),
+            format!("UID:{}", self.uid),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", date_value(self.date)),
+            format!(
+                "DTEND;VALUE=DATE:{}", date_value(self.date.add_days(1))
+            ),
+            format!("SUMMARY:{}", escape(&self.summary)),
+            format!("DESCRIPTION:{}", escape(&self.description)),
+            String::from("TRANSP:TRANSPARENT"),
+            String::from("END:VEVENT"),
+        ];
+        for line in lines {
+            out.push_str(&fold(&line));
+        }
+    }
+}
+
+/// Returns events of the prediction: the death itself and reminders
+/// `reminders` days before it. Reminders in the past are skipped.
+fn events(prediction: &Prediction, reminders: &[u32]) -> Vec<Event> {
+    let uid = format!(
+        "{}-{}@death", prediction.id, prediction.death_date.to_iso()
+    );
+    let summary = match prediction.name.as_str() {
+        "" => String::from("Death"),
+        name => format!("Death of {}", name),
+    };
+    let mut description = format!("Be aware of: {}", prediction.reason);
+    if let Some(tip) = &prediction.reason.tip {
+        description.push_str(&format!("\nSurvival tip: {}", tip));
+    }
+
+    let mut res = vec![Event {
+        uid: uid.clone(),
+        date: prediction.death_date,
+        summary: summary.clone(),
+        description,
+    }];
+
+    let today = Date::today();
+    for &days in reminders {
+        let date = prediction.death_date.add_days(-(days as i64));
+        if date < today {
+            continue;
+        }
+        res.push(Event {
+            uid: format!("{}-{}", days, uid),
+            date,
+            summary: format!("{} in {} days", summary, days),
+            description: format!("Be aware of: {}", prediction.reason),
+        });
+    }
+
+    res
+}
+
+/// Returns iCalendar with all-day events on the predicted death dates, and
+/// reminders `reminders` days before them.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::ical;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let calendar = ical::to_ical(&[prediction], &[]);
+///
+/// assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+/// assert!(calendar.contains("SUMMARY:Death of Alice\r\n"));
+/// ```
+pub fn to_ical(predictions: &[Prediction], reminders: &[u32]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut res = String::new();
+    for line in [
+        "BEGIN:VCALENDAR", "VERSION:2.0", "PRODID:-//wadrodrog//death//EN",
+        "CALSCALE:GREGORIAN",
+    ] {
+        res.push_str(&fold(line));
+    }
+    for prediction in predictions {
+        for event in events(prediction, reminders) {
+            event.write(&mut res, &stamp);
+        }
+    }
+    res.push_str(&fold("END:VCALENDAR"));
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Algorithm;
+    use crate::reasons::{Reason, ReasonSet};
+    use crate::user::User;
+
+    #[test]
+    fn calendar() {
+        let mut reason = Reason::new("sharks, eels");
+        reason.tip = Some(String::from("swim"));
+        let reasons = ReasonSet::new(vec![reason]).unwrap();
+        let mut user = User::new(1, 20, reasons);
+        user.set_name("Alice");
+        let prediction = Prediction::new(&user, Algorithm::Linear, false);
+        let date = date_value(prediction.death_date);
+
+        let calendar = to_ical(&[prediction], &[0, 36500]);
+
+        assert!(calendar.lines().all(|l| l.len() <= MAX_LINE_LENGTH + 1));
+        assert!(
+            calendar.contains(&format!("DTSTART;VALUE=DATE:{}\r\n", date))
+        );
+        assert!(calendar.contains(
+            "DESCRIPTION:Be aware of: sharks\\, eels\\nSurvival tip: swim"
+        ));
+        assert!(calendar.contains("SUMMARY:Death of Alice in 0 days"));
+        // Reminder in the past
+        assert!(!calendar.contains("36500"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+    }
+
+    #[test]
+    fn fold_lines() {
+        let line = "D".repeat(100);
+        let folded = fold(&line);
+        assert_eq!(folded, format!(
+            "{}\r\n {}\r\n", "D".repeat(75), "D".repeat(25)
+        ));
+        assert_eq!(escape("a;b,c\\d\ne"), "a\\;b\\,c\\\\d\\ne");
+    }
+}
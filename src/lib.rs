@@ -1,10 +1,58 @@
-use std::fs;
 use std::path::PathBuf;
-use std::io::{Error, ErrorKind};
+
+use error::DeathError;
+
+use locale::Language;
+use reasons::ReasonSet;
+use theme::Theme;
 
 pub mod date;
+pub mod error;
 pub mod user;
+pub mod prediction;
+#[cfg(feature = "cli")]
 pub mod cli;
+pub mod algorithm;
+pub mod numerology;
+pub mod tarot;
+pub mod fortune;
+pub mod milestones;
+pub mod tombstone;
+pub mod banner;
+pub mod chart;
+pub mod reaper;
+pub mod report;
+pub mod simulate;
+pub mod stats;
+pub mod ical;
+pub mod species;
+pub mod theme;
+pub mod reasons;
+pub mod locale;
+pub mod palette;
+pub mod paths;
+pub mod config;
+pub mod packs;
+pub mod validate;
+pub mod batch;
+pub mod contacts;
+pub mod history;
+#[cfg(feature = "http")]
+pub mod remote;
+#[cfg(feature = "watch")]
+pub mod watch;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+#[cfg(feature = "notifications")]
+pub mod notification;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
 /// Returns default death reasons.
 pub fn default_death_reasons() -> Vec<String> {
@@ -18,45 +66,167 @@ pub fn default_death_reasons() -> Vec<String> {
     ]
 }
 
+/// Returns default death reasons translated to the language.
+///
+/// Reasons are in the same order in every language, so the prediction is
+/// the same too.
+pub fn default_death_reasons_in(language: Language) -> Vec<String> {
+    let reasons = match language {
+        Language::En => return default_death_reasons(),
+        Language::Ru => [
+            "машины", "болезнь", "высота", "темнота", "огонь", "вода",
+            "природа", "стройка", "электричество", "взрывы", "еда",
+            "животные", "температура", "оружие",
+        ],
+        Language::De => [
+            "Autos", "Krankheit", "Höhe", "Dunkelheit", "Feuer", "Wasser",
+            "Natur", "Baustelle", "Elektrizität", "Explosionen", "Essen",
+            "Tiere", "Temperatur", "Waffen",
+        ],
+        Language::Es => [
+            "coches", "enfermedad", "altura", "oscuridad", "fuego", "agua",
+            "naturaleza", "construcción", "electricidad", "explosiones",
+            "comida", "animales", "temperatura", "armas",
+        ],
+        Language::Fr => [
+            "voitures", "maladie", "hauteur", "obscurité", "feu", "eau",
+            "nature", "chantier", "électricité", "explosions", "nourriture",
+            "animaux", "température", "armes",
+        ],
+    };
+    reasons.iter().map(|r| r.to_string()).collect()
+}
+
+/// Returns death reasons of the built-in pack.
+///
+/// [`Theme::Classic`] is the same as [`default_death_reasons`].
+///
+/// # Example
+///
+/// ```
+/// use death::default_death_reasons_for;
+/// use death::theme::Theme;
+///
+/// assert!(default_death_reasons_for(Theme::Medieval)
+///     .contains(&String::from("dragons")));
+/// ```
+pub fn default_death_reasons_for(theme: Theme) -> Vec<String> {
+    let reasons = match theme {
+        Theme::Classic => return default_death_reasons(),
+        Theme::Silly => vec![
+            "banana peel", "falling piano", "laughing too hard", "rubber duck",
+            "angry geese", "vending machine", "unexpected pun",
+            "stepping on lego", "tickling", "too many pancakes",
+            "falling coconut", "escalator",
+        ],
+        Theme::SciFi => vec![
+            "airlock malfunction", "rogue AI", "black hole", "laser blast",
+            "alien parasites", "teleporter accident", "time paradox",
+            "hyperspace jump", "robot uprising", "decompression",
+            "solar flare", "nanobots",
+        ],
+        Theme::Medieval => vec![
+            "plague", "dragons", "jousting", "witch trial", "catapult",
+            "poisoned wine", "siege", "wolves", "dysentery", "duel",
+            "falling portcullis", "peasant revolt",
+        ],
+    };
+    reasons.into_iter().map(String::from).collect()
+}
+
 /// Returns death reasons from file. Each reason is on separate lines. Lines
 /// are trimmed of leading and trailing spaces.
 ///
+/// Files in other formats are detected by extension.
+/// (see [`reasons::Format::from_path`])
+///
 /// If [`None`] was passed, a default death reasons returned.
 ///
 /// # Errors
 ///
-/// Returns [`std::io::Error`] if cannot read the file.
+/// Returns [`DeathError::Reasons`] if cannot read or parse the file.
+#[deprecated(
+    since = "0.3.0",
+    note = "use `read_reason_set`, it keeps weights and categories"
+)]
 pub fn read_death_reasons(file_path: &Option<PathBuf>)
--> Result<Vec<String>, Error> {
+-> Result<Vec<String>, DeathError> {
     let file_path = match file_path {
         Some(x) => x,
         None => return Ok(default_death_reasons()),
     };
 
-    let contents = fs::read_to_string(file_path)?;
+    let res = reasons::read_reasons(file_path)
+        .map_err(DeathError::Reasons)?;
 
-    let mut res = vec![];
-
-    for line in contents.lines() {
-        let line = line.trim().to_string();
-        if line.len() > 0 {
-            res.push(line);
-        }
-    }
+    Ok(res.into_iter().map(|r| r.text).collect())
+}
 
-    if res.len() == 0 {
-        Err(Error::new(ErrorKind::Other, "File is empty"))
-    } else {
-        Ok(res)
+/// Returns death reasons from file with their weights, categories, tips and
+/// emoji. (see [`reasons::read_reasons`])
+///
+/// If [`None`] was passed, English default death reasons with their
+/// categories are returned. (see [`reasons::ReasonSet::defaults`])
+///
+/// # Errors
+///
+/// Returns [`DeathError::Reasons`] if cannot read or parse the file.
+///
+/// # Example
+///
+/// ```
+/// use std::path::PathBuf;
+/// use death::read_reason_set;
+///
+/// let path = Some(PathBuf::from("tests/read_file.toml"));
+/// let reasons = read_reason_set(&path).unwrap();
+///
+/// assert_eq!(reasons.len(), 2);
+/// assert_eq!(reasons[0].weight, 5);
+/// assert_eq!(read_reason_set(&None).unwrap()[0].text, "cars");
+/// ```
+pub fn read_reason_set(file_path: &Option<PathBuf>)
+-> Result<ReasonSet, DeathError> {
+    match file_path {
+        Some(path) => reasons::read_reasons(path)
+            .map_err(DeathError::Reasons),
+        None => Ok(ReasonSet::defaults(Language::En)),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::{Error, ErrorKind};
+    use std::io::Error;
+
+    use user::User;
 
     #[test]
+    fn translations() {
+        let count = default_death_reasons().len();
+        for language in Language::ALL {
+            assert_eq!(default_death_reasons_in(language).len(), count);
+        }
+
+        // Index of the predicted reason, with and without a tarot card
+        let predict = |name: &str, language: Language| {
+            let mut user = User::new(0, 30, ReasonSet::defaults(language));
+            user.set_name(name);
+            let reasons = user.death_reasons();
+            let index = |reason| reasons.iter()
+                .position(|r| std::ptr::eq(r, reason));
+            (index(user.get_death_reason()), index(user.get_tarot_reason().1))
+        };
+        for name in ["Alice", "Bob", "Carol", "Dave", "Eve", "Mallory"] {
+            let english = predict(name, Language::En);
+            for language in Language::ALL {
+                assert_eq!(predict(name, language), english, "{:?}", language);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn read_file() {
         // Generic file with different characters, empty lines and spaces
         let reference = vec![
@@ -72,22 +242,44 @@ mod tests {
         assert_eq!(sample, reference);
 
         // Empty file and file with spaces
-        let error_ref = Error::new(ErrorKind::Other, "File is empty");
+        let error_ref = Error::other("File is empty");
 
         match read_death_reasons(&Some(PathBuf::from("tests/spaces.txt"))) {
             Ok(_) => panic!("It is not an error as expected"),
-            Err(e) => {
+            Err(DeathError::Reasons(e)) => {
                 assert_eq!(e.kind(), error_ref.kind());
                 assert_eq!(e.to_string(), error_ref.to_string());
             },
+            Err(e) => panic!("Unexpected error: {}", e),
         }
 
         match read_death_reasons(&Some(PathBuf::from("tests/empty.txt"))) {
             Ok(_) => panic!("It is not an error as expected"),
-            Err(e) => {
+            Err(DeathError::Reasons(e)) => {
                 assert_eq!(e.kind(), error_ref.kind());
                 assert_eq!(e.to_string(), error_ref.to_string());
             }
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn read_reason_set_file() {
+        let path = Some(PathBuf::from("tests/read_file.csv"));
+        let sample = read_reason_set(&path).unwrap();
+
+        let weights: Vec<u32> = sample.iter().map(|r| r.weight).collect();
+        assert_eq!(weights, vec![1, 5, 2]);
+        assert_eq!(sample.reasons()[2].category.as_deref(), Some("animals"));
+
+        let defaults = read_reason_set(&None).unwrap();
+        assert_eq!(defaults.len(), default_death_reasons().len());
+
+        match read_reason_set(&Some(PathBuf::from("tests/empty.txt"))) {
+            Err(DeathError::Reasons(e)) => {
+                assert_eq!(e.to_string(), "File is empty");
+            },
+            _ => panic!("It is not an error as expected"),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,586 @@
+use std::env;
+use std::fmt;
+use std::sync::RwLock;
+
+use serde::Deserialize;
+
+use crate::date::TimeFormat;
+
+/// Language of built-in texts.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English
+    #[default]
+    En,
+    /// Russian
+    Ru,
+    /// German
+    De,
+    /// Spanish
+    Es,
+    /// French
+    Fr,
+}
+
+impl Language {
+    /// Every language.
+    pub const ALL: [Language; 5] = [
+        Language::En, Language::Ru, Language::De, Language::Es, Language::Fr,
+    ];
+
+    /// Returns two-letter code of the language, e.g. `ru`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ru => "ru",
+            Language::De => "de",
+            Language::Es => "es",
+            Language::Fr => "fr",
+        }
+    }
+
+    /// Returns language of a locale like `ru_RU.UTF-8` or `de`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::locale::Language;
+    ///
+    /// assert_eq!(Language::from_locale("ru_RU.UTF-8"), Some(Language::Ru));
+    /// assert_eq!(Language::from_locale("fr"), Some(Language::Fr));
+    /// assert_eq!(Language::from_locale("C"), None);
+    /// ```
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let code = locale.split(['_', '-', '.', '@']).next()?;
+        Language::ALL.into_iter()
+            .find(|l| l.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns language of the system locale from `LC_ALL`, `LC_MESSAGES` or
+    /// `LANG` environment variables.
+    ///
+    /// If none of them set to a known language, English is returned.
+    pub fn detect() -> Language {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|v| env::var(v).ok())
+            .find(|v| !v.is_empty())
+            .and_then(|v| Language::from_locale(&v))
+            .unwrap_or_default()
+    }
+
+    /// Returns how time is usually written in the language.
+    pub fn time_format(&self) -> TimeFormat {
+        match self {
+            Language::En => TimeFormat::H12,
+            _ => TimeFormat::H24,
+        }
+    }
+
+    /// Returns the message translated to the language.
+    pub fn message(&self, message: Message) -> &'static str {
+        let messages = match self {
+            Language::En => &MESSAGES_EN,
+            Language::Ru => &MESSAGES_RU,
+            Language::De => &MESSAGES_DE,
+            Language::Es => &MESSAGES_ES,
+            Language::Fr => &MESSAGES_FR,
+        };
+        messages[message as usize]
+    }
+
+    /// Returns name of the month from 1 to 12 as it is written in dates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::locale::Language;
+    ///
+    /// assert_eq!(Language::En.month_name(10), "October");
+    /// assert_eq!(Language::De.month_name(3), "März");
+    /// ```
+    pub fn month_name(&self, month: u8) -> &'static str {
+        let months = match self {
+            Language::En => [
+                "January", "February", "March", "April", "May", "June",
+                "July", "August", "September", "October", "November",
+                "December",
+            ],
+            Language::Ru => [
+                "января", "февраля", "марта", "апреля", "мая", "июня",
+                "июля", "августа", "сентября", "октября", "ноября",
+                "декабря",
+            ],
+            Language::De => [
+                "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli",
+                "August", "September", "Oktober", "November", "Dezember",
+            ],
+            Language::Es => [
+                "enero", "febrero", "marzo", "abril", "mayo", "junio",
+                "julio", "agosto", "septiembre", "octubre", "noviembre",
+                "diciembre",
+            ],
+            Language::Fr => [
+                "janvier", "février", "mars", "avril", "mai", "juin",
+                "juillet", "août", "septembre", "octobre", "novembre",
+                "décembre",
+            ],
+        };
+        months[(month - 1) as usize]
+    }
+
+    /// Returns name of the day of the week from 0 (Monday) to 6 (Sunday).
+    pub fn weekday_name(&self, weekday: u8) -> &'static str {
+        let weekdays = match self {
+            Language::En => [
+                "Monday", "Tuesday", "Wednesday", "Thursday", "Friday",
+                "Saturday", "Sunday",
+            ],
+            Language::Ru => [
+                "понедельник", "вторник", "среда", "четверг", "пятница",
+                "суббота", "воскресенье",
+            ],
+            Language::De => [
+                "Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag",
+                "Samstag", "Sonntag",
+            ],
+            Language::Es => [
+                "lunes", "martes", "miércoles", "jueves", "viernes",
+                "sábado", "domingo",
+            ],
+            Language::Fr => [
+                "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+                "dimanche",
+            ],
+        };
+        weekdays[weekday as usize]
+    }
+}
+
+/// Message shown to user. Messages with `{}` are templates for [`fill`].
+///
+/// To translate messages to a new language, add an array of them in the
+/// same order and return it from [`Language::message`].
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Message {
+    /// Day, month name and year of a date
+    Date,
+    /// Heading of the prediction
+    DateOfDeath,
+    /// Year and range of years the death most likely happens in
+    MostLikely,
+    /// Tarot card name and meaning
+    CardDrawn,
+    /// Death reason
+    BeAwareOf,
+    /// Tip how to avoid the death
+    SurvivalTip,
+    /// Prompt for name
+    YourName,
+    /// Prompt for birthday with its pattern
+    EnterBirthday,
+    /// Label of error messages
+    Error,
+    /// Label of warning messages
+    Warning,
+    /// Date has no separator
+    InvalidSeparator,
+    /// Date doesn't have 3 parts, with the pattern
+    InvalidPartsCount,
+    /// Part of date is not a number
+    InvalidNumber,
+    /// Year is invalid
+    InvalidYear,
+    /// Month is invalid
+    InvalidMonth,
+    /// Day is invalid
+    InvalidDay,
+    /// Age is not less than max age
+    AgeTooBig,
+    /// Birthday is after today
+    BirthdayInFuture,
+    /// Name is not passed and can't be asked
+    NameRequired,
+    /// Birthday is not passed and can't be asked
+    BirthdayRequired,
+    /// Input is closed while asking
+    InputClosed,
+    /// Wrong answers are given too many times
+    TooManyAttempts,
+    /// Name of minor arcana card from its rank and suit
+    MinorArcana,
+    /// Meaning of minor arcana card from its rank and suit
+    MinorArcanaMeaning,
+    /// Pack is installed, with number of its reasons
+    PackInstalled,
+    /// Heading of death reasons list with their source
+    ReasonsFrom,
+    /// Heading of death reasons list with their source and extra files
+    ReasonsFromExtra,
+    /// Category and weight of a reason in the list
+    ReasonCategoryWeight,
+    /// Weight of a reason without category in the list
+    ReasonWeight,
+    /// Pack is removed
+    PackRemoved,
+    /// Number of issues found in death reasons file
+    IssuesFound,
+    /// Prediction is saved to history
+    SavedToHistory,
+    /// Arguments are checked without predicting
+    EverythingValid,
+    /// Heading of questions about the first person to compare
+    FirstPerson,
+    /// Heading of questions about the second person to compare
+    SecondPerson,
+    /// Explanation of wizard questions
+    WizardIntro,
+    /// Question about species
+    Species,
+    /// Question about theme of death reasons
+    Theme,
+    /// Question about formula to calculate the date
+    Formula,
+    /// Question whether to draw a tarot card
+    DrawTarot,
+    /// Question and values to choose from
+    ChooseOrSkip,
+    /// Question what to do after the prediction, letters in brackets are
+    /// the answers
+    WhatNext,
+}
+
+/// Number of messages in each language.
+const MESSAGE_COUNT: usize = 42;
+
+/// All messages in the order of translations.
+pub const MESSAGES: [Message; MESSAGE_COUNT] = [
+    Message::Date, Message::DateOfDeath, Message::MostLikely,
+    Message::CardDrawn, Message::BeAwareOf, Message::SurvivalTip,
+    Message::YourName, Message::EnterBirthday, Message::Error,
+    Message::Warning, Message::InvalidSeparator, Message::InvalidPartsCount,
+    Message::InvalidNumber, Message::InvalidYear, Message::InvalidMonth,
+    Message::InvalidDay, Message::AgeTooBig, Message::BirthdayInFuture,
+    Message::NameRequired, Message::BirthdayRequired, Message::InputClosed,
+    Message::TooManyAttempts, Message::MinorArcana,
+    Message::MinorArcanaMeaning, Message::PackInstalled, Message::ReasonsFrom,
+    Message::ReasonsFromExtra, Message::ReasonCategoryWeight,
+    Message::ReasonWeight, Message::PackRemoved, Message::IssuesFound,
+    Message::SavedToHistory, Message::EverythingValid, Message::FirstPerson,
+    Message::SecondPerson, Message::WizardIntro, Message::Species,
+    Message::Theme, Message::Formula, Message::DrawTarot,
+    Message::ChooseOrSkip, Message::WhatNext,
+];
+
+const MESSAGES_EN: [&str; MESSAGE_COUNT] = [
+    "{} {} {}",
+    "DATE OF DEATH",
+    "Most likely {}, somewhere between {} and {}",
+    "Card drawn: {} ({})",
+    "Be aware of: {}",
+    "Survival tip: {}",
+    "Your name",
+    "Enter your birthday ({}) or leave it empty",
+    "error:",
+    "warning:",
+    "Use '/', or '.', or '-', or whitespace as separator between day, month \
+    and year.",
+    "Invalid should be {} - day, month and year.",
+    "Invalid number.",
+    "Invalid year.",
+    "Invalid month.",
+    "Invalid day.",
+    "Your age {} must be less than max age {}.",
+    "Your birthday cannot be in the future.",
+    "Name is required, pass `--name`.",
+    "Birthday is required, pass `--birthday` or `--age`.",
+    "Input is closed, pass the value in arguments.",
+    "Too many invalid attempts.",
+    "{} of {}",
+    "{} in {}",
+    "Installed {} ({} reasons)",
+    "Death reasons from {}:",
+    "Death reasons from {} and {}:",
+    "({}, weight {})",
+    "(weight {})",
+    "Removed {}",
+    "{} issues found",
+    "Saved to history",
+    "Everything is valid",
+    "First person",
+    "Second person",
+    "Answer the questions, leave an answer empty to skip it.",
+    "Species",
+    "Theme",
+    "Formula",
+    "Draw a tarot card to choose the reason?",
+    "{} ({}) or leave it empty",
+    "[r]eroll, [c]ompare with someone, [s]ave, [q]uit",
+];
+
+const MESSAGES_RU: [&str; MESSAGE_COUNT] = [
+    "{} {} {}",
+    "ДАТА СМЕРТИ",
+    "Скорее всего в {}, где-то между {} и {}",
+    "Выпавшая карта: {} ({})",
+    "Остерегайтесь: {}",
+    "Совет по выживанию: {}",
+    "Ваше имя",
+    "Введите дату рождения ({}) или оставьте пустой",
+    "ошибка:",
+    "предупреждение:",
+    "Используйте '/', '.', '-' или пробел как разделитель дня, месяца и \
+    года.",
+    "Неверный формат, должно быть {} - день, месяц и год.",
+    "Неверное число.",
+    "Неверный год.",
+    "Неверный месяц.",
+    "Неверный день.",
+    "Ваш возраст {} должен быть меньше максимального {}.",
+    "Дата рождения не может быть в будущем.",
+    "Нужно имя, передайте `--name`.",
+    "Нужна дата рождения, передайте `--birthday` или `--age`.",
+    "Ввод закрыт, передайте значение в аргументах.",
+    "Слишком много неверных попыток.",
+    "{} {}",
+    "{} в {}",
+    "Установлен {} (причин: {})",
+    "Причины смерти из {}:",
+    "Причины смерти из {} и {}:",
+    "({}, вес {})",
+    "(вес {})",
+    "Удалён {}",
+    "Найдено проблем: {}",
+    "Сохранено в историю",
+    "Всё верно",
+    "Первый человек",
+    "Второй человек",
+    "Ответьте на вопросы, оставьте ответ пустым, чтобы пропустить его.",
+    "Вид",
+    "Тема",
+    "Формула",
+    "Вытянуть карту таро, чтобы выбрать причину?",
+    "{} ({}) или оставьте пустым",
+    "[r] заново, [c] сравнить с кем-то, [s] сохранить, [q] выйти",
+];
+
+const MESSAGES_DE: [&str; MESSAGE_COUNT] = [
+    "{}. {} {}",
+    "TODESDATUM",
+    "Höchstwahrscheinlich {}, irgendwann zwischen {} und {}",
+    "Gezogene Karte: {} ({})",
+    "Hüte dich vor: {}",
+    "Überlebenstipp: {}",
+    "Dein Name",
+    "Gib deinen Geburtstag ein ({}) oder lass ihn leer",
+    "Fehler:",
+    "Warnung:",
+    "Verwende '/', '.', '-' oder Leerzeichen als Trennzeichen zwischen Tag, \
+    Monat und Jahr.",
+    "Ungültig, es sollte {} sein - Tag, Monat und Jahr.",
+    "Ungültige Zahl.",
+    "Ungültiges Jahr.",
+    "Ungültiger Monat.",
+    "Ungültiger Tag.",
+    "Dein Alter {} muss kleiner als das Höchstalter {} sein.",
+    "Dein Geburtstag kann nicht in der Zukunft liegen.",
+    "Name wird benötigt, gib `--name` an.",
+    "Geburtstag wird benötigt, gib `--birthday` oder `--age` an.",
+    "Eingabe ist geschlossen, gib den Wert als Argument an.",
+    "Zu viele ungültige Versuche.",
+    "{} der {}",
+    "{} in {}",
+    "{} installiert ({} Gründe)",
+    "Todesursachen aus {}:",
+    "Todesursachen aus {} und {}:",
+    "({}, Gewicht {})",
+    "(Gewicht {})",
+    "{} entfernt",
+    "{} Probleme gefunden",
+    "Im Verlauf gespeichert",
+    "Alles ist gültig",
+    "Erste Person",
+    "Zweite Person",
+    "Beantworte die Fragen, lass eine Antwort leer, um sie zu überspringen.",
+    "Art",
+    "Thema",
+    "Formel",
+    "Eine Tarotkarte ziehen, um den Grund zu wählen?",
+    "{} ({}) oder lass es leer",
+    "[r] neu würfeln, [c] mit jemandem vergleichen, [s] speichern, \
+    [q] beenden",
+];
+
+const MESSAGES_ES: [&str; MESSAGE_COUNT] = [
+    "{} de {} de {}",
+    "FECHA DE MUERTE",
+    "Lo más probable en {}, en algún momento entre {} y {}",
+    "Carta sacada: {} ({})",
+    "Cuidado con: {}",
+    "Consejo de supervivencia: {}",
+    "Tu nombre",
+    "Introduce tu fecha de nacimiento ({}) o déjala vacía",
+    "error:",
+    "aviso:",
+    "Usa '/', '.', '-' o un espacio como separador entre día, mes y año.",
+    "Inválido, debe ser {} - día, mes y año.",
+    "Número inválido.",
+    "Año inválido.",
+    "Mes inválido.",
+    "Día inválido.",
+    "Tu edad {} debe ser menor que la edad máxima {}.",
+    "Tu fecha de nacimiento no puede estar en el futuro.",
+    "Se necesita el nombre, usa `--name`.",
+    "Se necesita la fecha de nacimiento, usa `--birthday` o `--age`.",
+    "La entrada está cerrada, pasa el valor en los argumentos.",
+    "Demasiados intentos inválidos.",
+    "{} de {}",
+    "{} en {}",
+    "Instalado {} ({} causas)",
+    "Causas de muerte de {}:",
+    "Causas de muerte de {} y {}:",
+    "({}, peso {})",
+    "(peso {})",
+    "Eliminado {}",
+    "Se encontraron {} problemas",
+    "Guardado en el historial",
+    "Todo es válido",
+    "Primera persona",
+    "Segunda persona",
+    "Responde a las preguntas, deja una respuesta vacía para omitirla.",
+    "Especie",
+    "Tema",
+    "Fórmula",
+    "¿Sacar una carta del tarot para elegir la causa?",
+    "{} ({}) o déjalo vacío",
+    "[r] repetir, [c] comparar con alguien, [s] guardar, [q] salir",
+];
+
+const MESSAGES_FR: [&str; MESSAGE_COUNT] = [
+    "{} {} {}",
+    "DATE DE DÉCÈS",
+    "Probablement en {}, quelque part entre {} et {}",
+    "Carte tirée : {} ({})",
+    "Méfiez-vous de : {}",
+    "Conseil de survie : {}",
+    "Votre nom",
+    "Entrez votre date de naissance ({}) ou laissez-la vide",
+    "erreur :",
+    "avertissement :",
+    "Utilisez '/', '.', '-' ou un espace comme séparateur entre le jour, le \
+    mois et l'année.",
+    "Invalide, cela doit être {} - jour, mois et année.",
+    "Nombre invalide.",
+    "Année invalide.",
+    "Mois invalide.",
+    "Jour invalide.",
+    "Votre âge {} doit être inférieur à l'âge maximal {}.",
+    "Votre date de naissance ne peut pas être dans le futur.",
+    "Le nom est requis, passez `--name`.",
+    "La date de naissance est requise, passez `--birthday` ou `--age`.",
+    "L'entrée est fermée, passez la valeur en argument.",
+    "Trop de tentatives invalides.",
+    "{} de {}",
+    "{} dans {}",
+    "{} installé ({} causes)",
+    "Causes de décès de {} :",
+    "Causes de décès de {} et {} :",
+    "({}, poids {})",
+    "(poids {})",
+    "{} supprimé",
+    "{} problèmes trouvés",
+    "Enregistré dans l'historique",
+    "Tout est valide",
+    "Première personne",
+    "Deuxième personne",
+    "Répondez aux questions, laissez une réponse vide pour la passer.",
+    "Espèce",
+    "Thème",
+    "Formule",
+    "Tirer une carte de tarot pour choisir la cause ?",
+    "{} ({}) ou laissez vide",
+    "[r] relancer, [c] comparer avec quelqu'un, [s] enregistrer, [q] quitter",
+];
+
+/// Language of messages, English until it is set.
+static LANGUAGE: RwLock<Language> = RwLock::new(Language::En);
+
+/// Set language of messages.
+pub fn set_language(language: Language) {
+    *LANGUAGE.write().unwrap() = language;
+}
+
+/// Returns language of messages. (see [`set_language`])
+pub fn language() -> Language {
+    *LANGUAGE.read().unwrap()
+}
+
+/// Format of time, the default one of the language until it is set.
+static TIME_FORMAT: RwLock<Option<TimeFormat>> = RwLock::new(None);
+
+/// Set format of time.
+pub fn set_time_format(format: TimeFormat) {
+    *TIME_FORMAT.write().unwrap() = Some(format);
+}
+
+/// Returns format of time. (see [`set_time_format`] and
+/// [`Language::time_format`])
+pub fn time_format() -> TimeFormat {
+    TIME_FORMAT.read().unwrap().unwrap_or_else(|| language().time_format())
+}
+
+/// Returns the message in language of messages.
+pub fn tr(message: Message) -> &'static str {
+    language().message(message)
+}
+
+/// Returns the template with every `{}` replaced by the next value.
+///
+/// # Example
+///
+/// ```
+/// use death::locale::fill;
+///
+/// assert_eq!(fill("{} and {}", &[&1, &"two"]), "1 and two");
+/// assert_eq!(fill("{} and {}", &[&1]), "1 and ");
+/// ```
+pub fn fill(template: &str, values: &[&dyn fmt::Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut res = parts.next().unwrap_or("").to_string();
+    let mut values = values.iter();
+    for part in parts {
+        if let Some(value) = values.next() {
+            res.push_str(&value.to_string());
+        }
+        res.push_str(part);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages() {
+        let placeholders = |m: &str| m.matches("{}").count();
+        for (i, &message) in MESSAGES.iter().enumerate() {
+            assert_eq!(message as usize, i);
+            let english = Language::En.message(message);
+            for language in Language::ALL {
+                let translation = language.message(message);
+                assert_eq!(
+                    placeholders(translation), placeholders(english),
+                    "{:?}: {}", language, translation
+                );
+            }
+        }
+    }
+}
@@ -1,36 +1,1485 @@
-use death::cli;
-use death::user::User;
+use death::cli::{
+    self, BatchArgs, CheckArgs, Command, CompareArgs, FortuneArgs, HistoryArgs,
+    HistoryCommand, Output, PageArgs, PredictArgs, PredictOptions,
+    PredictionSettings, ReasonsCommand, GroupArgs, ImportArgs, RemindArgs,
+    ServeArgs, SimulateArgs, StatsArgs, WizardArgs,
+};
+use death::{
+    banner, contacts, history, ical, packs, paths, reaper, report, tombstone,
+    validate,
+};
+use death::batch::{self, Person, SortKey};
+use death::date::{Date, DateOrder};
+use death::error::DeathError;
+use death::fortune::Fortune;
+use death::milestones::{self, Milestone};
+use death::prediction::{Prediction, CSV_HEADER};
+use death::simulate::Simulation;
+use death::stats::Stats;
+use death::user::{self, User};
+use death::algorithm::Algorithm;
+use death::species::Species;
+use death::reasons::{Reason, ReasonSet};
+use death::locale::{fill, tr, Message};
+use death::palette::{paint, Role};
 
-fn predict(user: &User, linear: bool) {
-    println!("DATE OF DEATH");
-    println!("{}", user.get_death_date(linear));
-    println!("Be aware of: {}", user.get_death_reason());
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+
+use std::{env, fmt};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, ErrorKind, IsTerminal, Write};
+use std::panic;
+use std::process;
+
+/// Prints the error and closes the program with the exit code.
+fn fail<T: fmt::Display>(error: T, code: i32) -> ! {
+    cli::print_error(error, code);
+    process::exit(code);
 }
 
-fn main() {
-    let args = cli::parse();
+/// Prints the error and closes the program with the exit code of its kind.
+fn exit_with(error: DeathError) -> ! {
+    let code = error.exit_code();
+    fail(error, code)
+}
+
+/// Value of a result the program can't go on without.
+trait OrExit<T> {
+    /// Returns the value or closes the program with the error.
+    fn or_exit(self) -> T;
+}
+
+impl<T> OrExit<T> for Result<T, DeathError> {
+    fn or_exit(self) -> T {
+        self.unwrap_or_else(|e| exit_with(e))
+    }
+}
+
+fn print_reason(out: &mut dyn Write, reason: &Reason, emoji: bool)
+-> io::Result<()> {
+    let mut text = match &reason.category {
+        Some(category) => format!("{} ({})", reason, category),
+        None => reason.to_string(),
+    };
+    if emoji {
+        text = format!("{} {}", reason.icon(), text);
+    }
+    writeln!(out, "{}", fill(tr(Message::BeAwareOf), &[&text]))?;
+    if let Some(tip) = &reason.tip {
+        writeln!(out, "{}", fill(tr(Message::SurvivalTip), &[tip]))?;
+    }
+    Ok(())
+}
+
+/// Prints the prediction for humans. If the output is decorated, there is
+/// a header and the date is highlighted, otherwise it is a plain block of
+/// lines. (see [`is_decorated`])
+fn print_text(
+    out: &mut dyn Write, prediction: &Prediction, options: &PredictOptions,
+) -> io::Result<()> {
+    let decorated = is_decorated(options);
+    let date = match prediction.death_time {
+        _ if options.big => {
+            banner::date(prediction, tombstone::terminal_width())
+        },
+        Some(time) => format!("{}, {}", prediction.death_date, time),
+        None => prediction.death_date.to_string(),
+    };
+    if decorated {
+        writeln!(out, "{}", paint(tr(Message::DateOfDeath), Role::Headline))?;
+        writeln!(out, "{}", date.bold())?;
+    } else {
+        writeln!(out, "{}", date)?;
+    }
+    writeln!(out, "{}", fill(tr(Message::MostLikely), &[
+        &prediction.death_date.year(), &prediction.likely_from,
+        &prediction.likely_to,
+    ]))?;
+    if let Some(card) = &prediction.card {
+        writeln!(
+            out, "{}",
+            fill(tr(Message::CardDrawn), &[&card.name, &card.meaning])
+        )?;
+    }
+    print_reason(out, &prediction.reason, options.emoji)
+}
 
-    let mut user = User::from(&args);
+/// Returns `true` if the output is decorated for humans. Output to files and
+/// clipboard is always plain. (see [`cli::is_decorated`])
+fn is_decorated(options: &PredictOptions) -> bool {
+    cli::is_decorated() && options.output_file.is_none() && !options.copy
+}
+
+fn print_prediction(
+    out: &mut dyn Write, prediction: &Prediction, reasons: &ReasonSet,
+    options: &PredictOptions,
+) -> io::Result<()> {
+    if options.quiet {
+        return writeln!(
+            out, "{}\t{}", prediction.death_date.to_iso(), prediction.reason
+        );
+    }
+    if options.date_only {
+        return writeln!(out, "{}", prediction.death_date.to_iso());
+    }
+    if options.oneline && options.emoji {
+        let icon = prediction.reason.icon();
+        return writeln!(out, "{} {}", icon, prediction.oneline(false));
+    }
+    if options.oneline {
+        return writeln!(out, "{}", prediction.oneline(!options.no_emoji));
+    }
+    if options.reason_only {
+        return writeln!(out, "{}", prediction.reason);
+    }
+    if let Some(template) = &options.format {
+        match prediction.format(template) {
+            Ok(v) => writeln!(out, "{}", v)?,
+            Err(e) => fail(e, cli::EXIT_USAGE),
+        }
+        return Ok(());
+    }
+    if options.full_report {
+        let text = match is_decorated(options) {
+            true => report::painted_text(prediction, reasons),
+            false => report::text(prediction, reasons),
+        };
+        write!(out, "{}", text)?;
+        return print_qr(out, prediction, options);
+    }
+    if options.tombstone {
+        let width = tombstone::terminal_width();
+        writeln!(out, "{}", tombstone::render(prediction, width))?;
+        return print_qr(out, prediction, options);
+    }
+    match options.output {
+        Output::Text => {
+            print_text(out, prediction, options)?;
+            print_qr(out, prediction, options)
+        },
+        Output::Json | Output::Jsonl => {
+            writeln!(out, "{}", prediction.to_json())
+        },
+        Output::Markdown => {
+            write!(out, "{}", report::markdown(prediction, reasons))
+        },
+        Output::Html => write!(
+            out, "{}",
+            report::html(std::slice::from_ref(prediction), reasons)
+        ),
+        Output::Waybar => writeln!(out, "{}", prediction.to_waybar()),
+        Output::Csv => {
+            write_csv_row(out, &CSV_HEADER)?;
+            write_csv_row(out, &prediction.to_csv_row())
+        },
+    }
+}
+
+fn write_csv_row<T: AsRef<[u8]>>(out: &mut dyn Write, row: &[T])
+-> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(row)?;
+    writer.flush()
+}
+
+#[cfg(feature = "qr")]
+fn print_qr(
+    out: &mut dyn Write, prediction: &Prediction, options: &PredictOptions
+) -> io::Result<()> {
+    if !options.qr {
+        return Ok(());
+    }
+    match death::qr::render(&death::qr::summary(prediction)) {
+        Ok(code) => writeln!(out, "{}", code),
+        Err(e) => {
+            cli::print_warning(format!("QR code: {}", e));
+            Ok(())
+        },
+    }
+}
+
+#[cfg(not(feature = "qr"))]
+fn print_qr(
+    _out: &mut dyn Write, _prediction: &Prediction, options: &PredictOptions
+) -> io::Result<()> {
+    if options.qr {
+        fail(QR_UNSUPPORTED, cli::EXIT_USAGE);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "qr"))]
+const QR_UNSUPPORTED: &str =
+    "QR codes are not enabled, build with `qr` feature";
+
+#[cfg(feature = "qr")]
+fn save_qr(prediction: &Prediction, options: &PredictOptions) {
+    if let Some(path) = &options.qr_file {
+        let text = death::qr::summary(prediction);
+        if let Err(e) = death::qr::write_png(&text, path) {
+            fail(
+                format!("{}: {}", path.display(), e), cli::EXIT_IO
+            );
+        }
+    }
+}
+
+#[cfg(not(feature = "qr"))]
+fn save_qr(_prediction: &Prediction, options: &PredictOptions) {
+    if options.qr_file.is_some() {
+        fail(QR_UNSUPPORTED, cli::EXIT_USAGE);
+    }
+}
+
+/// Where predictions are printed. Everything printed is kept if it is
+/// copied to clipboard in the end.
+struct Out {
+    inner: Box<dyn Write>,
+    copied: Option<Vec<u8>>,
+}
+
+impl Write for Out {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        if let Some(copied) = &mut self.copied {
+            copied.extend_from_slice(&buf[..n]);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns where to print predictions: the output file or stdout. The file
+/// is not overwritten without `--force`.
+fn open_output(options: &PredictOptions) -> Out {
+    Out {
+        inner: open_file(options),
+        copied: (options.copy && !options.dry_run).then(Vec::new),
+    }
+}
+
+fn open_file(options: &PredictOptions) -> Box<dyn Write> {
+    let path = match &options.output_file {
+        Some(v) => v,
+        None => return Box::new(io::stdout()),
+    };
+    if options.dry_run {
+        if path.exists() && !options.force {
+            fail(format!(
+                "{}: File exists, pass `--force` to overwrite it",
+                path.display()
+            ), cli::EXIT_IO);
+        }
+        return Box::new(io::sink());
+    }
+    let file = if options.force {
+        File::create(path)
+    } else {
+        OpenOptions::new().write(true).create_new(true).open(path)
+    };
+    match file {
+        Ok(v) => Box::new(BufWriter::new(v)),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            fail(format!(
+                "{}: File exists, pass `--force` to overwrite it",
+                path.display()
+            ), cli::EXIT_IO);
+        },
+        Err(e) => fail(
+            format!("{}: {}", path.display(), e), cli::EXIT_IO
+        ),
+    }
+}
+
+/// Copies everything printed to clipboard if `--copy` is passed.
+#[cfg(feature = "clipboard")]
+fn close_output(out: Out) {
+    if let Some(copied) = out.copied {
+        let text = String::from_utf8_lossy(&copied);
+        if let Err(e) = death::clipboard::copy(&text) {
+            cli::print_warning(format!("Cannot copy to clipboard: {}", e));
+        }
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn close_output(out: Out) {
+    if out.copied.is_some() {
+        fail(
+            "Clipboard is not enabled, build with `clipboard` feature",
+            cli::EXIT_USAGE
+        );
+    }
+}
+
+/// Exits with an error if predictions could not be printed.
+fn check_output(result: io::Result<()>, options: &PredictOptions) {
+    if let Err(e) = result {
+        let message = match &options.output_file {
+            Some(path) => format!("{}: {}", path.display(), e),
+            None => e.to_string(),
+        };
+        fail(message, cli::EXIT_IO);
+    }
+}
+
+fn print_trace(user: &User, algorithm: Algorithm, options: &PredictOptions) {
+    if !options.verbose {
+        return;
+    }
+    for line in user.trace(algorithm, options.tarot) {
+        eprintln!("{}", line);
+    }
+}
+
+fn save_history(
+    user: &User, prediction: &Prediction, options: &PredictOptions
+) {
+    if !options.no_history {
+        append_history(user, prediction);
+    }
+}
+
+/// Appends the prediction to history file. Returns `false` and prints a
+/// warning if it is not saved.
+fn append_history(user: &User, prediction: &Prediction) -> bool {
+    let path = match paths::history_file() {
+        Some(v) => v,
+        None => return false,
+    };
+    let record = history::Record::new(user, prediction);
+    if let Err(e) = history::append(&path, &[record]) {
+        cli::print_warning(format!("{}: {}", path.display(), e));
+        return false;
+    }
+    true
+}
+
+#[cfg(feature = "http")]
+fn send_webhook(prediction: &Prediction, options: &PredictOptions) {
+    if let Some(url) = &options.webhook {
+        let json = prediction.to_json();
+        if let Err(e) = death::remote::post_json(url, &json) {
+            cli::print_warning(format!("{}: {}", url, e));
+        }
+    }
+}
+
+#[cfg(not(feature = "http"))]
+fn send_webhook(_prediction: &Prediction, options: &PredictOptions) {
+    if options.webhook.is_some() {
+        fail(
+            "HTTP support is not enabled, build with `http` feature",
+            cli::EXIT_USAGE
+        );
+    }
+}
+
+#[cfg(feature = "notifications")]
+fn show_notification(prediction: &Prediction, options: &PredictOptions) {
+    if options.notify {
+        let body = format!(
+            "{}\n{}", prediction.death_date,
+            fill(tr(Message::BeAwareOf), &[&prediction.reason])
+        );
+        let summary = tr(Message::DateOfDeath);
+        if let Err(e) = death::notification::show(summary, &body) {
+            cli::print_warning(format!("Cannot show notification: {}", e));
+        }
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+fn show_notification(_prediction: &Prediction, options: &PredictOptions) {
+    if options.notify {
+        fail(NOTIFICATIONS_UNSUPPORTED, cli::EXIT_USAGE);
+    }
+}
+
+const NOTIFICATIONS_UNSUPPORTED: &str =
+    "Notifications are not enabled, build with `notifications` feature";
+
+fn save_ical(predictions: &[Prediction], options: &PredictOptions) {
+    if let Some(path) = &options.ical {
+        let calendar = ical::to_ical(predictions, &options.reminder);
+        if let Err(e) = fs::write(path, calendar) {
+            fail(
+                format!("{}: {}", path.display(), e), cli::EXIT_IO
+            );
+        }
+    }
+}
+
+fn reasons_command(command: &ReasonsCommand) {
+    match command {
+        ReasonsCommand::Install { source } => match packs::install(source) {
+            Ok((pack, reasons)) => println!("{}", fill(
+                tr(Message::PackInstalled), &[&pack.name, &reasons.len()]
+            )),
+            Err(e) => fail(
+                format!("{}: {}", source, e), cli::EXIT_REASONS
+            ),
+        },
+        ReasonsCommand::List { reasons } => {
+            let (death_reasons, source) = user::death_reasons_from(reasons)
+                .or_exit();
+            let extra: Vec<String> = reasons.extra_reasons.iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            match extra.is_empty() {
+                true => println!(
+                    "{}", fill(tr(Message::ReasonsFrom), &[&source])
+                ),
+                false => println!("{}", fill(
+                    tr(Message::ReasonsFromExtra),
+                    &[&source, &extra.join(", ")]
+                )),
+            }
+            if !death_reasons.metadata().is_empty() {
+                println!("{}", death_reasons.metadata());
+            }
+            for reason in &death_reasons {
+                let details = match &reason.category {
+                    Some(c) => fill(
+                        tr(Message::ReasonCategoryWeight), &[c, &reason.weight]
+                    ),
+                    None => fill(tr(Message::ReasonWeight), &[&reason.weight]),
+                };
+                println!("  {} {}", reason, details);
+            }
+        },
+        ReasonsCommand::Packs => match packs::list() {
+            Ok(packs) => for pack in packs {
+                println!("{}\t{}", pack.name, pack.path.display());
+            },
+            Err(e) => fail(e, cli::EXIT_IO),
+        },
+        ReasonsCommand::Remove { name } => match packs::remove(name) {
+            Ok(pack) => println!(
+                "{}", fill(tr(Message::PackRemoved), &[&pack.name])
+            ),
+            Err(e) => fail(e, cli::EXIT_ERROR),
+        },
+        ReasonsCommand::Validate { file } => {
+            let path = packs::resolve(file);
+            match validate::validate_reasons(&path) {
+                Ok(stats) => println!("{}: {}", file.display(), stats),
+                Err(issues) => {
+                    for issue in issues.iter() {
+                        println!("{}: {}", file.display(), issue);
+                    }
+                    fail(
+                        fill(tr(Message::IssuesFound), &[&issues.len()]),
+                        cli::EXIT_REASONS
+                    );
+                },
+            }
+        },
+    }
+}
+
+/// Plays the Grim Reaper animation if it is asked for and the prediction is
+/// printed to a terminal for humans.
+fn play_animation(options: &PredictOptions) {
+    let is_text = options.output == Output::Text && !options.quiet &&
+        options.format.is_none() && !options.date_only &&
+        !options.reason_only && !options.oneline;
+    let to_terminal = options.output_file.is_none() &&
+        io::stdout().is_terminal();
+    if !options.reaper || options.no_animation || !is_text || !to_terminal {
+        return;
+    }
+    let result = reaper::animate(&mut io::stdout(), reaper::FRAME_DELAY);
+    check_output(result, options);
+}
+
+/// Returns prediction for the user with time of death if it is asked for.
+fn predict(user: &User, algorithm: Algorithm, options: &PredictOptions)
+-> Prediction {
+    let mut prediction = Prediction::new(user, algorithm, options.tarot);
+    if options.time || options.full_report {
+        prediction.death_time = Some(user.get_death_time());
+    }
+    prediction
+}
+
+/// Returns `true` if actions are offered after the prediction: it is
+/// printed as text to the terminal, which can answer.
+fn offers_actions(options: &PredictOptions) -> bool {
+    is_decorated(options) && io::stdin().is_terminal() &&
+        options.output == Output::Text && options.format.is_none() &&
+        !options.date_only && !options.reason_only && !options.full_report &&
+        !options.tombstone && !options.exit_years
+}
+
+/// Asks what to do with the prediction until the user quits: reroll it
+/// with the next salt, compare it with someone else's one or save it.
+fn offer_actions(
+    user: &mut User, mut prediction: Prediction, algorithm: Algorithm,
+    options: &PredictOptions,
+) {
+    let id = user.id();
+    let mut salt = 0;
+    loop {
+        println!();
+        match cli::ask_action().or_exit() {
+            cli::Action::Reroll => {
+                salt += 1;
+                user.set_id(id);
+                user.reroll(salt);
+                prediction = predict(user, algorithm, options);
+                println!();
+                let mut out = io::stdout();
+                let result = print_text(&mut out, &prediction, options);
+                check_output(result, options);
+            },
+            cli::Action::Compare => {
+                let order = options.settings.date_order();
+                let reasons = user.death_reasons().clone();
+                let mut other = User::new(0, 0, reasons);
+                other.set_max_age(user.max_age());
+                other.set_name(&cli::ask_name().or_exit());
+                if let Some(birthday) = cli::ask_birthday(
+                    user.max_age(), order
+                ).or_exit() {
+                    other.set_birthday(birthday);
+                }
+                let other = predict(&other, algorithm, options);
+                println!();
+                print!("{}", report::comparison(&prediction, &other));
+            },
+            cli::Action::Save => if append_history(user, &prediction) {
+                println!("{}", tr(Message::SavedToHistory));
+            },
+            cli::Action::Quit => return,
+        }
+    }
+}
+
+/// Exits with an error if the template of `--format` is invalid.
+fn check_template(options: &PredictOptions, death_reasons: &ReasonSet) {
+    if let Some(template) = &options.format {
+        let user = User::new(0, 0, death_reasons.clone());
+        let prediction = Prediction::new(
+            &user, options.settings.algorithm(), false
+        );
+        if let Err(e) = prediction.format(template) {
+            fail(e, cli::EXIT_USAGE);
+        }
+    }
+}
+
+fn predict_command(args: &PredictArgs) {
+    let algorithm = match args.linear {
+        Some(linear) => {
+            cli::print_warning(
+                "`--linear` is deprecated, use `--algorithm linear`"
+            );
+            match linear {
+                true => Algorithm::Linear,
+                false => args.options.settings.algorithm(),
+            }
+        },
+        None => args.options.settings.algorithm(),
+    };
+    let mut out = open_output(&args.options);
+
+    if args.stdin {
+        let people = io::stdin().lines()
+            .map_while(Result::ok)
+            .map(|line| line.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .map(|name| Person {
+                name, birthday: args.birthday.clone(), age: args.age,
+                id: None,
+            });
+        predict_people(
+            &mut out, people, &args.options, algorithm, ProgressBar::hidden(),
+            None, false,
+        );
+        close_output(out);
+        return;
+    }
+
+    let names = args.all_names();
+    if names.len() > 1 {
+        if args.seed.is_some() {
+            fail(
+                "`--seed` can't be used with several names", cli::EXIT_USAGE
+            );
+        }
+        let people = names.into_iter().map(|name| Person {
+            name: name.to_string(),
+            birthday: args.birthday.clone(),
+            age: args.age,
+            id: None,
+        });
+        predict_people(
+            &mut out, people, &args.options, algorithm, ProgressBar::hidden(),
+            None, false,
+        );
+        close_output(out);
+        return;
+    }
+
+    let mut user = User::from(args).or_exit();
+
+    if args.options.dry_run {
+        check_template(&args.options, user.death_reasons());
+        println!("{}", tr(Message::EverythingValid));
+        return;
+    }
 
     let mut asked = false;
 
-    if args.name == None {
-        let name = cli::ask_name();
-        user.set_id(User::get_id_from_string(&name));
+    // Quiet and one-line output is for scripts and prompts, nobody answers
+    let never_ask = args.no_input || args.options.quiet ||
+        args.options.oneline;
+    if never_ask {
+        if names.is_empty() {
+            fail(tr(Message::NameRequired), cli::EXIT_USAGE);
+        }
+        if args.birthday.is_none() && args.age.is_none() {
+            fail(
+                tr(Message::BirthdayRequired), cli::EXIT_USAGE
+            );
+        }
+    }
+
+    if names.is_empty() && !never_ask {
+        let name = cli::ask_name().or_exit();
+        user.set_name(&name);
+        if let Some(seed) = args.seed {
+            user.set_id(seed);
+        }
         asked = true;
     }
 
-    if args.birthday == None {
-        let age = cli::ask_birthday();
-        user.set_age(age);
+    if args.birthday.is_none() && args.age.is_none() && !never_ask {
+        let birthday = cli::ask_birthday(
+            user.max_age(), args.options.settings.date_order()
+        ).or_exit();
+        if let Some(birthday) = birthday {
+            user.set_birthday(birthday);
+        }
         asked = true;
     }
 
+    if asked && is_decorated(&args.options) {
+        println!();
+    }
+
+    let prediction = predict(&user, algorithm, &args.options);
+    play_animation(&args.options);
+    let result = print_prediction(
+        &mut out, &prediction, user.death_reasons(), &args.options
+    )
+        .and_then(|_| out.flush());
+    check_output(result, &args.options);
+    print_trace(&user, algorithm, &args.options);
+    save_history(&user, &prediction, &args.options);
+    send_webhook(&prediction, &args.options);
+    show_notification(&prediction, &args.options);
+    save_qr(&prediction, &args.options);
+    save_ical(std::slice::from_ref(&prediction), &args.options);
+    close_output(out);
+    if asked && offers_actions(&args.options) {
+        offer_actions(&mut user, prediction, algorithm, &args.options);
+        return;
+    }
+    if args.options.exit_years {
+        let years = prediction.years_left.min(cli::MAX_EXIT_YEARS);
+        process::exit(years.into());
+    }
+}
+
+/// Returns progress bar of `len` predictions on stderr. It is shown only if
+/// stderr is a terminal and the predictions are not, e.g. in
+/// `death batch team.csv -o json > team.json`, and never in quiet mode.
+fn progress_bar(len: usize, options: &PredictOptions) -> ProgressBar {
+    let to_terminal = options.output_file.is_none() &&
+        io::stdout().is_terminal();
+    if options.quiet || to_terminal {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template(
+        "{bar:40} {human_pos}/{human_len} {rate} ETA {eta}"
+    )
+        .expect("template is valid")
+        .with_key("rate", |state: &ProgressState, w: &mut dyn fmt::Write| {
+            let _ = write!(w, "{:.0}/s", state.per_sec());
+        });
+    ProgressBar::new(len as u64).with_style(style)
+}
+
+/// Returns max age, order of day, month and year in birthdays and death
+/// reasons chosen by the settings, or closes the program if death reasons
+/// can't be read.
+fn prepare(settings: &PredictionSettings) -> (u16, DateOrder, ReasonSet) {
+    let (death_reasons, _) = user::death_reasons_from(&settings.reasons)
+        .or_exit();
+    (settings.max_age(), settings.date_order(), death_reasons)
+}
+
+/// Returns name of the user running the program, or empty one if it's
+/// unknown.
+fn current_user_name() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+/// Predicts for the people and prints the predictions, sorted by the key
+/// if it is passed.
+fn predict_people<I>(
+    out: &mut dyn Write, people: I, options: &PredictOptions,
+    algorithm: Algorithm, progress: ProgressBar, sort: Option<SortKey>,
+    reverse: bool,
+) where
+    I: IntoIterator<Item = Person>,
+{
+    let (max_age, order, death_reasons) = prepare(&options.settings);
+
+    let is_plain = !options.quiet && options.format.is_none() &&
+        !options.date_only && !options.reason_only && !options.oneline;
+    let is_array = is_plain && options.output == Output::Json;
+    // HTML reports are on the same page
+    let is_page = is_plain && options.output == Output::Html;
+    // CSV has one header for all rows
+    let is_csv = is_plain && options.output == Output::Csv;
+    if options.qr_file.is_some() {
+        fail(
+            "`--qr-file` can't be used with several people", cli::EXIT_USAGE
+        );
+    }
+    if options.exit_years {
+        fail(
+            "`--exit-years` can't be used with several people",
+            cli::EXIT_USAGE
+        );
+    }
+    if options.notify {
+        fail(
+            "`--notify` can't be used with several people", cli::EXIT_USAGE
+        );
+    }
+    if options.dry_run {
+        check_template(options, &death_reasons);
+        check_people(people, &death_reasons, max_age, order, progress);
+        return;
+    }
+    if is_csv {
+        check_output(write_csv_row(out, &CSV_HEADER), options);
+    }
+
+    let mut predictions = vec![];
+    let mut printed = 0;
+    let mut print = |prediction: Prediction| {
+        if is_array || is_page {
+            predictions.push(prediction);
+            return;
+        }
+        if options.ical.is_some() {
+            predictions.push(prediction.clone());
+        }
+        if is_csv {
+            let result = write_csv_row(out, &prediction.to_csv_row())
+                .and_then(|_| out.flush());
+            check_output(result, options);
+            return;
+        }
+        let is_text = is_plain && options.output == Output::Text;
+        let is_markdown = is_plain && options.output == Output::Markdown;
+        let mut result = Ok(());
+        if (is_text || is_markdown) && printed > 0 {
+            result = writeln!(out);
+        }
+        if is_text && !options.tombstone && !options.full_report {
+            let mut name = match is_decorated(options) {
+                true => prediction.name.bold().to_string(),
+                false => prediction.name.clone(),
+            };
+            let key = prediction.key.as_ref()
+                .filter(|k| **k != prediction.name);
+            if let Some(key) = key {
+                name.push_str(&format!(" ({})", key));
+            }
+            result = result.and_then(|_| writeln!(out, "{}", name));
+        }
+        let result = result
+            .and_then(|_| {
+                print_prediction(out, &prediction, &death_reasons, options)
+            })
+            .and_then(|_| out.flush());
+        check_output(result, options);
+        printed += 1;
+    };
+
+    let mut sorted = vec![];
+    for person in people {
+        progress.inc(1);
+        let user = person.to_user(death_reasons.clone(), max_age, order);
+        let user = match user {
+            Ok(v) => v,
+            Err(e) => {
+                progress.suspend(|| {
+                    cli::print_warning(format!("{}: {}", person.name, e))
+                });
+                continue;
+            },
+        };
+
+        let mut prediction = predict(&user, algorithm, options);
+        prediction.key = person.id;
+        progress.suspend(|| {
+            print_trace(&user, algorithm, options);
+            save_history(&user, &prediction, options);
+            send_webhook(&prediction, options);
+        });
+        match sort {
+            Some(_) => sorted.push(prediction),
+            // Predictions are printed right away, so pipelines get them as
+            // soon as names come in
+            None => print(prediction),
+        }
+    }
+
+    if let Some(key) = sort {
+        key.sort(&mut sorted);
+        if reverse {
+            sorted.reverse();
+        }
+        sorted.into_iter().for_each(&mut print);
+    }
+
+    progress.finish_and_clear();
+
+    if is_array {
+        let result = writeln!(
+            out, "{}", serde_json::to_string(&predictions).unwrap()
+        ).and_then(|_| out.flush());
+        check_output(result, options);
+    }
+    if is_page {
+        let result = write!(
+            out, "{}", report::html(&predictions, &death_reasons)
+        ).and_then(|_| out.flush());
+        check_output(result, options);
+    }
+    save_ical(&predictions, options);
+}
+
+/// Checks birthdays and ages of the people without predicting for them.
+/// Exits with an error if some of them are invalid.
+fn check_people<I>(
+    people: I, death_reasons: &ReasonSet, max_age: u16, order: DateOrder,
+    progress: ProgressBar,
+) where
+    I: IntoIterator<Item = Person>,
+{
+    let mut checked = 0;
+    let mut invalid = 0;
+    for person in people {
+        progress.inc(1);
+        checked += 1;
+        let user = person.to_user(death_reasons.clone(), max_age, order);
+        if let Err(e) = user {
+            invalid += 1;
+            progress.suspend(|| {
+                cli::print_warning(format!("{}: {}", person.name, e))
+            });
+        }
+    }
+    progress.finish_and_clear();
+
+    if invalid > 0 {
+        fail(
+            format!("{} of {} people are invalid", invalid, checked),
+            cli::EXIT_DATE
+        );
+    }
+    println!("All {} people are valid", checked);
+}
+
+fn batch_command(args: &BatchArgs) {
+    let order = args.options.settings.date_order();
+    let people = match batch::read_people(&args.file, order) {
+        Ok(v) => v,
+        Err(e) => {
+            fail(
+                format!("{}: {}", args.file.display(), e), cli::EXIT_IO
+            );
+        },
+    };
+    let mut out = open_output(&args.options);
+    let progress = progress_bar(people.len(), &args.options);
+    predict_people(
+        &mut out, people, &args.options, args.options.settings.algorithm(),
+        progress, args.sort, args.reverse,
+    );
+    close_output(out);
+}
+
+fn import_command(args: &ImportArgs) {
+    let order = args.options.settings.date_order();
+    let contacts = match contacts::read_contacts(&args.file, order) {
+        Ok(v) => v,
+        Err(e) => {
+            fail(
+                format!("{}: {}", args.file.display(), e), cli::EXIT_IO
+            );
+        },
+    };
+    if contacts.skipped > 0 {
+        cli::print_warning(format!(
+            "Skipped {} of {} contacts without birthday", contacts.skipped,
+            contacts.skipped + contacts.people.len()
+        ));
+    }
+    if contacts.people.is_empty() {
+        fail("No contacts with birthday found", cli::EXIT_ERROR);
+    }
+
+    let mut out = open_output(&args.options);
+    let progress = progress_bar(contacts.people.len(), &args.options);
+    predict_people(
+        &mut out, contacts.people, &args.options,
+        args.options.settings.algorithm(), progress, args.sort, args.reverse,
+    );
+    close_output(out);
+}
+
+fn group_command(args: &GroupArgs) {
+    let (max_age, order, death_reasons) = prepare(&args.settings);
+    let mut people = match &args.from {
+        Some(path) => match batch::read_people(path, order) {
+            Ok(v) => v,
+            Err(e) => {
+                fail(
+                    format!("{}: {}", path.display(), e), cli::EXIT_IO
+                );
+            },
+        },
+        None => vec![],
+    };
+    people.extend(args.people.iter().map(|p| Person::parse(p)));
+    if people.len() < 2 {
+        fail("Group needs at least two people", cli::EXIT_USAGE);
+    }
+
+    let mut predictions = vec![];
+    for person in people {
+        let user = match person.to_user(death_reasons.clone(), max_age, order) {
+            Ok(v) => v,
+            Err(e) => {
+                fail(
+                    format!("{}: {}", person.name, e), e.exit_code()
+                );
+            },
+        };
+        let mut prediction = Prediction::new(
+            &user, args.settings.algorithm(), false
+        );
+        if args.time {
+            prediction.death_time = Some(user.get_death_time());
+        }
+        predictions.push(prediction);
+    }
+
+    print!("{}", report::group(&predictions));
+}
+
+fn stats_command(args: &StatsArgs) {
+    let (max_age, order, death_reasons) = prepare(&args.settings);
+    let people = match batch::read_people(&args.file, order) {
+        Ok(v) => v,
+        Err(e) => {
+            fail(
+                format!("{}: {}", args.file.display(), e), cli::EXIT_IO
+            );
+        },
+    };
+    let mut predictions = vec![];
+    for person in people {
+        match person.to_user(death_reasons.clone(), max_age, order) {
+            Ok(user) => predictions.push(
+                Prediction::new(&user, args.settings.algorithm(), false)
+            ),
+            Err(e) => {
+                cli::print_warning(format!("{}: {}", person.name, e))
+            },
+        }
+    }
+
+    let stats = Stats::new(&predictions);
+    match args.json {
+        true => println!("{}", serde_json::to_string(&stats).unwrap()),
+        false if args.chart => {
+            print!("{}", stats.chart(tombstone::terminal_width()))
+        },
+        false => print!("{}", stats),
+    }
+}
+
+fn simulate_command(args: &SimulateArgs) {
+    let max_age = args.settings.max_age();
+    if let Some(age) = args.age.map(u16::from).filter(|&a| a >= max_age) {
+        exit_with(DeathError::AgeTooBig { age, max_age });
+    }
+    let (_, _, death_reasons) = prepare(&args.settings);
+
+    let simulation = Simulation::run(
+        args.samples, args.settings.algorithm(), death_reasons, max_age,
+        args.age
+    );
+    match args.json {
+        true => println!("{}", serde_json::to_string(&simulation).unwrap()),
+        false if args.chart => {
+            print!("{}", simulation.chart(tombstone::terminal_width()))
+        },
+        false => print!("{}", simulation),
+    }
+}
+
+fn page_command(args: &PageArgs) {
+    let (max_age, order, death_reasons) = prepare(&args.settings);
+    let name = args.name.clone().unwrap_or_else(current_user_name);
+    let person = Person {
+        name, birthday: args.birthday.clone(), age: None, id: None,
+    };
+    let user = person.to_user(death_reasons.clone(), max_age, order)
+        .or_exit();
+    let mut prediction = Prediction::new(
+        &user, args.settings.algorithm(), false
+    );
+    if args.time {
+        prediction.death_time = Some(user.get_death_time());
+    }
+
+    if let Err(e) = fs::create_dir_all(&args.out) {
+        fail(
+            format!("{}: {}", args.out.display(), e), cli::EXIT_IO
+        );
+    }
+    let path = args.out.join("index.html");
+    if path.exists() && !args.force {
+        fail(format!(
+            "{}: File exists, pass `--force` to overwrite it", path.display()
+        ), cli::EXIT_IO);
+    }
+    let page = report::page(&prediction, &death_reasons);
+    if let Err(e) = fs::write(&path, page) {
+        fail(format!("{}: {}", path.display(), e), cli::EXIT_IO);
+    }
+}
+
+fn history_command(args: &HistoryArgs, command: &Option<HistoryCommand>) {
+    let path = match paths::history_file() {
+        Some(v) => v,
+        None => {
+            fail("Cannot find user data directory", cli::EXIT_IO);
+        },
+    };
+
+    if let Some(HistoryCommand::Clear) = command {
+        if let Err(e) = history::clear(&path) {
+            fail(
+                format!("{}: {}", path.display(), e), cli::EXIT_IO
+            );
+        }
+        return;
+    }
+
+    let mut records = history::read(&path).unwrap_or_else(|e| {
+        fail(format!("{}: {}", path.display(), e), cli::EXIT_IO);
+    });
+    let args = match command {
+        Some(HistoryCommand::Diff { args }) => args,
+        _ => args,
+    };
+    if let Some(name) = &args.name {
+        records.retain(|r| &r.name == name);
+    }
+    let last = |len: usize| len - args.last.unwrap_or(len).min(len);
+
+    if let Some(HistoryCommand::Diff { .. }) = command {
+        let changes = history::changes(&records);
+        let changes = &changes[last(changes.len())..];
+        if args.json {
+            println!("{}", serde_json::to_string(changes).unwrap());
+            return;
+        }
+        for change in changes {
+            let (before, after) = (change.before, change.after);
+            println!(
+                "{}\t{}\t{} {} -> {} {}\t{} -> {}",
+                after.timestamp, after.name, before.death_date.to_iso(),
+                before.reason, after.death_date.to_iso(), after.reason,
+                before.version, after.version,
+            );
+        }
+        return;
+    }
+
+    let records = &records[last(records.len())..];
+    if args.json {
+        println!("{}", serde_json::to_string(records).unwrap());
+        return;
+    }
+    for record in records {
+        println!(
+            "{}\t{}\t{}\t{}", record.timestamp, record.name,
+            record.death_date.to_iso(), record.reason
+        );
+    }
+}
+
+fn compare_command(args: &CompareArgs) {
+    let (max_age, order, death_reasons) = prepare(&args.settings);
+
+    let people = [
+        (tr(Message::FirstPerson), &args.a_name, &args.a_birthday),
+        (tr(Message::SecondPerson), &args.b_name, &args.b_birthday),
+    ];
+    let mut predictions = vec![];
+    let mut asked = false;
+    for (title, name, birthday) in people {
+        if name.is_none() || birthday.is_none() {
+            println!("{}:", title);
+            asked = true;
+        }
+        let name = match name {
+            Some(v) => v.clone(),
+            None => cli::ask_name().or_exit(),
+        };
+        let person = Person {
+            name, birthday: birthday.clone(), age: None, id: None,
+        };
+        let mut user = person.to_user(
+            death_reasons.clone(), max_age, order
+        ).or_exit();
+        if birthday.is_none() {
+            let birthday = cli::ask_birthday(max_age, order).or_exit();
+            if let Some(birthday) = birthday {
+                user.set_birthday(birthday);
+            }
+        }
+
+        let mut prediction = Prediction::new(
+            &user, args.settings.algorithm(), false
+        );
+        if args.time {
+            prediction.death_time = Some(user.get_death_time());
+        }
+        predictions.push(prediction);
+    }
+
     if asked {
-        println!("");
+        println!();
+    }
+    print!("{}", report::comparison(&predictions[0], &predictions[1]));
+}
+
+fn wizard_command(args: &WizardArgs) {
+    println!("{}", tr(Message::WizardIntro));
+    let mut reasons = args.settings.reasons.clone();
+    let name = cli::ask_name().or_exit();
+    if reasons.pet.is_none() {
+        reasons.pet = cli::ask_choice(tr(Message::Species)).or_exit();
+    }
+    let species = reasons.pet.unwrap_or_default();
+    let max_age = args.settings.max_age.unwrap_or(species.max_age());
+    let birthday = cli::ask_birthday(max_age, args.settings.date_order())
+        .or_exit();
+    // Theme is used only instead of default death reasons of humans
+    let has_reasons = !reasons.death_reasons.is_empty() ||
+        env::var_os(death::reasons::ENV_VAR).is_some();
+    if species == Species::Human && reasons.theme.is_none() && !has_reasons {
+        reasons.theme = cli::ask_choice(tr(Message::Theme)).or_exit();
+    }
+    let algorithm = match args.settings.algorithm {
+        Some(v) => v,
+        None => cli::ask_choice(tr(Message::Formula)).or_exit()
+            .unwrap_or_default(),
+    };
+    let tarot = cli::ask_yes_no(tr(Message::DrawTarot)).or_exit();
+    println!();
+
+    let (death_reasons, _) = user::death_reasons_from(&reasons).or_exit();
+    let mut user = User::new(0, 0, death_reasons.clone());
+    user.set_max_age(max_age);
+    user.set_name(&name);
+    if let Some(birthday) = birthday {
+        user.set_birthday(birthday);
+    }
+    let mut prediction = Prediction::new(&user, algorithm, tarot);
+    prediction.death_time = Some(user.get_death_time());
+    match cli::is_decorated() {
+        true => print!("{}", report::painted_text(&prediction, &death_reasons)),
+        false => print!("{}", report::text(&prediction, &death_reasons)),
+    }
+}
+
+fn fortune_command(args: &FortuneArgs) {
+    let name = args.name.clone().unwrap_or_else(current_user_name);
+    let (death_reasons, _) = user::death_reasons_from(&args.reasons).or_exit();
+    let mut user = User::new(0, 0, death_reasons);
+    user.set_name(&name);
+
+    let fortune = Fortune::of_day(&user, Date::today());
+    match args.json {
+        true => println!("{}", serde_json::to_string(&fortune).unwrap()),
+        false => println!("{}", fortune),
+    }
+}
+
+#[cfg(feature = "notifications")]
+fn show_milestone(milestone: &Milestone) {
+    let body = milestone.to_string();
+    if let Err(e) = death::notification::show("Memento mori", &body) {
+        cli::print_warning(format!("Cannot show notification: {}", e));
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+fn show_milestone(_milestone: &Milestone) {
+    fail(NOTIFICATIONS_UNSUPPORTED, cli::EXIT_USAGE);
+}
+
+#[cfg(feature = "notifications")]
+fn remind_command(args: &RemindArgs) {
+    use std::thread;
+    use std::time::Duration;
+
+    /// How often to check if the next milestone is reached.
+    const INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+    let (max_age, order, death_reasons) = prepare(&args.settings);
+
+    let name = match &args.name {
+        Some(v) => v.clone(),
+        None => cli::ask_name().or_exit(),
+    };
+    let person = Person {
+        name, birthday: args.birthday.clone(), age: None, id: None,
+    };
+    let mut user = person.to_user(death_reasons, max_age, order).or_exit();
+    if args.birthday.is_none() {
+        let birthday = cli::ask_birthday(max_age, order).or_exit();
+        if let Some(birthday) = birthday {
+            user.set_birthday(birthday);
+        }
     }
 
-    let linear = args.linear.unwrap_or(false);
+    let prediction = Prediction::new(&user, args.settings.algorithm(), false);
+    let today = Date::today();
+    let milestones = milestones::milestones(
+        prediction.death_date, prediction.birthday
+    );
+    let upcoming: Vec<_> = milestones.into_iter()
+        .filter(|m| m.date >= today)
+        .collect();
+    if upcoming.is_empty() {
+        println!("No milestones are left");
+        return;
+    }
+    println!("Upcoming milestones:");
+    for milestone in &upcoming {
+        println!("  {}", milestone);
+    }
+
+    for milestone in upcoming {
+        while Date::today() < milestone.date {
+            thread::sleep(INTERVAL);
+        }
+        show_milestone(&milestone);
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+fn remind_command(_args: &RemindArgs) {
+    fail(NOTIFICATIONS_UNSUPPORTED, cli::EXIT_USAGE);
+}
+
+fn check_command(args: &CheckArgs) {
+    if args.notify && !cfg!(feature = "notifications") {
+        fail(NOTIFICATIONS_UNSUPPORTED, cli::EXIT_USAGE);
+    }
+    let path = match paths::history_file() {
+        Some(v) => v,
+        None => {
+            fail("Cannot find user data directory", cli::EXIT_IO);
+        },
+    };
+    let records = history::read(&path).unwrap_or_else(|e| {
+        fail(format!("{}: {}", path.display(), e), cli::EXIT_IO);
+    });
+    let record = records.iter()
+        .rev()
+        .find(|r| args.name.as_ref().is_none_or(|name| &r.name == name));
+    let record = match record {
+        Some(v) => v,
+        None => {
+            fail(
+                "There are no predictions in history, make one first",
+                cli::EXIT_ERROR
+            );
+        },
+    };
+
+    let reached = milestones::reached_on(
+        record.death_date, record.birthday, Date::today()
+    );
+    for milestone in reached {
+        println!("{} today", milestone.text);
+        if args.notify {
+            show_milestone(&milestone);
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn serve_command(args: &ServeArgs) {
+    use death::server::{self, Options, RateLimit, Tls};
+
+    #[cfg(not(feature = "tls"))]
+    if args.tls_cert.is_some() {
+        fail(
+            "TLS is not enabled, build with `tls` feature", cli::EXIT_USAGE
+        );
+    }
+
+    let (death_reasons, _source) =
+        user::death_reasons_from(&args.settings.reasons).or_exit();
+    let options = Options {
+        death_reasons,
+        max_age: args.settings.max_age(),
+        date_order: args.settings.date_order(),
+        algorithm: args.settings.algorithm(),
+        tarot: args.tarot,
+        rate_limit: args.rate_limit.map(|per_minute| RateLimit {
+            per_minute,
+            burst: args.burst.unwrap_or(per_minute),
+        }),
+        cache_size: args.cache_size,
+        tls: args.tls_cert.clone().zip(args.tls_key.clone())
+            .map(|(certificate, private_key)| Tls {
+                certificate, private_key,
+            }),
+        slack_secret: env::var(server::SLACK_SECRET_VAR).ok()
+            .filter(|v| !v.is_empty())
+            .or_else(|| args.slack_secret.clone()),
+    };
+    let address = format!("{}:{}", args.host, args.port);
+    let scheme = match options.tls {
+        Some(_) => "https",
+        None => "http",
+    };
+    eprintln!("Listening on {}://{}", scheme, address);
+    #[cfg(feature = "watch")]
+    let result = match watch_reasons(&args.settings.reasons, _source) {
+        Some(live) => server::serve_live(&address, &options, &live),
+        None => server::serve(&address, &options),
+    };
+    #[cfg(not(feature = "watch"))]
+    let result = server::serve(&address, &options);
+    if let Err(e) = result {
+        fail(format!("{}: {}", address, e), cli::EXIT_IO);
+    }
+}
+
+/// Returns death reasons that are read again when their files change, if
+/// they are read from files and used as they are.
+#[cfg(all(feature = "server", feature = "watch"))]
+fn watch_reasons(args: &cli::ReasonsArgs, source: user::ReasonsSource)
+-> Option<death::watch::LiveReasons> {
+    use death::reasons::Dedup;
+    use death::user::ReasonsSource;
+    use death::watch::LiveReasons;
+
+    let paths = match source {
+        ReasonsSource::Files(v) | ReasonsSource::UserFiles(v) => v,
+        _ => return None,
+    };
+    // Extra and excluded reasons, categories and dedup are not applied to
+    // the reasons read again
+    let is_as_is = args.extra_reasons.is_empty() &&
+        args.exclude_reasons.is_empty() && args.category.is_none() &&
+        args.dedup == Dedup::Off &&
+        !paths.iter().any(|p| p.as_os_str() == "-");
+    if !is_as_is {
+        return None;
+    }
+    match LiveReasons::watch(&paths, args.max_reasons) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            cli::print_warning(
+                format!("Cannot watch death reasons files: {}", e)
+            );
+            None
+        },
+    }
+}
+
+#[cfg(not(feature = "server"))]
+fn serve_command(_args: &ServeArgs) {
+    fail(
+        "HTTP server is not enabled, build with `server` feature",
+        cli::EXIT_USAGE
+    );
+}
+
+fn main() {
+    // Only print here, panics of worker threads must not close the server
+    panic::set_hook(Box::new(|info| {
+        cli::print_error(
+            format!("Internal error: {}", info), cli::EXIT_INTERNAL
+        );
+    }));
+
+    if panic::catch_unwind(run).is_err() {
+        process::exit(cli::EXIT_INTERNAL);
+    }
+}
 
-    predict(&user, linear);
+/// Parses arguments and runs the command.
+fn run() {
+    let args = match cli::parse() {
+        Ok(v) => v,
+        Err(DeathError::Args(e)) => e.exit(),
+        Err(e) => exit_with(e),
+    };
+
+    match &args.command {
+        Some(Command::Predict(predict)) => predict_command(predict),
+        Some(Command::Batch(batch)) => batch_command(batch),
+        Some(Command::Import(import)) => import_command(import),
+        Some(Command::Group(group)) => group_command(group),
+        Some(Command::Stats(stats)) => stats_command(stats),
+        Some(Command::Simulate(simulate)) => simulate_command(simulate),
+        Some(Command::Page(page)) => page_command(page),
+        Some(Command::Reasons { command }) => reasons_command(command),
+        Some(Command::Serve(serve)) => serve_command(serve),
+        Some(Command::Compare(compare)) => compare_command(compare),
+        Some(Command::Wizard(wizard)) => wizard_command(wizard),
+        Some(Command::Fortune(fortune)) => fortune_command(fortune),
+        Some(Command::Remind(remind)) => remind_command(remind),
+        Some(Command::Check(check)) => check_command(check),
+        Some(Command::History { args, command }) => {
+            history_command(args, command)
+        },
+        Some(Command::Completions { shell }) => {
+            cli::print_completions(*shell)
+        },
+        Some(Command::Man) => {
+            if let Err(e) = cli::print_man() {
+                fail(e, cli::EXIT_IO);
+            }
+        },
+        None => predict_command(&args.predict),
+    }
 }
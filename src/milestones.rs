@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::date::Date;
+
+/// Days left until the death that are worth a reminder.
+pub const DAYS_LEFT: [u32; 10] = [
+    20000, 10000, 5000, 1000, 500, 365, 100, 30, 7, 1,
+];
+
+/// Memento mori date of the life.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Milestone {
+    /// Day the milestone is reached
+    pub date: Date,
+    /// What happens on the day, e.g. `10,000 days left`
+    pub text: String,
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.date, self.text)
+    }
+}
+
+/// Returns the number with commas between thousands, e.g. `10,000`.
+fn group_thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut res = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            res.push(',');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Returns milestones of the life that ends on `death_date` sorted by date:
+/// days when round numbers of days are left (see [`DAYS_LEFT`]) and the
+/// middle of the life if the birthday is known. Milestones before the
+/// birthday are left out.
+///
+/// # Example
+///
+/// ```
+/// use death::date::Date;
+/// use death::milestones::milestones;
+///
+/// let death_date = Date::build(2061, 3, 14).unwrap();
+/// let birthday = Date::build(1990, 2, 1).ok();
+/// let milestones = milestones(death_date, birthday);
+///
+/// let last = milestones.last().unwrap();
+/// assert_eq!(last.date, Date::build(2061, 3, 13).unwrap());
+/// assert_eq!(last.text, "1 day left");
+/// ```
+pub fn milestones(death_date: Date, birthday: Option<Date>)
+-> Vec<Milestone> {
+    let mut res: Vec<Milestone> = DAYS_LEFT.iter()
+        .map(|&days| Milestone {
+            date: death_date.add_days(-(days as i64)),
+            text: match days {
+                1 => String::from("1 day left"),
+                _ => format!("{} days left", group_thousands(days)),
+            },
+        })
+        .collect();
+
+    if let Some(birthday) = birthday {
+        res.retain(|m| m.date > birthday);
+        let half = death_date.days_from(birthday) / 2;
+        res.push(Milestone {
+            date: birthday.add_days(half as i64),
+            text: String::from("Half of the life is lived"),
+        });
+    }
+
+    res.sort_by_key(|m| m.date);
+    res
+}
+
+/// Returns milestones of the life reached on the date.
+/// (see [`milestones`])
+pub fn reached_on(death_date: Date, birthday: Option<Date>, date: Date)
+-> Vec<Milestone> {
+    milestones(death_date, birthday).into_iter()
+        .filter(|m| m.date == date)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life() {
+        assert_eq!(group_thousands(20000), "20,000");
+        assert_eq!(group_thousands(500), "500");
+
+        let death_date = Date::build(2061, 3, 14).unwrap();
+        let birthday = Date::build(2000, 1, 2).ok();
+        let all = milestones(death_date, birthday);
+
+        assert!(all.windows(2).all(|w| w[0].date <= w[1].date));
+        assert!(all.iter().all(|m| Some(m.date) > birthday));
+        let half = all.iter()
+            .find(|m| m.text.starts_with("Half"))
+            .unwrap();
+        assert_eq!(reached_on(death_date, birthday, half.date)[0], *half);
+    }
+}
@@ -0,0 +1,17 @@
+use std::io::Error;
+
+/// Shows desktop notification with the summary and the body.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if there is no notification server, e.g. in
+/// SSH session.
+pub fn show(summary: &str, body: &str) -> Result<(), Error> {
+    notify_rust::Notification::new()
+        .appname("death")
+        .summary(summary)
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(Error::other)
+}
@@ -0,0 +1,135 @@
+use crate::date::Date;
+
+/// Returns numerology value of a letter using Pythagorean system: `a`, `j`
+/// and `s` are `1`, `b`, `k` and `t` are `2` and so on.
+///
+/// Characters other than latin letters are worth `0`.
+///
+/// # Example
+///
+/// ```
+/// use death::numerology::letter_value;
+///
+/// assert_eq!(letter_value('a'), 1);
+/// assert_eq!(letter_value('J'), 1);
+/// assert_eq!(letter_value('z'), 8);
+/// assert_eq!(letter_value('!'), 0);
+/// ```
+pub fn letter_value(c: char) -> u32 {
+    match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => (c as u32 - 'a' as u32) % 9 + 1,
+        _ => 0,
+    }
+}
+
+/// Returns digital root of a number: digits are summed repeatedly until
+/// single digit is left.
+///
+/// # Example
+///
+/// ```
+/// use death::numerology::digital_root;
+///
+/// assert_eq!(digital_root(0), 0);
+/// assert_eq!(digital_root(9), 9);
+/// assert_eq!(digital_root(1998), 9);
+/// assert_eq!(digital_root(2023), 7);
+/// ```
+pub fn digital_root(n: u32) -> u32 {
+    if n == 0 {
+        return 0;
+    }
+    (n - 1) % 9 + 1
+}
+
+/// Returns sum of letter values of the name.
+/// (see [`letter_value`])
+pub fn name_sum(name: &str) -> u32 {
+    name.chars().map(letter_value).sum()
+}
+
+/// Returns expression number of the name: digital root of its letter values.
+pub fn expression_number(name: &str) -> u32 {
+    digital_root(name_sum(name))
+}
+
+/// Returns life path number of the birthday: digital root of all its digits.
+///
+/// # Example
+///
+/// ```
+/// use death::date::Date;
+/// use death::numerology::life_path_number;
+///
+/// let birthday = Date::build(1990, 5, 12).unwrap();
+///
+/// assert_eq!(life_path_number(&birthday), 9);
+/// ```
+pub fn life_path_number(birthday: &Date) -> u32 {
+    digital_root(
+        birthday.day() as u32 + birthday.month() as u32 + birthday.year() as u32
+    )
+}
+
+/// Returns digital root of expression and life path numbers, the death age
+/// must have the same one.
+pub fn target_number(name: &str, birthday: &Date) -> u32 {
+    digital_root(expression_number(name) + life_path_number(birthday))
+}
+
+/// Returns ages between current age and `max_age` that can be the death age:
+/// ones with digital root equal to [`target_number`].
+pub fn death_age_candidates(name: &str, birthday: &Date, age: u8, max_age: u16)
+-> Vec<u16> {
+    let target = target_number(name, birthday);
+    (age as u16 + 1..=max_age)
+        .filter(|&a| digital_root(a as u32) == target)
+        .collect()
+}
+
+/// Returns years left for a person of given name, birthday and age.
+///
+/// Death age is one of the ages between current age and `max_age` whose
+/// digital root is equal to the digital root of expression and life path
+/// numbers. The name decides which one of them.
+/// (see [`death_age_candidates`])
+pub fn years_left(name: &str, birthday: &Date, age: u8, max_age: u16) -> u8 {
+    let candidates = death_age_candidates(name, birthday, age, max_age);
+
+    if candidates.is_empty() {
+        return (max_age - age as u16).max(1) as u8;
+    }
+
+    let death_age = candidates[name_sum(name) as usize % candidates.len()];
+
+    (death_age - age as u16) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(name_sum("Alice"), 1 + 3 + 9 + 3 + 5);
+        assert_eq!(expression_number("Alice"), 3);
+        assert_eq!(expression_number(""), 0);
+        assert_eq!(expression_number("Иван"), 0);
+    }
+
+    #[test]
+    fn years() {
+        let birthday = Date::build(1990, 5, 12).unwrap();
+
+        // Target root is 3+9=12 -> 3, candidates are 39, 48, 57, ..., 93
+        // and the name sum 21 picks 21 % 7 = 0th one
+        assert_eq!(years_left("Alice", &birthday, 33, 100), 6);
+
+        // Always within the range
+        for age in 0..100 {
+            let years = years_left("Bob", &birthday, age, 100);
+            assert!(years >= 1);
+            assert!(age as u16 + years as u16 <= 100);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::paths;
+use crate::reasons::{self, ReasonSet};
+
+/// Installed death reasons pack.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Pack {
+    /// Name to use in `--death-reasons`, file name without extensions
+    pub name: String,
+    /// Path to the pack file
+    pub path: PathBuf,
+}
+
+/// Returns pack name from file name or URL: the last path segment without
+/// extensions.
+///
+/// # Example
+///
+/// ```
+/// use death::packs::pack_name;
+///
+/// assert_eq!(pack_name("packs/gothic.json"), "gothic");
+/// assert_eq!(pack_name("https://a.b/sea.txt.gz?raw=1"), "sea");
+/// ```
+pub fn pack_name(source: &str) -> &str {
+    let file_name = file_name(source);
+    file_name.split('.').next().unwrap_or(file_name)
+}
+
+/// Returns file name to store the pack with: the last path segment of
+/// `source`.
+fn file_name(source: &str) -> &str {
+    let source = match source.find(['?', '#']) {
+        Some(i) if reasons::is_url(source) => &source[..i],
+        _ => source,
+    };
+    source.rsplit(['/', '\\']).next().unwrap_or(source)
+}
+
+fn packs_dir() -> Result<PathBuf, Error> {
+    paths::packs_dir().ok_or_else(|| Error::new(
+        ErrorKind::NotFound, "Cannot find user data directory"
+    ))
+}
+
+/// Installs death reasons pack from URL or file into user's packs directory
+/// (see [`paths::packs_dir`]). Installed pack replaces the one with the same
+/// name.
+///
+/// Returns the installed pack with its reasons.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read the pack, it has no valid
+/// reasons or cannot be saved.
+pub fn install(source: &str) -> Result<(Pack, ReasonSet), Error> {
+    install_in(&packs_dir()?, source)
+}
+
+fn install_in(dir: &Path, source: &str) -> Result<(Pack, ReasonSet), Error> {
+    let name = pack_name(source);
+    let file_name = file_name(source);
+    if name.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput, format!("Invalid pack name `{}`", source)
+        ));
+    }
+
+    let path = Path::new(source);
+    let is_file = !reasons::is_url(source) && path.is_file();
+    if !reasons::is_url(source) && !is_file {
+        return Err(Error::new(ErrorKind::NotFound, format!(
+            "Pack `{}` not found, pass a URL or a file", source
+        )));
+    }
+
+    let reasons = reasons::read_reasons(path)?;
+
+    fs::create_dir_all(dir)?;
+    match remove_in(dir, name) {
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+        _ => (),
+    }
+
+    let target = dir.join(file_name);
+    if is_file {
+        fs::copy(path, &target)?;
+    } else {
+        let (contents, _) = reasons::read_contents(path)?;
+        fs::write(&target, contents)?;
+    }
+
+    Ok((Pack { name: name.to_string(), path: target }, reasons))
+}
+
+/// Returns installed packs sorted by name.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read the packs directory.
+pub fn list() -> Result<Vec<Pack>, Error> {
+    list_in(&packs_dir()?)
+}
+
+fn list_in(dir: &Path) -> Result<Vec<Pack>, Error> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let res = match reasons::dir_files(dir) {
+        Ok(v) => v,
+        Err(e) if e.kind() == ErrorKind::Other => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    Ok(res.into_iter()
+        .map(|path| Pack {
+            name: pack_name(&path.to_string_lossy()).to_string(),
+            path,
+        })
+        .collect())
+}
+
+/// Returns installed pack with the name.
+pub fn find(name: &str) -> Option<Pack> {
+    find_in(&packs_dir().ok()?, name)
+}
+
+fn find_in(dir: &Path, name: &str) -> Option<Pack> {
+    list_in(dir).ok()?.into_iter().find(|p| p.name == name)
+}
+
+/// Removes installed pack.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of kind [`ErrorKind::NotFound`] if there is no
+/// such pack, or other one if cannot remove the file.
+pub fn remove(name: &str) -> Result<Pack, Error> {
+    remove_in(&packs_dir()?, name)
+}
+
+fn remove_in(dir: &Path, name: &str) -> Result<Pack, Error> {
+    let pack = find_in(dir, name).ok_or_else(|| Error::new(
+        ErrorKind::NotFound, format!("Pack `{}` is not installed", name)
+    ))?;
+    fs::remove_file(&pack.path)?;
+    Ok(pack)
+}
+
+/// Returns path to the installed pack if `path` is a pack name and not an
+/// existing file. Otherwise `path` itself is returned.
+pub fn resolve(path: &Path) -> PathBuf {
+    match packs_dir() {
+        Ok(dir) => resolve_in(&dir, path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+fn resolve_in(dir: &Path, path: &Path) -> PathBuf {
+    let name = path.to_string_lossy();
+    let is_name = !name.is_empty() && name != "-" &&
+        !name.contains(['/', '\\', '.']) &&
+        !path.exists();
+    if !is_name {
+        return path.to_path_buf();
+    }
+
+    match find_in(dir, &name) {
+        Some(pack) => pack.path,
+        None => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn install_and_remove() {
+        let dir = env::temp_dir()
+            .join(format!("death-packs-{}", std::process::id()));
+
+        let (pack, reasons) = install_in(&dir, "tests/read_file.json")
+            .unwrap();
+        assert_eq!(pack.name, "read_file");
+        assert_eq!(reasons.len(), 4);
+        install_in(&dir, "tests/comments.txt.gz").unwrap();
+
+        let names: Vec<String> = list_in(&dir).unwrap().into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["comments", "read_file"]);
+        assert_eq!(resolve_in(&dir, Path::new("read_file")), pack.path);
+        assert_eq!(
+            resolve_in(&dir, Path::new("other")), PathBuf::from("other")
+        );
+        let path = resolve_in(&dir, Path::new("comments"));
+        assert_eq!(reasons::read_reasons(&path).unwrap().len(), 4);
+
+        remove_in(&dir, "read_file").unwrap();
+        assert!(!pack.path.exists());
+        let err = remove_in(&dir, "read_file").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        assert!(install_in(&dir, "no-such-pack").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
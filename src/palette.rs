@@ -0,0 +1,172 @@
+use std::sync::RwLock;
+
+#[cfg(feature = "cli")]
+use colored::{Color, ColoredString, Colorize};
+use serde::Deserialize;
+
+/// Colors of the output.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Palette {
+    /// Red and yellow
+    #[default]
+    Doom,
+    /// Soft pink, peach and blue
+    Pastel,
+    /// No colors, only bold, italic and underlined text
+    Mono,
+    /// Green on black like old terminals
+    Hacker,
+}
+
+/// What the painted text is.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Role {
+    /// Headers of the prediction
+    Headline,
+    /// Label of warnings
+    Warning,
+    /// Label of errors
+    Error,
+    /// Time left until the death
+    Countdown,
+}
+
+#[cfg(feature = "cli")]
+impl Palette {
+    /// Returns color of the text of the role, [`None`] if the text keeps
+    /// color of the terminal.
+    pub fn color(&self, role: Role) -> Option<Color> {
+        let pastel = |r, g, b| Color::TrueColor { r, g, b };
+        let color = match (self, role) {
+            (Palette::Doom, Role::Warning) => Color::Yellow,
+            (Palette::Doom, Role::Countdown) => Color::BrightRed,
+            (Palette::Doom, _) => Color::Red,
+            (Palette::Pastel, Role::Headline) => pastel(255, 179, 186),
+            (Palette::Pastel, Role::Warning) => pastel(255, 223, 186),
+            (Palette::Pastel, Role::Error) => pastel(255, 140, 148),
+            (Palette::Pastel, Role::Countdown) => pastel(186, 225, 255),
+            (Palette::Mono, _) => return None,
+            (Palette::Hacker, Role::Warning | Role::Countdown) => Color::Green,
+            (Palette::Hacker, _) => Color::BrightGreen,
+        };
+        Some(color)
+    }
+
+    /// Returns the text painted for the role. Whether it is painted at all
+    /// depends on `--color` option. (see [`crate::cli::set_color`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colored::Color;
+    /// use death::palette::{Palette, Role};
+    ///
+    /// colored::control::set_override(true);
+    /// let text = Palette::Hacker.paint("R.I.P.", Role::Headline);
+    ///
+    /// assert_eq!(text.fgcolor(), Some(Color::BrightGreen));
+    /// ```
+    pub fn paint(&self, text: &str, role: Role) -> ColoredString {
+        let res = match self.color(role) {
+            Some(color) => text.color(color),
+            None => text.normal(),
+        };
+        match (self, role) {
+            (_, Role::Headline) | (Palette::Mono, Role::Error) => res.bold(),
+            (Palette::Mono, Role::Warning) => res.italic(),
+            (Palette::Mono, Role::Countdown) => res.underline(),
+            _ => res,
+        }
+    }
+}
+
+/// When to use colors in messages.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// If `NO_COLOR` environment variable is not set and stderr is a
+    /// terminal
+    #[default]
+    Auto,
+    /// Always
+    Always,
+    /// Never
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns `true` if colors should be used. `no_color` is `true` if
+    /// `NO_COLOR` environment variable is set and not empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::palette::ColorChoice;
+    ///
+    /// assert!(ColorChoice::Auto.enabled(false, true));
+    /// assert!(!ColorChoice::Auto.enabled(true, true));
+    /// assert!(!ColorChoice::Auto.enabled(false, false));
+    /// assert!(ColorChoice::Always.enabled(true, false));
+    /// ```
+    pub fn enabled(&self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => !no_color && is_terminal,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Palette of the output, [`Palette::Doom`] until it is set.
+static PALETTE: RwLock<Palette> = RwLock::new(Palette::Doom);
+
+/// Set palette of the output.
+pub fn set_palette(palette: Palette) {
+    *PALETTE.write().unwrap() = palette;
+}
+
+/// Returns palette of the output. (see [`set_palette`])
+pub fn palette() -> Palette {
+    *PALETTE.read().unwrap()
+}
+
+/// Returns the text painted for the role with palette of the output.
+/// (see [`Palette::paint`])
+#[cfg(feature = "cli")]
+pub fn paint(text: &str, role: Role) -> ColoredString {
+    palette().paint(text, role)
+}
+
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn palettes() {
+        let roles = [Role::Headline, Role::Warning, Role::Error];
+        for palette in Palette::value_variants() {
+            if *palette == Palette::Mono {
+                assert!(roles.iter().all(|r| palette.color(*r).is_none()));
+                continue;
+            }
+            assert_ne!(
+                palette.color(Role::Warning), palette.color(Role::Error),
+                "{:?}", palette
+            );
+        }
+    }
+}
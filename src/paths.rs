@@ -0,0 +1,78 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Name of the program's directory inside config and data directories.
+const APP_DIR: &str = "death";
+
+/// Returns program's config directory: `$XDG_CONFIG_HOME/death` if the
+/// variable is set, or platform's config directory otherwise (e.g.
+/// `~/.config/death` on Linux or `%APPDATA%\death` on Windows).
+pub fn config_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(v) if !v.is_empty() => PathBuf::from(v),
+        _ => dirs::config_dir()?,
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// Returns program's data directory: `$XDG_DATA_HOME/death` if the variable
+/// is set, or platform's data directory otherwise (e.g.
+/// `~/.local/share/death` on Linux or `%APPDATA%\death` on Windows).
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(v) if !v.is_empty() => PathBuf::from(v),
+        _ => dirs::data_dir()?,
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// Returns program's cache directory: `$XDG_CACHE_HOME/death` if the
+/// variable is set, or platform's cache directory otherwise (e.g.
+/// `~/.cache/death` on Linux or `%LOCALAPPDATA%\death` on Windows).
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(v) if !v.is_empty() => PathBuf::from(v),
+        _ => dirs::cache_dir()?,
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// Returns path to config file `config.toml` inside [`config_dir`].
+pub fn config_file() -> Option<PathBuf> {
+    Some(config_dir()?.join("config.toml"))
+}
+
+/// Returns directory of installed death reasons packs `packs` inside
+/// [`data_dir`].
+pub fn packs_dir() -> Option<PathBuf> {
+    Some(data_dir()?.join("packs"))
+}
+
+/// Returns path to prediction history file `history.jsonl` inside
+/// [`data_dir`].
+pub fn history_file() -> Option<PathBuf> {
+    Some(data_dir()?.join("history.jsonl"))
+}
+
+/// Returns paths to user's default death reasons file `reasons.txt` and
+/// directory `reasons.d` inside [`config_dir`] if they exist.
+pub fn reasons_files() -> Vec<PathBuf> {
+    let dir = match config_dir() {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let mut res = vec![];
+
+    let file = dir.join("reasons.txt");
+    if file.is_file() {
+        res.push(file);
+    }
+
+    let packs = dir.join("reasons.d");
+    if packs.is_dir() {
+        res.push(packs);
+    }
+
+    res
+}
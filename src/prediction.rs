@@ -0,0 +1,397 @@
+use std::io::{Error, ErrorKind};
+
+use serde::Serialize;
+
+use crate::algorithm::Algorithm;
+use crate::date::{Date, Time};
+use crate::locale::{fill, tr, Message};
+use crate::reasons::Reason;
+use crate::tarot::Card;
+use crate::user::User;
+
+/// Everything predicted for the user.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Prediction {
+    /// User's name
+    pub name: String,
+    /// User's id the prediction is derived from
+    pub id: u64,
+    /// Id of the person in the file the prediction is made from, e.g.
+    /// GEDCOM individual `@I1@`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// User's birthday if it is known
+    #[cfg_attr(feature = "server",
+        schema(value_type = Option<String>, format = Date))]
+    pub birthday: Option<Date>,
+    /// Predicted death date
+    #[cfg_attr(feature = "server", schema(value_type = String, format = Date))]
+    pub death_date: Date,
+    /// Predicted time of death if it is asked for
+    /// (see [`User::get_death_time`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "server", schema(value_type = Option<String>))]
+    pub death_time: Option<Time>,
+    /// Full years left until the death date
+    pub years_left: u16,
+    /// Days left until the death date
+    pub days_left: u32,
+    /// Age at the death date if birthday is known
+    pub age_at_death: Option<u16>,
+    /// First year of the range the death most likely happens in
+    pub likely_from: u16,
+    /// Last year of the range the death most likely happens in
+    pub likely_to: u16,
+    /// Predicted death reason
+    #[serde(flatten)]
+    pub reason: Reason,
+    /// Tarot card the reason was chosen by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<Card>,
+    /// Formula the date was calculated with
+    pub algorithm: Algorithm,
+}
+
+impl Prediction {
+    /// Returns prediction for the user. If `tarot` is `true`, the reason is
+    /// chosen by a tarot card. (see [`User::get_tarot_reason`])
+    pub fn new(user: &User, algorithm: Algorithm, tarot: bool) -> Prediction {
+        let today = Date::today();
+        let death_date = user.get_death_date(algorithm);
+        let range = user.get_confidence_range(algorithm);
+        let (card, reason) = if tarot {
+            let (card, reason) = user.get_tarot_reason();
+            (Some(card), reason.clone())
+        } else {
+            (None, user.get_death_reason().clone())
+        };
+
+        Prediction {
+            name: user.name().to_string(),
+            id: user.id(),
+            key: None,
+            birthday: user.birthday(),
+            death_date,
+            death_time: None,
+            years_left: death_date.years_from(today),
+            days_left: death_date.days_from(today),
+            age_at_death: user.birthday().map(|b| b.years_from(death_date)),
+            likely_from: *range.start(),
+            likely_to: *range.end(),
+            reason,
+            card,
+            algorithm,
+        }
+    }
+
+    /// Returns prediction as JSON object in one line.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Returns prediction as JSON object for custom modules of status bars
+    /// like Waybar and i3status-rust: days left are the text, the date and
+    /// the reason are the tooltip, and the class is `critical` if less than
+    /// [`CRITICAL_DAYS`] are left, `warning` if less than [`WARNING_DAYS`],
+    /// `normal` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::{Reason, ReasonSet};
+    /// use death::user::User;
+    /// use serde_json::Value;
+    ///
+    /// let reasons = ReasonSet::new(vec![Reason::new("cars")]).unwrap();
+    /// let user = User::new(1234567890, 45, reasons);
+    /// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+    ///
+    /// let value: Value = serde_json::from_str(&prediction.to_waybar())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(value["text"], prediction.days_left.to_string());
+    /// assert!(value["tooltip"].as_str().unwrap().contains("cars"));
+    /// ```
+    pub fn to_waybar(&self) -> String {
+        let class = match self.days_left {
+            d if d < CRITICAL_DAYS => "critical",
+            d if d < WARNING_DAYS => "warning",
+            _ => "normal",
+        };
+        let reason = fill(tr(Message::BeAwareOf), &[&self.reason]);
+        let tooltip = format!("{}\n{}", self.death_date, reason);
+        serde_json::json!({
+            "text": self.days_left.to_string(),
+            "tooltip": tooltip,
+            "class": class,
+        }).to_string()
+    }
+
+    /// Returns row of CSV output with columns of [`CSV_HEADER`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::{Reason, ReasonSet};
+    /// use death::user::User;
+    ///
+    /// let reasons = ReasonSet::new(vec![Reason::new("cars")]).unwrap();
+    /// let mut user = User::new(1234567890, 45, reasons);
+    /// user.set_name("Alice");
+    /// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+    ///
+    /// let row = prediction.to_csv_row();
+    ///
+    /// assert_eq!(row[0], "Alice");
+    /// assert_eq!(row[1], "");
+    /// assert_eq!(row[4], "cars");
+    /// ```
+    pub fn to_csv_row(&self) -> [String; 6] {
+        [
+            self.name.clone(),
+            self.birthday.map(|b| b.to_iso()).unwrap_or_default(),
+            self.death_date.to_iso(),
+            self.death_date.get_weekday_name().to_string(),
+            self.reason.text.clone(),
+            self.years_left.to_string(),
+        ]
+    }
+
+    /// Returns the death date and the reason in one short line, e.g.
+    /// `☠ 2061-03-14 (cars)`, for shell prompts and status lines. The skull
+    /// is left out if `emoji` is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::{Reason, ReasonSet};
+    /// use death::user::User;
+    ///
+    /// let reasons = ReasonSet::new(vec![Reason::new("cars")]).unwrap();
+    /// let user = User::new(1234567890, 45, reasons);
+    /// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+    /// let date = prediction.death_date.to_iso();
+    ///
+    /// assert_eq!(prediction.oneline(true), format!("☠ {} (cars)", date));
+    /// assert_eq!(prediction.oneline(false), format!("{} (cars)", date));
+    /// ```
+    pub fn oneline(&self, emoji: bool) -> String {
+        let line = format!("{} ({})", self.death_date.to_iso(), self.reason);
+        match emoji {
+            true => format!("☠ {}", line),
+            false => line,
+        }
+    }
+
+    /// Returns value of the placeholder (see [`PLACEHOLDERS`]), or [`None`]
+    /// if there is no such placeholder. Unknown values are empty.
+    pub fn placeholder(&self, name: &str) -> Option<String> {
+        let optional = |v: Option<&String>| v.cloned().unwrap_or_default();
+        let value = match name {
+            "name" => self.name.clone(),
+            "id" => self.id.to_string(),
+            "birthday" => self.birthday.map(|b| b.to_string())
+                .unwrap_or_default(),
+            "date" => self.death_date.to_string(),
+            "iso_date" => self.death_date.to_iso(),
+            "year" => self.death_date.year().to_string(),
+            "month" => self.death_date.get_month_name().to_string(),
+            "day" => self.death_date.day().to_string(),
+            "weekday" => self.death_date.get_weekday_name().to_string(),
+            "time" => self.death_time.map(|t| t.to_string())
+                .unwrap_or_default(),
+            "years_left" => self.years_left.to_string(),
+            "days_left" => self.days_left.to_string(),
+            "age_at_death" => self.age_at_death.map(|a| a.to_string())
+                .unwrap_or_default(),
+            "likely_from" => self.likely_from.to_string(),
+            "likely_to" => self.likely_to.to_string(),
+            "reason" => self.reason.text.clone(),
+            "category" => optional(self.reason.category.as_ref()),
+            "tip" => optional(self.reason.tip.as_ref()),
+            "emoji" => self.reason.icon().to_string(),
+            "card" => optional(self.card.as_ref().map(|c| &c.name)),
+            "algorithm" => format!("{:?}", self.algorithm).to_lowercase(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns prediction formatted by template. Placeholders in braces are
+    /// replaced with their values (see [`PLACEHOLDERS`]), `{{` and `}}` are
+    /// replaced with braces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidInput`] if
+    /// there is unknown placeholder or unmatched brace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::{Reason, ReasonSet};
+    /// use death::user::User;
+    ///
+    /// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+    /// let user = User::new(1234567890, 45, reasons);
+    /// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+    ///
+    /// assert_eq!(
+    ///     prediction.format("{{{reason}}}").unwrap(),
+    ///     "{lego}"
+    /// );
+    /// assert!(prediction.format("{unknown}").is_err());
+    /// ```
+    pub fn format(&self, template: &str) -> Result<String, Error> {
+        let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, msg);
+        let mut res = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    res.push('{');
+                },
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    res.push('}');
+                },
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(invalid(String::from(
+                                "Unmatched `{`, use `{{` to print it"
+                            ))),
+                        }
+                    }
+                    match self.placeholder(&name) {
+                        Some(v) => res.push_str(&v),
+                        None => return Err(invalid(
+                            format!("Unknown placeholder `{{{}}}`", name)
+                        )),
+                    }
+                },
+                '}' => return Err(invalid(String::from(
+                    "Unmatched `}`, use `}}` to print it"
+                ))),
+                c => res.push(c),
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Columns of CSV output. (see [`Prediction::to_csv_row`])
+pub const CSV_HEADER: [&str; 6] = [
+    "name", "birthday", "death_date", "weekday", "reason", "years_left",
+];
+
+/// Days left when status bar output is `critical`.
+/// (see [`Prediction::to_waybar`])
+pub const CRITICAL_DAYS: u32 = 365;
+
+/// Days left when status bar output is `warning`.
+/// (see [`Prediction::to_waybar`])
+pub const WARNING_DAYS: u32 = 3650;
+
+/// Placeholders that can be used in templates. (see [`Prediction::format`])
+pub const PLACEHOLDERS: [(&str, &str); 21] = [
+    ("name", "your name"),
+    ("id", "id the prediction is derived from"),
+    ("birthday", "your birthday"),
+    ("date", "death date, e.g. `4 April 2077`"),
+    ("iso_date", "death date in ISO format, e.g. `2077-04-04`"),
+    ("year", "year of death"),
+    ("month", "month of death, e.g. `April`"),
+    ("day", "day of month of death"),
+    ("weekday", "day of week of death, e.g. `Sunday`"),
+    ("time", "time of death, e.g. `3:47 AM`, empty if it is not predicted"),
+    ("years_left", "full years left"),
+    ("days_left", "days left"),
+    ("age_at_death", "age at death, empty if birthday is unknown"),
+    ("likely_from", "first year the death most likely happens in"),
+    ("likely_to", "last year the death most likely happens in"),
+    ("reason", "death reason"),
+    ("category", "category of the reason"),
+    ("tip", "survival tip"),
+    ("emoji", "emoji of the reason, e.g. `🔥`"),
+    ("card", "tarot card drawn"),
+    ("algorithm", "formula the date was calculated with"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reasons::ReasonSet;
+    use serde_json::Value;
+
+    #[test]
+    fn json() {
+        let mut user = User::new(1234567890, 0, ReasonSet::default());
+        user.set_birthday(Date::build(2000, 1, 2).unwrap());
+        let prediction = Prediction::new(&user, Algorithm::Linear, false);
+        let value: Value = serde_json::from_str(&prediction.to_json()).unwrap();
+
+        assert_eq!(value["id"], 1234567890);
+        assert_eq!(value["birthday"], "2000-01-02");
+        assert_eq!(value["algorithm"], "linear");
+        assert_eq!(value["reason"], prediction.reason.text);
+        assert_eq!(value["category"], "natural");
+        assert_eq!(value["death_date"], prediction.death_date.to_iso());
+        assert!(value.get("card").is_none());
+        assert!(value.get("death_time").is_none());
+
+        let prediction = Prediction::new(&user, Algorithm::Linear, true);
+        let value: Value = serde_json::from_str(&prediction.to_json()).unwrap();
+        assert!(value["card"]["name"].is_string());
+
+        let mut prediction = prediction;
+        prediction.death_time = Some(user.get_death_time());
+        let value: Value = serde_json::from_str(&prediction.to_json()).unwrap();
+        let time = user.get_death_time().to_string();
+        assert_eq!(value["death_time"], time);
+    }
+
+    #[test]
+    fn template() {
+        let mut user = User::new(1234567890, 0, ReasonSet::default());
+        user.set_birthday(Date::build(2000, 1, 2).unwrap());
+        let prediction = Prediction::new(&user, Algorithm::Linear, false);
+
+        for (name, _) in PLACEHOLDERS {
+            assert!(prediction.placeholder(name).is_some(), "{}", name);
+        }
+        assert_eq!(
+            prediction.format("{iso_date}\t{reason}").unwrap(),
+            format!("{}\tfood", prediction.death_date.to_iso())
+        );
+        assert_eq!(
+            prediction.format("{card}|{tip}|{age_at_death}").unwrap(),
+            format!("||{}", prediction.age_at_death.unwrap())
+        );
+
+        let err = prediction.format("{dates}").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown placeholder `{dates}`");
+        let err = prediction.format("{date").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = prediction.format("date}").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
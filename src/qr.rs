@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path::Path;
+
+use qrcode::render::unicode::Dense1x2;
+use qrcode::{Color, QrCode};
+
+use crate::prediction::Prediction;
+
+/// Width of the empty border around the code in modules.
+const QUIET_ZONE: usize = 4;
+
+/// Size of a module in PNG image in pixels.
+const MODULE_SIZE: usize = 8;
+
+/// Returns text to share the prediction with: the name, the death date and
+/// the reason.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::qr;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// assert!(qr::summary(&prediction).starts_with("Alice dies on "));
+/// ```
+pub fn summary(prediction: &Prediction) -> String {
+    let name = match prediction.name.as_str() {
+        "" => "Somebody",
+        name => name,
+    };
+    format!(
+        "{} dies on {} of {}", name, prediction.death_date.to_iso(),
+        prediction.reason
+    )
+}
+
+fn encode(text: &str) -> Result<QrCode, Error> {
+    QrCode::new(text.as_bytes())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))
+}
+
+/// Returns QR code of the text drawn with Unicode blocks, light on dark,
+/// so it can be scanned from a terminal.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if the text is too long for QR code.
+pub fn render(text: &str) -> Result<String, Error> {
+    Ok(encode(text)?.render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build())
+}
+
+/// Saves QR code of the text to PNG image.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if the text is too long for QR code or
+/// cannot write the file.
+pub fn write_png(text: &str, file_path: &Path) -> Result<(), Error> {
+    let code = encode(text)?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + 2 * QUIET_ZONE) * MODULE_SIZE;
+
+    let mut pixels = vec![u8::MAX; size * size];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x = (i % modules + QUIET_ZONE) * MODULE_SIZE;
+        let y = (i / modules + QUIET_ZONE) * MODULE_SIZE;
+        for row in y..y + MODULE_SIZE {
+            pixels[row * size + x..row * size + x + MODULE_SIZE].fill(0);
+        }
+    }
+
+    let file = BufWriter::new(File::create(file_path)?);
+    let mut encoder = png::Encoder::new(file, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    #[test]
+    fn codes() {
+        let code = render("lego").unwrap();
+        let width = code.lines().next().unwrap().chars().count();
+        assert!(code.lines().all(|l| l.chars().count() == width));
+
+        let file_path = env::temp_dir()
+            .join(format!("death-qr-{}.png", std::process::id()));
+        write_png("lego", &file_path).unwrap();
+        let image = fs::read(&file_path).unwrap();
+        assert!(image.starts_with(b"\x89PNG"));
+        fs::remove_file(&file_path).unwrap();
+
+        assert!(render(&"lego".repeat(1000)).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Time every frame of the animation is shown for.
+pub const FRAME_DELAY: Duration = Duration::from_millis(400);
+
+/// Frames of the Grim Reaper coming for you, all of the same height.
+pub const FRAMES: [&str; 4] = [
+    r"
+                       .---.
+                      /     \
+                      |() ()|
+                       \ ^ /
+                        |||
+",
+    r"
+              ___
+         .---'   `.       .---.
+        /           \    /     \
+                    |    |() ()|
+                    |     \ ^ /
+                    |      |||
+",
+    r"
+       __________
+      '          `.  .---.
+                   \/     \
+                   ||() ()|
+                   | \ ^ /
+                   |  |||
+",
+    r"
+    __________
+   '          `.---.
+              //     \
+              ||() ()|   IT IS TIME.
+              | \ ^ /
+              |  |||
+",
+];
+
+/// Returns the frame padded with empty lines to the height of the highest
+/// frame, so frames overwrite each other completely.
+fn pad(frame: &str, height: usize) -> String {
+    let mut res = frame.trim_matches('\n').to_string();
+    res.push('\n');
+    for _ in frame.trim_matches('\n').lines().count()..height {
+        res.push('\n');
+    }
+    res
+}
+
+/// Plays the animation in the terminal, every frame is shown for `delay`.
+/// The last frame stays on the screen.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot write to `out`.
+pub fn animate(out: &mut dyn Write, delay: Duration) -> io::Result<()> {
+    let height = FRAMES.iter()
+        .map(|f| f.trim_matches('\n').lines().count())
+        .max()
+        .unwrap_or(0);
+    for (i, frame) in FRAMES.iter().enumerate() {
+        if i > 0 {
+            // Move cursor to the first line of the previous frame and clear
+            // everything below it
+            write!(out, "\x1b[{}A\x1b[J", height)?;
+        }
+        write!(out, "{}", pad(frame, height))?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        assert_eq!(pad("\na\nb\n", 3), "a\nb\n\n");
+        let mut out = vec![];
+        animate(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[6A\x1b[J").count(), FRAMES.len() - 1);
+        assert!(out.contains("IT IS TIME."));
+    }
+}
@@ -0,0 +1,1688 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::{self, File};
+use std::fmt;
+use std::env;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::io::{self, BufRead, BufReader, Error, ErrorKind, Read};
+
+use crate::locale::Language;
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Death reason with its weight and category.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Reason {
+    /// Reason itself, e.g. `cars`
+    #[serde(rename = "reason", alias = "text")]
+    pub text: String,
+    /// How often the reason is chosen compared to others
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Category of the reason, e.g. `accident`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// How to avoid the death, e.g. `stay away from open water`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tip: Option<String>,
+    /// Emoji of the reason, e.g. `🦈`. (see [`Reason::icon`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl Reason {
+    /// Returns a new reason with weight `1` and without category.
+    pub fn new(text: &str) -> Reason {
+        Reason {
+            text: text.to_string(), weight: 1, category: None, tip: None,
+            emoji: None,
+        }
+    }
+
+    /// Returns emoji of the reason: its own one if the pack sets it, the
+    /// default one if it is a default reason in any language, or `☠`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::reasons::Reason;
+    ///
+    /// let emoji = Some("🦈".to_string());
+    /// let shark = Reason { emoji, ..Reason::new("sharks") };
+    ///
+    /// assert_eq!(shark.icon(), "🦈");
+    /// assert_eq!(Reason::new("Fire").icon(), "🔥");
+    /// assert_eq!(Reason::new("feu").icon(), "🔥");
+    /// assert_eq!(Reason::new("boredom").icon(), "☠");
+    /// ```
+    pub fn icon(&self) -> &str {
+        if let Some(emoji) = &self.emoji {
+            return emoji;
+        }
+        Language::ALL.into_iter()
+            .find_map(|language| crate::default_death_reasons_in(language)
+                .iter()
+                .position(|r| r.to_lowercase() == self.text.to_lowercase()))
+            .map_or("☠", |i| DEFAULT_EMOJI[i])
+    }
+
+    /// Returns reasons with weight `1` and without category from strings.
+    pub fn from_strings(strings: Vec<String>) -> Vec<Reason> {
+        strings.iter().map(|s| Reason::new(s)).collect()
+    }
+}
+
+/// Categories of default death reasons in the same order.
+/// (see [`crate::default_death_reasons`])
+pub const DEFAULT_CATEGORIES: [&str; 14] = [
+    "accident", "natural", "accident", "mystery", "accident", "accident",
+    "natural", "accident", "accident", "accident", "natural", "natural",
+    "natural", "violence",
+];
+
+/// Emoji of default death reasons in the same order.
+/// (see [`crate::default_death_reasons`])
+pub const DEFAULT_EMOJI: [&str; 14] = [
+    "🚗", "🤒", "🧗", "🌑", "🔥", "🌊", "🌿", "🏗️", "⚡", "💥", "🍔", "🐻",
+    "🌡️", "🔫",
+];
+
+/// Returns default death reasons in the language with their categories and
+/// emoji.
+pub fn defaults(language: Language) -> Vec<Reason> {
+    crate::default_death_reasons_in(language).iter()
+        .zip(DEFAULT_CATEGORIES)
+        .zip(DEFAULT_EMOJI)
+        .map(|((text, category), emoji)| Reason {
+            text: text.to_string(),
+            weight: 1,
+            category: Some(category.to_string()),
+            tip: None,
+            emoji: Some(emoji.to_string()),
+        })
+        .collect()
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Non-empty set of death reasons.
+///
+/// Prefix sums of weights are precomputed, so a weighted pick takes
+/// O(log n) time even for huge packs. The set dereferences to a slice of
+/// reasons.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+#[serde(try_from = "Vec<Reason>", into = "Vec<Reason>")]
+pub struct ReasonSet {
+    reasons: Vec<Reason>,
+    /// Sum of weights of all reasons up to and including the one at index
+    cumulative: Vec<u64>,
+    /// Information about the pack the reasons are read from
+    metadata: Metadata,
+}
+
+impl ReasonSet {
+    /// Returns a new set of death reasons.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidInput`] if
+    /// there are no reasons.
+    pub fn new(reasons: Vec<Reason>) -> Result<ReasonSet, Error> {
+        if reasons.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "No death reasons"));
+        }
+        Ok(ReasonSet::build(reasons))
+    }
+
+    /// Returns a new set of reasons with weight `1` and without category from
+    /// strings. (see [`ReasonSet::new`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if there are no reasons.
+    pub fn from_strings(strings: Vec<String>) -> Result<ReasonSet, Error> {
+        ReasonSet::new(Reason::from_strings(strings))
+    }
+
+    /// Returns default death reasons in the language with their categories.
+    /// (see [`defaults`])
+    pub fn defaults(language: Language) -> ReasonSet {
+        ReasonSet::build(defaults(language))
+    }
+
+    /// Returns a set without checking that `reasons` is not empty.
+    fn build(reasons: Vec<Reason>) -> ReasonSet {
+        let mut total = 0;
+        let cumulative = reasons.iter()
+            .map(|r| {
+                total += r.weight as u64;
+                total
+            })
+            .collect();
+        ReasonSet { reasons, cumulative, metadata: Metadata::default() }
+    }
+
+    /// Returns the set with metadata of the pack it is read from.
+    pub fn with_metadata(mut self, metadata: Metadata) -> ReasonSet {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns metadata of the pack the reasons are read from, empty if
+    /// there is none. (see [`parse_metadata`])
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns sum of weights of all reasons.
+    pub fn total_weight(&self) -> u64 {
+        self.cumulative.last().copied().unwrap_or(0)
+    }
+
+    /// Returns index of the reason chosen by `id`. Reasons with greater
+    /// weight are chosen more often, reasons with zero weight are never
+    /// chosen.
+    ///
+    /// If all weights are `1`, it is `id % len`. If all weights are `0`, all
+    /// reasons have the same chance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::reasons::{Reason, ReasonSet};
+    ///
+    /// let mut reasons = vec![Reason::new("cars"), Reason::new("fire")];
+    /// reasons[1].weight = 3;
+    /// let set = ReasonSet::new(reasons).unwrap();
+    ///
+    /// assert_eq!(set.pick_index(0), 0);
+    /// assert_eq!(set.pick_index(1), 1);
+    /// assert_eq!(set.pick_index(3), 1);
+    /// assert_eq!(set.pick_index(4), 0);
+    /// ```
+    pub fn pick_index(&self, id: u64) -> usize {
+        let total = self.total_weight();
+
+        if total == 0 {
+            return (id % self.reasons.len() as u64) as usize;
+        }
+
+        let target = id % total;
+        self.cumulative.partition_point(|&c| c <= target)
+    }
+
+    /// Returns chance of the reason at the index to be chosen, from 0 to 1.
+    /// (see [`ReasonSet::pick_index`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::reasons::{Reason, ReasonSet};
+    ///
+    /// let mut reasons = vec![Reason::new("cars"), Reason::new("fire")];
+    /// reasons[1].weight = 3;
+    /// let set = ReasonSet::new(reasons).unwrap();
+    ///
+    /// assert_eq!(set.chance(0), 0.25);
+    /// assert_eq!(set.chance(1), 0.75);
+    /// ```
+    pub fn chance(&self, index: usize) -> f64 {
+        match self.total_weight() {
+            0 => 1.0 / self.reasons.len() as f64,
+            total => self.reasons[index].weight as f64 / total as f64,
+        }
+    }
+
+    /// Returns the reason chosen by `id`. (see [`ReasonSet::pick_index`])
+    pub fn pick(&self, id: u64) -> &Reason {
+        &self.reasons[self.pick_index(id)]
+    }
+
+    /// Returns all reasons.
+    pub fn reasons(&self) -> &[Reason] {
+        &self.reasons
+    }
+
+    /// Returns categories of the reasons in order of first appearance,
+    /// without repeats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::locale::Language;
+    /// use death::reasons::ReasonSet;
+    ///
+    /// assert_eq!(
+    ///     ReasonSet::defaults(Language::En).categories(),
+    ///     vec!["accident", "natural", "mystery", "violence"]
+    /// );
+    /// ```
+    pub fn categories(&self) -> Vec<&str> {
+        let mut res: Vec<&str> = vec![];
+        let categories = self.reasons.iter()
+            .filter_map(|r| r.category.as_deref());
+        for category in categories {
+            if !res.contains(&category) {
+                res.push(category);
+            }
+        }
+        res
+    }
+
+    /// Returns reasons of the category, compared case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if there are no reasons in the category.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::locale::Language;
+    /// use death::reasons::ReasonSet;
+    ///
+    /// let reasons = ReasonSet::defaults(Language::En)
+    ///     .filter("Violence")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(reasons.len(), 1);
+    /// assert_eq!(reasons.reasons()[0].text, "weapons");
+    /// ```
+    pub fn filter(&self, category: &str) -> Result<ReasonSet, Error> {
+        let res = self.reasons.iter()
+            .filter(|r| r.category.as_ref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(category)))
+            .cloned()
+            .collect();
+        ReasonSet::new(res)
+            .map(|set| set.with_metadata(self.metadata.clone()))
+    }
+}
+
+impl Default for ReasonSet {
+    /// Returns default death reasons in English.
+    fn default() -> ReasonSet {
+        ReasonSet::defaults(Language::En)
+    }
+}
+
+impl Deref for ReasonSet {
+    type Target = [Reason];
+
+    fn deref(&self) -> &[Reason] {
+        &self.reasons
+    }
+}
+
+impl TryFrom<Vec<Reason>> for ReasonSet {
+    type Error = Error;
+
+    fn try_from(reasons: Vec<Reason>) -> Result<ReasonSet, Error> {
+        ReasonSet::new(reasons)
+    }
+}
+
+impl From<ReasonSet> for Vec<Reason> {
+    fn from(set: ReasonSet) -> Vec<Reason> {
+        set.reasons
+    }
+}
+
+impl IntoIterator for ReasonSet {
+    type Item = Reason;
+    type IntoIter = std::vec::IntoIter<Reason>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reasons.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ReasonSet {
+    type Item = &'a Reason;
+    type IntoIter = std::slice::Iter<'a, Reason>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reasons.iter()
+    }
+}
+
+/// Information about death reasons pack.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Deserialize)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub language: Option<String>,
+    pub license: Option<String>,
+    pub version: Option<String>,
+}
+
+impl Metadata {
+    /// Returns `true` if no fields are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Metadata::default()
+    }
+
+    /// Returns fields that are set with their names.
+    pub fn fields(&self) -> Vec<(&str, &str)> {
+        [
+            ("name", &self.name), ("author", &self.author),
+            ("language", &self.language), ("license", &self.license),
+            ("version", &self.version),
+        ].into_iter()
+            .filter_map(|(k, v)| Some((k, v.as_deref()?)))
+            .collect()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let field = match key.to_ascii_lowercase().as_str() {
+            "name" => &mut self.name,
+            "author" => &mut self.author,
+            "language" => &mut self.language,
+            "license" => &mut self.license,
+            "version" => &mut self.version,
+            _ => return false,
+        };
+        *field = Some(value.to_string());
+        true
+    }
+}
+
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, value)) in self.fields().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Format of death reasons file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Format {
+    /// Each reason is on separate line
+    Text,
+    /// Array of strings or objects with `reason`, `weight`, `category`,
+    /// `tip` and `emoji`
+    Json,
+    /// `[[reason]]` tables with `text`, `weight`, `category`, `tip` and
+    /// `emoji`
+    Toml,
+    /// Same as [`Format::Json`], needs `yaml` feature
+    Yaml,
+    /// `reason,weight[,category[,tip[,emoji]]]` rows with optional header
+    Csv,
+}
+
+impl Format {
+    /// Returns format detected by file extension. Unknown extensions are
+    /// treated as [`Format::Text`]. `.gz` extension is skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use death::reasons::Format;
+    ///
+    /// assert_eq!(Format::from_path(Path::new("a.json")), Format::Json);
+    /// assert_eq!(Format::from_path(Path::new("a.toml")), Format::Toml);
+    /// assert_eq!(Format::from_path(Path::new("a.yml")), Format::Yaml);
+    /// assert_eq!(Format::from_path(Path::new("a.csv")), Format::Csv);
+    /// assert_eq!(Format::from_path(Path::new("a.txt")), Format::Text);
+    /// assert_eq!(Format::from_path(Path::new("a")), Format::Text);
+    /// assert_eq!(Format::from_path(Path::new("a.json.gz")), Format::Json);
+    /// ```
+    pub fn from_path(path: &Path) -> Format {
+        if is_gzip(path) {
+            if let Some(stem) = path.file_stem() {
+                return Format::from_path(Path::new(stem));
+            }
+        }
+
+        let extension = path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("json") => Format::Json,
+            Some("toml") => Format::Toml,
+            Some("yaml") | Some("yml") => Format::Yaml,
+            Some("csv") => Format::Csv,
+            _ => Format::Text,
+        }
+    }
+}
+
+/// Returns `true` if the file has `.gz` extension.
+pub fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case("gz"))
+}
+
+/// Returns reader of the file, decompressed if it has `.gz` extension.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot open the file.
+pub fn open(file_path: &Path) -> Result<Box<dyn BufRead>, Error> {
+    let file = File::open(file_path)?;
+    if is_gzip(file_path) {
+        Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+fn invalid_data(msg: String) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+/// Returns line without comment. Comment starts with `#` at the beginning of
+/// the line or after a whitespace.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::strip_comment;
+///
+/// assert_eq!(strip_comment("# comment"), "");
+/// assert_eq!(strip_comment("cars # too fast"), "cars ");
+/// assert_eq!(strip_comment("C#"), "C#");
+/// ```
+pub fn strip_comment(line: &str) -> &str {
+    let mut prev = None;
+    for (i, c) in line.char_indices() {
+        if c == '#' && prev.is_none_or(char::is_whitespace) {
+            return &line[..i];
+        }
+        prev = Some(c);
+    }
+    line
+}
+
+/// Returns death reasons from plain text. Each reason is on separate lines.
+/// Lines are trimmed of leading and trailing spaces, empty lines and comments
+/// are skipped. (see [`strip_comment`])
+///
+/// Use `\#` to start a reason with `#`.
+pub fn parse_text(contents: &str) -> Vec<Reason> {
+    contents.lines()
+        .map(|line| strip_comment(line).trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| Reason::new(&line.replace("\\#", "#")))
+        .collect()
+}
+
+/// Reads `# key: value` comments at the top of plain text or CSV pack.
+/// Returns the metadata and reader of the rest, starting with the first
+/// line that is not a comment. (see [`parse_metadata`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read.
+fn read_header<'a, R: BufRead + 'a>(mut reader: R)
+-> Result<(Metadata, Box<dyn BufRead + 'a>), Error> {
+    let mut metadata = Metadata::default();
+    let mut buf = vec![];
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            return Ok((metadata, Box::new(io::empty())));
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let comment = match line.strip_prefix('#') {
+            Some(v) => v,
+            None => break,
+        };
+        if let Some((key, value)) = comment.split_once(':') {
+            metadata.set(key.trim(), value.trim());
+        }
+    }
+
+    Ok((metadata, Box::new(io::Cursor::new(buf).chain(reader))))
+}
+
+/// Returns at most `limit` death reasons from plain text reader line by line,
+/// without reading it all into memory. (see [`parse_text`])
+///
+/// Invalid UTF-8 sequences are replaced with `�`.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read from the reader.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{read_text, Reason};
+///
+/// let reasons = read_text("cars\nfire\nwater\n".as_bytes(), Some(2))
+///     .unwrap();
+///
+/// assert_eq!(reasons, vec![Reason::new("cars"), Reason::new("fire")]);
+/// ```
+pub fn read_text<R: BufRead>(mut reader: R, limit: Option<usize>)
+-> Result<Vec<Reason>, Error> {
+    let mut res = vec![];
+    let mut buf = vec![];
+
+    while limit.is_none_or(|l| res.len() < l) {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = strip_comment(&line).trim();
+        if !line.is_empty() {
+            res.push(Reason::new(&line.replace("\\#", "#")));
+        }
+    }
+
+    Ok(res)
+}
+
+/// Returns death reasons from JSON array. Each item is either a string or an
+/// object with `reason` and optional `weight`, `category`, `tip` and `emoji`
+/// fields.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidData`] describing
+/// what is wrong and in which entry.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{parse_json, Reason};
+///
+/// let reasons = parse_json(r#"["cars", {"reason": "fire", "weight": 3}]"#)
+///     .unwrap();
+///
+/// assert_eq!(reasons[0], Reason::new("cars"));
+/// assert_eq!(reasons[1].text, "fire");
+/// assert_eq!(reasons[1].weight, 3);
+/// ```
+pub fn parse_json(contents: &str) -> Result<Vec<Reason>, Error> {
+    let value: Value = serde_json::from_str(contents)
+        .map_err(|e| invalid_data(format!("Invalid JSON: {}", e)))?;
+
+    parse_value(&value, "JSON")
+}
+
+/// Returns death reasons from YAML list. Items are the same as in
+/// [`parse_json`].
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidData`] describing
+/// what is wrong and in which entry.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{parse_yaml, Reason};
+///
+/// let reasons = parse_yaml("- cars\n- reason: fire\n  weight: 3\n")
+///     .unwrap();
+///
+/// assert_eq!(reasons[0], Reason::new("cars"));
+/// assert_eq!(reasons[1].weight, 3);
+/// ```
+#[cfg(feature = "yaml")]
+pub fn parse_yaml(contents: &str) -> Result<Vec<Reason>, Error> {
+    let value: Value = serde_yaml::from_str(contents)
+        .map_err(|e| invalid_data(format!("Invalid YAML: {}", e)))?;
+
+    parse_value(&value, "YAML")
+}
+
+/// Returns `true` if the first cell of a CSV row names the reason column, so
+/// the row is a header and not a reason.
+pub(crate) fn is_csv_header(cell: &str) -> bool {
+    ["reason", "text"].iter().any(|name| cell.eq_ignore_ascii_case(name))
+}
+
+/// Returns array of reasons: the value itself or its `reasons` field if the
+/// value is a pack with metadata.
+pub(crate) fn value_items(value: &Value) -> Option<&Vec<Value>> {
+    match value {
+        Value::Array(v) => Some(v),
+        Value::Object(map) => map.get("reasons")?.as_array(),
+        _ => None,
+    }
+}
+
+/// Returns death reasons from array of strings or objects.
+fn parse_value(value: &Value, format_name: &str)
+-> Result<Vec<Reason>, Error> {
+    let items = match value_items(value) {
+        Some(v) => v,
+        None => return Err(invalid_data(
+            format!("{} must be an array of reasons", format_name)
+        )),
+    };
+
+    let mut res = vec![];
+
+    for (i, item) in items.iter().enumerate() {
+        let mut reason = match item {
+            Value::String(s) => Reason::new(s),
+            Value::Object(_) => Reason::deserialize(item).map_err(|e| {
+                invalid_data(format!("Entry {}: {}", i + 1, e))
+            })?,
+            _ => return Err(invalid_data(format!(
+                "Entry {}: expected string or object", i + 1
+            ))),
+        };
+        trim(&mut reason);
+        if !reason.text.is_empty() {
+            res.push(reason);
+        }
+    }
+
+    Ok(res)
+}
+
+/// Trims the reason's text, tip and emoji, drops empty tip and emoji.
+fn trim(reason: &mut Reason) {
+    let trim_optional = |v: Option<&str>| v
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from);
+    reason.text = reason.text.trim().to_string();
+    reason.tip = trim_optional(reason.tip.as_deref());
+    reason.emoji = trim_optional(reason.emoji.as_deref());
+}
+
+#[derive(Deserialize)]
+struct TomlPack {
+    #[serde(default)]
+    reason: Vec<Reason>,
+    #[serde(flatten)]
+    metadata: Metadata,
+}
+
+/// Returns death reasons from TOML. Each reason is a `[[reason]]` table with
+/// `text` and optional `weight`, `category`, `tip` and `emoji` fields.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidData`] describing
+/// what is wrong and where.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::parse_toml;
+///
+/// let reasons = parse_toml(r#"
+/// [[reason]]
+/// text = "cars"
+/// weight = 5
+/// category = "accident"
+/// "#).unwrap();
+///
+/// assert_eq!(reasons[0].text, "cars");
+/// assert_eq!(reasons[0].weight, 5);
+/// assert_eq!(reasons[0].category.as_deref(), Some("accident"));
+/// ```
+pub fn parse_toml(contents: &str) -> Result<Vec<Reason>, Error> {
+    let pack: TomlPack = toml::from_str(contents)
+        .map_err(|e| invalid_data(format!("Invalid TOML: {}", e)))?;
+
+    let mut res = vec![];
+
+    for mut reason in pack.reason {
+        trim(&mut reason);
+        if !reason.text.is_empty() {
+            res.push(reason);
+        }
+    }
+
+    Ok(res)
+}
+
+/// Returns death reasons from CSV. Each row is
+/// `reason,weight[,category[,tip[,emoji]]]`, weight can be omitted too. First
+/// row is skipped if its weight is not a number, so it can be a header.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of kind [`ErrorKind::InvalidData`] describing
+/// what is wrong and on which line.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::parse_csv;
+///
+/// let reasons = parse_csv("reason,weight\ncars,5\nfire,1,accident\n")
+///     .unwrap();
+///
+/// assert_eq!(reasons[0].text, "cars");
+/// assert_eq!(reasons[0].weight, 5);
+/// assert_eq!(reasons[1].category.as_deref(), Some("accident"));
+/// ```
+pub fn parse_csv(contents: &str) -> Result<Vec<Reason>, Error> {
+    read_csv(contents.as_bytes(), None)
+}
+
+/// Returns at most `limit` death reasons from CSV reader without reading it
+/// all into memory. (see [`parse_csv`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the CSV.
+pub fn read_csv<R: Read>(reader: R, limit: Option<usize>)
+-> Result<Vec<Reason>, Error> {
+    let (_, reader) = read_header(BufReader::new(reader))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let mut res = vec![];
+
+    for (i, record) in reader.records().enumerate() {
+        if limit.is_some_and(|l| res.len() >= l) {
+            break;
+        }
+
+        let record = record
+            .map_err(|e| invalid_data(format!("Invalid CSV: {}", e)))?;
+        let line = record.position().map_or(i as u64 + 1, |p| p.line());
+
+        let text = record.get(0).unwrap_or("");
+        if text.is_empty() || (i == 0 && is_csv_header(text)) {
+            continue;
+        }
+
+        let weight = match record.get(1) {
+            None | Some("") => 1,
+            Some(w) => match w.parse() {
+                Ok(w) => w,
+                Err(_) if i == 0 => continue,
+                Err(_) => return Err(invalid_data(
+                    format!("Line {}: invalid weight `{}`", line, w)
+                )),
+            },
+        };
+
+        let category = record.get(2)
+            .filter(|c| !c.is_empty())
+            .map(String::from);
+        let tip = record.get(3)
+            .filter(|t| !t.is_empty())
+            .map(String::from);
+        let emoji = record.get(4)
+            .filter(|e| !e.is_empty())
+            .map(String::from);
+
+        res.push(Reason {
+            text: text.to_string(), weight, category, tip, emoji,
+        });
+    }
+
+    Ok(res)
+}
+
+/// Returns death reasons from string in given format.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if contents are invalid or there are no reasons.
+pub fn parse(contents: &str, format: Format) -> Result<Vec<Reason>, Error> {
+    let res = match format {
+        Format::Text => parse_text(contents),
+        Format::Json => parse_json(contents)?,
+        Format::Toml => parse_toml(contents)?,
+        #[cfg(feature = "yaml")]
+        Format::Yaml => parse_yaml(contents)?,
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => return Err(Error::new(
+            ErrorKind::Unsupported,
+            "YAML support is not enabled, build with `yaml` feature"
+        )),
+        Format::Csv => parse_csv(contents)?,
+    };
+
+    if res.is_empty() {
+        Err(Error::other("File is empty"))
+    } else {
+        Ok(res)
+    }
+}
+
+/// Returns metadata of death reasons pack in given format.
+///
+/// Plain text and CSV packs have it in comments at the top of the file, one
+/// `# key: value` per line. JSON and YAML packs are objects with metadata
+/// fields and `reasons` array. TOML packs have metadata fields before
+/// `[[reason]]` tables. Known fields are `name`, `author`, `language`,
+/// `license` and `version`.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if contents are invalid.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{parse_metadata, Format};
+///
+/// let metadata = parse_metadata(
+///     "# Name: Classic\n# Author: Someone\ncars\n", Format::Text
+/// ).unwrap();
+///
+/// assert_eq!(metadata.name.as_deref(), Some("Classic"));
+/// assert_eq!(metadata.author.as_deref(), Some("Someone"));
+/// assert_eq!(metadata.license, None);
+/// ```
+pub fn parse_metadata(contents: &str, format: Format)
+-> Result<Metadata, Error> {
+    let mut metadata = Metadata::default();
+
+    match format {
+        Format::Text | Format::Csv => {
+            metadata = read_header(contents.as_bytes())?.0;
+        },
+        Format::Json => {
+            let value: Value = serde_json::from_str(contents)
+                .map_err(|e| invalid_data(format!("Invalid JSON: {}", e)))?;
+            if value.is_object() {
+                metadata = Metadata::deserialize(&value).map_err(|e| {
+                    invalid_data(format!("Invalid metadata: {}", e))
+                })?;
+            }
+        },
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let value: Value = serde_yaml::from_str(contents)
+                .map_err(|e| invalid_data(format!("Invalid YAML: {}", e)))?;
+            if value.is_object() {
+                metadata = Metadata::deserialize(&value).map_err(|e| {
+                    invalid_data(format!("Invalid metadata: {}", e))
+                })?;
+            }
+        },
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => (),
+        Format::Toml => {
+            let pack: TomlPack = toml::from_str(contents)
+                .map_err(|e| invalid_data(format!("Invalid TOML: {}", e)))?;
+            metadata = pack.metadata;
+        },
+    }
+
+    Ok(metadata)
+}
+
+/// Returns metadata of death reasons file. (see [`parse_metadata`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file.
+pub fn read_metadata(file_path: &Path) -> Result<Metadata, Error> {
+    let (contents, format) = read_contents(file_path)?;
+    parse_metadata(&contents, format)
+}
+
+/// Returns contents of death reasons file and its format detected by
+/// extension. (see [`Format::from_path`])
+///
+/// If path is `-`, contents are read from stdin as plain text. If path is
+/// `http://` or `https://` URL, the file is downloaded (needs `http` feature).
+/// Files with `.gz` extension are decompressed.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read the file.
+pub fn read_contents(file_path: &Path) -> Result<(String, Format), Error> {
+    if file_path == Path::new("-") {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        return Ok((contents, Format::Text));
+    }
+
+    let path_str = file_path.to_string_lossy();
+    if is_url(&path_str) {
+        return read_url(&path_str);
+    }
+
+    let mut contents = String::new();
+    open(file_path)?.read_to_string(&mut contents)?;
+    Ok((contents, Format::from_path(file_path)))
+}
+
+/// Returns death reasons from file. (see [`read_contents`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file.
+pub fn read_reasons(file_path: &Path) -> Result<ReasonSet, Error> {
+    read_reasons_limited(file_path, None)
+}
+
+/// Returns at most `limit` death reasons from file. (see [`read_contents`])
+///
+/// Plain text and CSV files are read line by line, so even huge files don't
+/// take much memory.
+///
+/// If path is a directory, all death reasons files in it are merged.
+/// (see [`dir_files`]) If path is a name of installed pack, the pack is read.
+/// (see [`crate::packs::resolve`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file.
+pub fn read_reasons_limited(file_path: &Path, limit: Option<usize>)
+-> Result<ReasonSet, Error> {
+    let file_path = &crate::packs::resolve(file_path);
+    let format = Format::from_path(file_path);
+    let is_stdin = file_path == Path::new("-");
+    let is_file = !is_stdin && !is_url(&file_path.to_string_lossy());
+
+    if is_file && file_path.is_dir() {
+        return read_many_limited(&dir_files(file_path)?, limit);
+    }
+    let (res, metadata) = if is_stdin {
+        let (metadata, rest) = read_header(io::stdin().lock())?;
+        (read_text(rest, limit)?, metadata)
+    } else if is_file && format == Format::Text {
+        let (metadata, rest) = read_header(open(file_path)?)?;
+        (read_text(rest, limit)?, metadata)
+    } else if is_file && format == Format::Csv {
+        let (metadata, rest) = read_header(open(file_path)?)?;
+        (read_csv(rest, limit)?, metadata)
+    } else {
+        let (contents, format) = read_contents(file_path)?;
+        let mut res = parse(&contents, format)?;
+        res.truncate(limit.unwrap_or(res.len()));
+        (res, parse_metadata(&contents, format)?)
+    };
+
+    if res.is_empty() {
+        Err(Error::other("File is empty"))
+    } else {
+        Ok(ReasonSet::build(res).with_metadata(metadata))
+    }
+}
+
+/// Returns death reasons from the file only: unlike [`read_reasons`], `-`
+/// is not stdin, and URLs, installed packs and directories are not read.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file. The error
+/// message starts with the file path.
+pub fn read_file(file_path: &Path) -> Result<ReasonSet, Error> {
+    let read = || {
+        if !file_path.is_file() {
+            return Err(Error::new(ErrorKind::NotFound, "No such file"));
+        }
+        let mut contents = String::new();
+        open(file_path)?.read_to_string(&mut contents)?;
+        let format = Format::from_path(file_path);
+        let res = parse(&contents, format)?;
+        let metadata = parse_metadata(&contents, format)?;
+        match res.is_empty() {
+            true => Err(Error::other("File is empty")),
+            false => Ok(ReasonSet::build(res).with_metadata(metadata)),
+        }
+    };
+    read().map_err(|e| Error::new(
+        e.kind(), format!("{}: {}", file_path.display(), e)
+    ))
+}
+
+/// Extensions of death reasons files.
+pub const EXTENSIONS: [&str; 6] = ["txt", "json", "toml", "yaml", "yml", "csv"];
+
+/// Returns death reasons files in the directory sorted by name. Files are
+/// detected by extension (see [`EXTENSIONS`]), also compressed ones with
+/// `.gz` extension after it. Subdirectories are skipped.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read the directory or there are no
+/// death reasons files.
+pub fn dir_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut res = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = if is_gzip(&path) {
+            path.file_stem().map(PathBuf::from).unwrap_or_default()
+        } else {
+            path.clone()
+        };
+        let extension = name.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let known = extension.is_some_and(|e| EXTENSIONS.contains(&e.as_str()));
+        if known && path.is_file() {
+            res.push(path);
+        }
+    }
+
+    if res.is_empty() {
+        return Err(Error::other("Directory has no death reasons files"));
+    }
+
+    res.sort();
+    Ok(res)
+}
+
+pub(crate) fn is_url(s: &str) -> bool {
+    s.starts_with("https://") || s.starts_with("http://")
+}
+
+#[cfg(feature = "http")]
+fn read_url(url: &str) -> Result<(String, Format), Error> {
+    let contents = crate::remote::fetch_cached(url)?;
+    let format = Format::from_path(Path::new(crate::remote::url_path(url)));
+    Ok((contents, format))
+}
+
+#[cfg(not(feature = "http"))]
+fn read_url(_url: &str) -> Result<(String, Format), Error> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "HTTP support is not enabled, build with `http` feature"
+    ))
+}
+
+/// Returns death reasons from all files merged in the same order.
+/// (see [`read_reasons`])
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse some file. The error
+/// message starts with the file path.
+pub fn read_many(file_paths: &[PathBuf]) -> Result<ReasonSet, Error> {
+    read_many_limited(file_paths, None)
+}
+
+/// Returns at most `limit` death reasons from all files merged in the same
+/// order. (see [`read_reasons_limited`]) Metadata is kept only if there is
+/// one file.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse some file. The error
+/// message starts with the file path. If no files are passed, the error is
+/// of kind [`ErrorKind::InvalidInput`].
+pub fn read_many_limited(file_paths: &[PathBuf], limit: Option<usize>)
+-> Result<ReasonSet, Error> {
+    let mut res = vec![];
+    let mut metadata = Metadata::default();
+
+    for file_path in file_paths {
+        let left = limit.map(|l| l.saturating_sub(res.len()));
+        if left == Some(0) {
+            break;
+        }
+        match read_reasons_limited(file_path, left) {
+            Ok(v) => {
+                if file_paths.len() == 1 {
+                    metadata = v.metadata().clone();
+                }
+                res.extend(v);
+            },
+            Err(e) => return Err(Error::new(
+                e.kind(), format!("{}: {}", file_path.display(), e)
+            )),
+        }
+    }
+
+    ReasonSet::new(res).map(|set| set.with_metadata(metadata))
+}
+
+/// What to do with repeated death reasons.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Dedup {
+    /// Keep repeated reasons as they are
+    #[default]
+    Off,
+    /// Keep only the first one of repeated reasons
+    Remove,
+    /// Keep only the first one, its weight is sum of all weights
+    Weight,
+}
+
+/// Collapses repeated death reasons, texts are compared case-insensitively.
+/// (see [`Dedup`])
+///
+/// Returns reasons left and a list of collapsed reasons with number of times
+/// they were found.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{dedup, Dedup, Reason};
+///
+/// let reasons = vec![
+///     Reason::new("cars"), Reason::new("fire"), Reason::new("Cars"),
+/// ];
+///
+/// let (res, collapsed) = dedup(reasons, Dedup::Weight);
+///
+/// assert_eq!(res.len(), 2);
+/// assert_eq!(res[0].weight, 2);
+/// assert_eq!(collapsed, vec![(String::from("cars"), 2)]);
+/// ```
+pub fn dedup(reasons: Vec<Reason>, mode: Dedup)
+-> (Vec<Reason>, Vec<(String, usize)>) {
+    if mode == Dedup::Off {
+        return (reasons, vec![]);
+    }
+
+    let mut res: Vec<Reason> = vec![];
+    let mut counts: Vec<usize> = vec![];
+    // Lowercase text of every reason left and its index
+    let mut indices: HashMap<String, usize> = HashMap::new();
+
+    for reason in reasons {
+        match indices.entry(reason.text.to_lowercase()) {
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                counts[i] += 1;
+                if mode == Dedup::Weight {
+                    res[i].weight = res[i].weight.saturating_add(reason.weight);
+                }
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(res.len());
+                res.push(reason);
+                counts.push(1);
+            },
+        }
+    }
+
+    let collapsed = res.iter().zip(counts)
+        .filter(|(_, count)| *count > 1)
+        .map(|(r, count)| (r.text.clone(), count))
+        .collect();
+
+    (res, collapsed)
+}
+
+/// Name of environment variable with death reasons file or list.
+pub const ENV_VAR: &str = "DEATH_REASONS";
+
+/// Returns death reasons from comma-separated list. Reasons are trimmed of
+/// leading and trailing spaces, empty ones are skipped.
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::{parse_inline, Reason};
+///
+/// assert_eq!(
+///     parse_inline("cars, fire,,"),
+///     vec![Reason::new("cars"), Reason::new("fire")]
+/// );
+/// ```
+pub fn parse_inline(list: &str) -> Vec<Reason> {
+    list.split(',')
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty())
+        .map(Reason::new)
+        .collect()
+}
+
+/// Returns `true` if the value has a path separator or an extension of
+/// death reasons file (see [`Format::from_path`]).
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::looks_like_path;
+///
+/// assert!(looks_like_path("packs/gothic"));
+/// assert!(looks_like_path("reasons.TXT"));
+/// assert!(looks_like_path("reasons.json.gz"));
+/// assert!(!looks_like_path("cars, fire"));
+/// assert!(!looks_like_path("st. elmo's fire"));
+/// ```
+pub fn looks_like_path(value: &str) -> bool {
+    if value.contains(['/', '\\']) {
+        return true;
+    }
+    Path::new(value).extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| {
+            ["txt", "json", "toml", "yaml", "yml", "csv", "gz"].iter()
+                .any(|known| e.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Returns death reasons from file or comma-separated list, e.g. from
+/// `DEATH_REASONS` environment variable.
+///
+/// If the value is an existing file, `-` or URL, reasons are read from it
+/// (see [`read_reasons`]). Otherwise it is a comma-separated list of reasons
+/// (see [`parse_inline`]).
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot read or parse the file, the list is
+/// empty, or the value looks like a path (see [`looks_like_path`]) to a file
+/// that doesn't exist.
+pub fn read_file_or_list(value: &str) -> Result<Vec<Reason>, Error> {
+    let path = Path::new(value);
+    if value == "-" || is_url(value) || path.exists() {
+        return read_reasons(path).map(Vec::from);
+    }
+    if looks_like_path(value) {
+        return Err(Error::new(ErrorKind::NotFound, "No such file"));
+    }
+
+    let res = parse_inline(value);
+
+    if res.is_empty() {
+        Err(Error::other("List is empty"))
+    } else {
+        Ok(res)
+    }
+}
+
+/// Returns death reasons from `DEATH_REASONS` environment variable.
+/// (see [`read_file_or_list`])
+///
+/// Returns [`None`] if the variable is not set or empty.
+pub fn read_env() -> Option<Result<ReasonSet, Error>> {
+    let value = env::var(ENV_VAR).ok().filter(|v| !v.trim().is_empty())?;
+
+    Some(read_file_or_list(&value).and_then(ReasonSet::new).map_err(|e| {
+        Error::new(e.kind(), format!("{}: {}", ENV_VAR, e))
+    }))
+}
+
+/// Returns death reasons except the excluded ones. Reason is excluded if its
+/// text or category matches some excluded reason's text, compared
+/// case-insensitively.
+///
+/// # Example
+///
+/// ```
+/// use death::locale::Language;
+/// use death::reasons::{defaults, exclude, Reason};
+///
+/// let reasons = defaults(Language::En);
+/// let excluded = [Reason::new("Illness"), Reason::new("natural")];
+/// let res = exclude(&reasons, &excluded);
+///
+/// assert!(res.iter().all(|r| r.text != "illness" && r.text != "food"));
+/// assert!(res.iter().any(|r| r.text == "cars"));
+/// ```
+pub fn exclude(reasons: &[Reason], excluded: &[Reason]) -> Vec<Reason> {
+    let matches = |s: &str| excluded.iter()
+        .any(|e| e.text.to_lowercase() == s.to_lowercase());
+
+    reasons.iter()
+        .filter(|r| !matches(&r.text))
+        .filter(|r| !r.category.as_deref().is_some_and(matches))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_comments() {
+        let reasons = read_reasons(Path::new("tests/comments.txt")).unwrap();
+
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason::new("fire"),
+            Reason::new("C#"),
+            Reason::new("#hashtags"),
+        ]);
+    }
+
+    #[test]
+    fn read_only_files() {
+        let reasons = read_file(Path::new("tests/read_file.toml")).unwrap();
+        assert_eq!(reasons, read_reasons(Path::new("tests/read_file.toml"))
+            .unwrap());
+
+        let error = read_file(Path::new("-")).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert_eq!(error.to_string(), "-: No such file");
+        assert!(read_file(Path::new("tests")).is_err());
+        assert!(read_file(Path::new("gothic")).is_err());
+    }
+
+    #[test]
+    fn merge_files() {
+        let reasons = read_many(&[
+            PathBuf::from("tests/read_file.toml"),
+            PathBuf::from("tests/comments.txt"),
+        ]).unwrap();
+
+        assert_eq!(reasons.len(), 6);
+        assert_eq!(reasons[0].text, "cars");
+        assert_eq!(reasons[0].weight, 5);
+        assert_eq!(reasons[5].text, "#hashtags");
+
+        let err = read_many(&[
+            PathBuf::from("tests/comments.txt"),
+            PathBuf::from("tests/empty.txt"),
+        ]).unwrap_err();
+        assert_eq!(err.to_string(), "tests/empty.txt: File is empty");
+    }
+
+    #[test]
+    fn env_value() {
+        assert_eq!(read_file_or_list("tests/read_file.toml").unwrap().len(), 2);
+        assert_eq!(
+            read_file_or_list("cars, fire").unwrap(),
+            vec![Reason::new("cars"), Reason::new("fire")]
+        );
+        assert_eq!(
+            read_file_or_list("lego").unwrap(), vec![Reason::new("lego")]
+        );
+        assert_eq!(read_file_or_list(" , ").unwrap_err().to_string(),
+            "List is empty");
+
+        let err = read_file_or_list("tests/missing.txt").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = read_file_or_list("missing.json").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn limit() {
+        let reasons = read_reasons_limited(
+            Path::new("tests/read_file.txt"), Some(3)
+        ).unwrap();
+        assert_eq!(reasons.len(), 3);
+        assert_eq!(reasons[2].text, "sample3;");
+
+        let reasons = read_reasons_limited(
+            Path::new("tests/read_file.csv"), Some(1)
+        ).unwrap();
+        assert_eq!(reasons.reasons(), vec![Reason::new("cars")]);
+
+        let reasons = read_many_limited(&[
+            PathBuf::from("tests/read_file.toml"),
+            PathBuf::from("tests/comments.txt"),
+        ], Some(3)).unwrap();
+        assert_eq!(reasons.len(), 3);
+        assert_eq!(reasons[2].text, "cars");
+    }
+
+    #[test]
+    fn metadata() {
+        let metadata = read_metadata(Path::new("tests/comments.txt")).unwrap();
+        assert_eq!(metadata.name, None);
+        assert_eq!(metadata.author.as_deref(), Some("nobody"));
+
+        let metadata = read_metadata(Path::new("tests/pack.json")).unwrap();
+        assert_eq!(metadata.fields(), vec![
+            ("name", "Sample"), ("author", "nobody"), ("license", "CC0"),
+        ]);
+        let reasons = read_reasons(Path::new("tests/pack.json")).unwrap();
+        assert_eq!(reasons.len(), 2);
+
+        let metadata = parse_metadata(
+            "name = \"Pack\"\nversion = \"1.0\"\n[[reason]]\ntext = \"x\"",
+            Format::Toml
+        ).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Pack"));
+        assert_eq!(metadata.version.as_deref(), Some("1.0"));
+
+        assert!(read_metadata(Path::new("tests/read_file.csv")).unwrap()
+            .is_empty());
+
+        let reasons = read_reasons(Path::new("tests/pack.csv")).unwrap();
+        assert_eq!(reasons.len(), 2);
+        assert_eq!(reasons[0].weight, 2);
+        assert_eq!(reasons.metadata().fields(), vec![
+            ("name", "Sample"), ("license", "CC0"),
+        ]);
+        for path in ["tests/comments.txt", "tests/comments.txt.gz"] {
+            let reasons = read_reasons(Path::new(path)).unwrap();
+            assert_eq!(reasons.metadata().author.as_deref(), Some("nobody"));
+        }
+        let reasons = read_many(&[PathBuf::from("tests/pack.json")]).unwrap();
+        assert_eq!(reasons.metadata().name.as_deref(), Some("Sample"));
+        let reasons = read_many(&[
+            PathBuf::from("tests/pack.json"), PathBuf::from("tests/pack.csv"),
+        ]).unwrap();
+        assert!(reasons.metadata().is_empty());
+    }
+
+    #[test]
+    fn read_dir() {
+        let files = dir_files(Path::new("tests/reasons.d")).unwrap();
+        assert_eq!(files, vec![
+            PathBuf::from("tests/reasons.d/10-base.txt"),
+            PathBuf::from("tests/reasons.d/20-extra.toml"),
+            PathBuf::from("tests/reasons.d/30-packed.txt.gz"),
+        ]);
+
+        let reasons = read_reasons(Path::new("tests/reasons.d")).unwrap();
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason::new("fire"),
+            Reason {
+                text: "meteorite".to_string(),
+                weight: 1,
+                category: Some("space".to_string()),
+                tip: None,
+                emoji: None,
+            },
+            Reason::new("lava"),
+        ]);
+    }
+
+    #[test]
+    fn read_gzip() {
+        for file in ["tests/read_file.json", "tests/comments.txt"] {
+            let plain = read_reasons(Path::new(file)).unwrap();
+            let packed = PathBuf::from(format!("{}.gz", file));
+            assert_eq!(read_reasons(&packed).unwrap(), plain);
+        }
+
+        let reasons = read_reasons_limited(
+            Path::new("tests/comments.txt.gz"), Some(1)
+        ).unwrap();
+        assert_eq!(reasons.reasons(), vec![Reason::new("cars")]);
+    }
+
+    #[test]
+    fn weighted_pick() {
+        let mut reasons = vec![
+            Reason::new("a"), Reason::new("b"), Reason::new("c"),
+        ];
+        reasons[0].weight = 2;
+        reasons[1].weight = 0;
+        reasons[2].weight = 5;
+        let set = ReasonSet::new(reasons).unwrap();
+
+        let mut counts = [0; 3];
+        for id in 0..700 {
+            counts[set.pick_index(id)] += 1;
+        }
+        assert_eq!(counts, [200, 0, 500]);
+
+        // Same as modulo for equal weights
+        let set = ReasonSet::default();
+        for id in 0..100 {
+            assert_eq!(set.pick_index(id), id as usize % 14);
+        }
+    }
+
+    #[test]
+    fn reason_set() {
+        let err = ReasonSet::new(vec![]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let set = read_reasons(Path::new("tests/read_file.json")).unwrap();
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.total_weight(), 9);
+        assert_eq!(set.categories(), vec!["animals", "nature"]);
+        assert_eq!(set.iter().filter(|r| r.tip.is_some()).count(), 1);
+
+        let json = serde_json::to_string(&set).unwrap();
+        let parsed: ReasonSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, set);
+        assert!(serde_json::from_str::<ReasonSet>("[]").is_err());
+
+        let animals = set.filter("Animals").unwrap();
+        assert_eq!(animals[0].text, "sharks");
+        assert!(set.filter("space").is_err());
+    }
+
+    #[test]
+    fn read_json() {
+        let reasons = read_reasons(Path::new("tests/read_file.json")).unwrap();
+
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason { weight: 5, ..Reason::new("fire") },
+            Reason {
+                text: "sharks".to_string(),
+                weight: 1,
+                category: Some("animals".to_string()),
+                tip: None,
+                emoji: None,
+            },
+            Reason {
+                text: "lightning".to_string(),
+                weight: 2,
+                category: Some("nature".to_string()),
+                tip: Some("stay indoors".to_string()),
+                emoji: Some("⚡".to_string()),
+            },
+        ]);
+    }
+
+    #[test]
+    fn read_toml() {
+        let reasons = read_reasons(Path::new("tests/read_file.toml")).unwrap();
+
+        assert_eq!(reasons.reasons(), vec![
+            Reason {
+                text: "cars".to_string(),
+                weight: 5,
+                category: Some("accident".to_string()),
+                tip: None,
+                emoji: None,
+            },
+            Reason::new("fire"),
+        ]);
+
+        let err = parse_toml("[[reason]]\nweight = 5").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("Invalid TOML"));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn read_yaml() {
+        let reasons = read_reasons(Path::new("tests/read_file.yaml")).unwrap();
+
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason {
+                text: "fire".to_string(),
+                weight: 5,
+                category: Some("accident".to_string()),
+                tip: None,
+                emoji: None,
+            },
+        ]);
+
+        let err = parse_yaml("reason: cars").unwrap_err();
+        assert_eq!(err.to_string(), "YAML must be an array of reasons");
+    }
+
+    #[test]
+    fn read_csv() {
+        let reasons = read_reasons(Path::new("tests/read_file.csv")).unwrap();
+
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason { weight: 5, ..Reason::new("fire") },
+            Reason {
+                text: "sharks, whales".to_string(),
+                weight: 2,
+                category: Some("animals".to_string()),
+                tip: Some("stay away from open water".to_string()),
+                emoji: Some("🦈".to_string()),
+            },
+        ]);
+
+        // No header
+        assert_eq!(parse_csv("cars,2").unwrap()[0].weight, 2);
+
+        // One column with a header
+        let reasons = read_reasons(Path::new("tests/one_column.csv")).unwrap();
+        assert_eq!(reasons.reasons(), vec![
+            Reason::new("cars"),
+            Reason::new("fire"),
+        ]);
+        assert_eq!(parse_csv("Text\ncars").unwrap(), vec![Reason::new("cars")]);
+
+        let err = parse_csv("reason,weight\ncars,five").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: invalid weight `five`");
+    }
+
+    #[test]
+    fn json_errors() {
+        let err = parse_json("[\"cars\",").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("Invalid JSON"));
+
+        let err = parse_json("{\"reason\": \"cars\"}").unwrap_err();
+        assert_eq!(err.to_string(), "JSON must be an array of reasons");
+
+        let err = parse_json("[\"cars\", 5]").unwrap_err();
+        assert_eq!(err.to_string(), "Entry 2: expected string or object");
+
+        let err = parse_json("[{\"weight\": 5}]").unwrap_err();
+        assert_eq!(err.to_string(), "Entry 1: missing field `reason`");
+
+        let err = parse("[\"\", \"  \"]", Format::Json).unwrap_err();
+        assert_eq!(err.to_string(), "File is empty");
+    }
+}
@@ -0,0 +1,300 @@
+use std::fs;
+use std::io::{Error, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use crate::paths;
+
+/// Max time to wait for the server.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Max size of downloaded file in bytes.
+pub const MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How many times a webhook is sent before giving up.
+pub const WEBHOOK_ATTEMPTS: u32 = 3;
+
+/// Delay before the second attempt to send a webhook, doubled for every
+/// next one.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How long downloaded files are used without downloading them again by
+/// default.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Settings of downloaded files cache.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct Cache {
+    /// How long cached file is used without downloading it again
+    pub ttl: Duration,
+    /// Never download files, use cached ones even if they are outdated
+    pub offline: bool,
+}
+
+impl Default for Cache {
+    fn default() -> Cache {
+        Cache { ttl: DEFAULT_TTL, offline: false }
+    }
+}
+
+static CACHE: RwLock<Cache> = RwLock::new(
+    Cache { ttl: DEFAULT_TTL, offline: false }
+);
+
+/// Sets cache settings used by [`fetch_cached`].
+pub fn set_cache(cache: Cache) {
+    *CACHE.write().unwrap() = cache;
+}
+
+/// Returns current cache settings.
+pub fn cache() -> Cache {
+    *CACHE.read().unwrap()
+}
+
+/// Returns `true` if the string is `http://` or `https://` URL.
+///
+/// # Example
+///
+/// ```
+/// use death::remote::is_url;
+///
+/// assert!(is_url("https://example.com/reasons.txt"));
+/// assert!(!is_url("reasons.txt"));
+/// ```
+pub fn is_url(s: &str) -> bool {
+    s.starts_with("https://") || s.starts_with("http://")
+}
+
+/// Returns URL path without query and fragment, so the file extension can be
+/// detected.
+///
+/// # Example
+///
+/// ```
+/// use death::remote::url_path;
+///
+/// assert_eq!(url_path("https://a.b/r.json?raw=1"), "https://a.b/r.json");
+/// ```
+pub fn url_path(url: &str) -> &str {
+    match url.find(['?', '#']) {
+        Some(i) => &url[..i],
+        None => url,
+    }
+}
+
+/// Returns error of the request. Client errors (4xx status) are
+/// [`ErrorKind::InvalidInput`], so they are not retried.
+fn request_error(error: ureq::Error) -> Error {
+    match error {
+        ureq::Error::Status(code, _) => Error::new(
+            if (400..500).contains(&code) {
+                ErrorKind::InvalidInput
+            } else {
+                ErrorKind::Other
+            },
+            format!("Server responded with status {}", code)
+        ),
+        ureq::Error::Transport(t) => Error::other(match t.message() {
+            Some(msg) => format!("{}: {}", t.kind(), msg),
+            None => t.kind().to_string(),
+        }),
+    }
+}
+
+/// Downloads text file from the URL.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if request failed, took longer than
+/// [`TIMEOUT`] or the file is larger than [`MAX_SIZE`].
+pub fn fetch(url: &str) -> Result<String, Error> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let response = agent.get(url).call().map_err(request_error)?;
+
+    let mut bytes = vec![];
+    response.into_reader().take(MAX_SIZE + 1).read_to_end(&mut bytes)?;
+
+    if bytes.len() as u64 > MAX_SIZE {
+        return Err(Error::new(
+            ErrorKind::FileTooLarge,
+            format!("File is larger than {} bytes", MAX_SIZE)
+        ));
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "File is not UTF-8"))
+}
+
+/// Sends JSON to the URL in POST request, e.g. to chat's incoming webhook.
+/// Failed requests are sent again up to [`WEBHOOK_ATTEMPTS`] times in total,
+/// unless the server rejected the request with 4xx status.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] of the last attempt if every one failed or
+/// took longer than [`TIMEOUT`].
+pub fn post_json(url: &str, json: &str) -> Result<(), Error> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    retry(WEBHOOK_ATTEMPTS, RETRY_DELAY, || {
+        agent.post(url)
+            .set("Content-Type", "application/json")
+            .send_string(json)
+            .map(|_| ())
+            .map_err(request_error)
+    })
+}
+
+fn retry<F>(attempts: u32, delay: Duration, mut send: F) -> Result<(), Error>
+where F: FnMut() -> Result<(), Error> {
+    let mut delay = delay;
+    let mut attempt = 1;
+    loop {
+        let res = send();
+        let is_retried = res.as_ref()
+            .is_err_and(|e| e.kind() != ErrorKind::InvalidInput);
+        if !is_retried || attempt >= attempts {
+            return res;
+        }
+        std::thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+/// Returns path to the cached file of the URL inside
+/// [`paths::cache_dir`]. File name is a hash of the URL.
+pub fn cache_path(url: &str) -> Option<PathBuf> {
+    // FNV-1a, so the name is the same in every build
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in url.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    Some(paths::cache_dir()?.join("remote").join(format!("{:016x}", hash)))
+}
+
+/// Returns `true` if the file was modified less than `ttl` ago.
+fn is_fresh(path: &Path, ttl: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age < ttl)
+}
+
+/// Downloads text file from the URL or returns it from the cache.
+/// (see [`cache`])
+///
+/// The file is downloaded only if it is not cached or cached file is older
+/// than TTL. If download fails, outdated cached file is used. In offline
+/// mode only cached files are used.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if the file is not cached and cannot be
+/// downloaded. (see [`fetch`])
+pub fn fetch_cached(url: &str) -> Result<String, Error> {
+    match cache_path(url) {
+        Some(path) => fetch_with(url, &path, cache(), fetch),
+        None => fetch(url),
+    }
+}
+
+fn fetch_with<F>(url: &str, path: &Path, cache: Cache, fetch: F)
+-> Result<String, Error>
+where F: FnOnce(&str) -> Result<String, Error> {
+    let cached = || fs::read_to_string(path);
+
+    if cache.offline {
+        return cached().map_err(|_| Error::new(
+            ErrorKind::NotFound,
+            "File is not cached, cannot download it offline"
+        ));
+    }
+
+    if is_fresh(path, cache.ttl) {
+        if let Ok(v) = cached() {
+            return Ok(v);
+        }
+    }
+
+    match fetch(url) {
+        Ok(v) => {
+            // Failing to cache is not a reason to fail the download
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir)
+                    .and_then(|_| fs::write(path, &v));
+            }
+            Ok(v)
+        },
+        Err(e) => cached().map_err(|_| e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn cache() {
+        let dir = env::temp_dir()
+            .join(format!("death-cache-{}", std::process::id()));
+        let path = dir.join("file");
+        let url = "https://example.com/reasons.txt";
+        let online = Cache::default();
+        let offline = Cache { offline: true, ..online };
+        let fail = |_: &str| Err(Error::other("No network"));
+
+        let err = fetch_with(url, &path, offline, fail).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = fetch_with(url, &path, online, fail).unwrap_err();
+        assert_eq!(err.to_string(), "No network");
+
+        let res = fetch_with(url, &path, online, |_| Ok("cars".to_string()));
+        assert_eq!(res.unwrap(), "cars");
+
+        // Fresh file is not downloaded again
+        let res = fetch_with(url, &path, online, |_| Ok("fire".to_string()));
+        assert_eq!(res.unwrap(), "cars");
+        assert_eq!(fetch_with(url, &path, offline, fail).unwrap(), "cars");
+
+        // Outdated file is downloaded again, but used if it fails
+        let outdated = Cache { ttl: Duration::ZERO, ..online };
+        assert_eq!(fetch_with(url, &path, outdated, fail).unwrap(), "cars");
+        let res = fetch_with(url, &path, outdated, |_| Ok("fire".to_string()));
+        assert_eq!(res.unwrap(), "fire");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn retries() {
+        let mut sent = 0;
+        let res = retry(3, Duration::ZERO, || {
+            sent += 1;
+            Err(Error::other("No network"))
+        });
+        assert!(res.is_err());
+        assert_eq!(sent, 3);
+
+        sent = 0;
+        let res = retry(3, Duration::ZERO, || {
+            sent += 1;
+            if sent < 2 { Err(Error::other("No network")) } else { Ok(()) }
+        });
+        assert!(res.is_ok());
+        assert_eq!(sent, 2);
+
+        sent = 0;
+        let res = retry(3, Duration::ZERO, || {
+            sent += 1;
+            Err(Error::from(ErrorKind::InvalidInput))
+        });
+        assert!(res.is_err());
+        assert_eq!(sent, 1);
+    }
+}
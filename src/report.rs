@@ -0,0 +1,799 @@
+use crate::date::Date;
+use crate::locale::{fill, tr, Message};
+use crate::prediction::Prediction;
+use crate::reasons::ReasonSet;
+#[cfg(feature = "cli")]
+use crate::palette;
+use crate::palette::Role;
+use crate::tombstone;
+
+/// Number of the most likely death reasons in the risk table.
+const RISKS: usize = 10;
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 40em; \
+margin: 2em auto; } table { border-collapse: collapse; } \
+th, td { border: 1px solid #999; padding: 0.2em 0.6em; } \
+.chance { text-align: right; }";
+
+/// Row of the risk table.
+struct Risk {
+    reason: String,
+    category: String,
+    chance: f64,
+    is_chosen: bool,
+}
+
+fn headline(prediction: &Prediction) -> String {
+    match prediction.name.as_str() {
+        "" => String::from("Death date"),
+        name => format!("Death date of {}", name),
+    }
+}
+
+/// Returns the most likely death reasons, and the predicted one if it is
+/// not among them.
+fn risks(prediction: &Prediction, reasons: &ReasonSet) -> Vec<Risk> {
+    let chosen = reasons.reasons().iter()
+        .position(|r| r.text == prediction.reason.text);
+    let mut indexes: Vec<usize> = (0..reasons.reasons().len()).collect();
+    indexes.sort_by(|&a, &b| reasons.chance(b).total_cmp(&reasons.chance(a)));
+    indexes.truncate(RISKS);
+    if let Some(i) = chosen.filter(|i| !indexes.contains(i)) {
+        indexes.push(i);
+    }
+
+    indexes.into_iter()
+        .map(|i| {
+            let reason = &reasons.reasons()[i];
+            Risk {
+                reason: reason.text.clone(),
+                category: reason.category.clone().unwrap_or_default(),
+                chance: reasons.chance(i),
+                is_chosen: Some(i) == chosen,
+            }
+        })
+        .collect()
+}
+
+/// Returns dates of the life with what happens on them.
+fn timeline(prediction: &Prediction) -> Vec<(String, String)> {
+    let mut res = vec![];
+    if let Some(birthday) = prediction.birthday {
+        res.push((birthday.to_string(), String::from("Born")));
+    }
+    res.push((Date::today().to_string(), String::from("Today")));
+    res.push((
+        prediction.likely_from.to_string(),
+        String::from("Most likely range begins"),
+    ));
+    let death = match prediction.age_at_death {
+        Some(age) => format!("Death at the age of {}", age),
+        None => String::from("Death"),
+    };
+    res.push((prediction.death_date.to_string(), death));
+    res.push((
+        prediction.likely_to.to_string(),
+        String::from("Most likely range ends"),
+    ));
+    res
+}
+
+fn obituary(prediction: &Prediction) -> String {
+    let name = match prediction.name.as_str() {
+        "" => "Somebody",
+        name => name,
+    };
+    let mut res = match prediction.birthday {
+        Some(birthday) => format!(
+            "{} was born on {} and passed away on {}", name, birthday,
+            prediction.death_date
+        ),
+        None => format!("{} passed away on {}", name, prediction.death_date),
+    };
+    if let Some(age) = prediction.age_at_death {
+        res.push_str(&format!(" at the age of {}", age));
+    }
+    res.push_str(&format!(", taken by {}.", prediction.reason));
+    if let Some(card) = &prediction.card {
+        res.push_str(&format!(" The cards foretold it: {}.", card.name));
+    }
+    if let Some(tip) = &prediction.reason.tip {
+        res.push_str(&format!(" If only they had listened: {}.", tip));
+    }
+    res
+}
+
+fn reason_text(prediction: &Prediction) -> String {
+    let reason = &prediction.reason;
+    match &reason.category {
+        Some(category) => format!("{} ({})", reason, category),
+        None => reason.to_string(),
+    }
+}
+
+/// Returns the death date with the day of week, and time if it is
+/// predicted.
+fn date_text(prediction: &Prediction) -> String {
+    let date = &prediction.death_date;
+    let mut res = format!("{}, {}", date.get_weekday_name(), date);
+    if let Some(time) = prediction.death_time {
+        res.push_str(&format!(", {}", time));
+    }
+    res
+}
+
+fn most_likely(prediction: &Prediction) -> String {
+    fill(tr(Message::MostLikely), &[
+        &prediction.death_date.year(), &prediction.likely_from,
+        &prediction.likely_to,
+    ])
+}
+
+/// Returns text with characters that have meaning in Markdown escaped.
+fn escape_markdown(text: &str) -> String {
+    let mut res = String::new();
+    for c in text.chars() {
+        if "\\`*_[]<>|#".contains(c) {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Returns text with characters that have meaning in HTML escaped.
+fn escape_html(text: &str) -> String {
+    let mut res = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+/// Returns the rows aligned in columns separated by two spaces.
+pub(crate) fn columns(rows: &[Vec<String>]) -> String {
+    let count = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..count)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|r| r.get(i))
+                .map(|c| c.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut res = String::new();
+    for row in rows {
+        let cells: Vec<String> = row.iter().zip(&widths)
+            .map(|(cell, &width)| format!("{:width$}", cell))
+            .collect();
+        res.push_str(&format!("  {}\n", cells.join("  ").trim_end()));
+    }
+    res
+}
+
+/// Returns everything known about the prediction as plain text: the date
+/// with the day of week and time, the reason with the tip, the epitaph, last
+/// words, zodiac sign, the countdown, the most likely death reasons of
+/// `reasons` and timeline of the life.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons.clone());
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let report = report::text(&prediction, &reasons);
+///
+/// assert!(report.starts_with("Death date of Alice\n"));
+/// assert!(report.contains("Epitaph: Taken by lego\n"));
+/// ```
+pub fn text(prediction: &Prediction, reasons: &ReasonSet) -> String {
+    full_text(prediction, reasons, |text, _| text.to_string())
+}
+
+/// Returns the same as [`text`] with headers and the countdown painted with
+/// palette of the output. (see [`palette::paint`])
+#[cfg(feature = "cli")]
+pub fn painted_text(prediction: &Prediction, reasons: &ReasonSet) -> String {
+    full_text(prediction, reasons, |text, role| {
+        palette::paint(text, role).to_string()
+    })
+}
+
+/// Returns everything known about the prediction, `paint` returns the text
+/// painted for the role.
+fn full_text(
+    prediction: &Prediction, reasons: &ReasonSet,
+    paint: impl Fn(&str, Role) -> String,
+) -> String {
+    let headline = headline(prediction);
+    let mut res = format!(
+        "{}\n{}\n\n", paint(&headline, Role::Headline),
+        paint(&"=".repeat(headline.chars().count()), Role::Headline)
+    );
+    res.push_str(&format!(
+        "{}\n{}\n", date_text(prediction), most_likely(prediction)
+    ));
+    res.push_str(&format!(
+        "{}\n", fill(tr(Message::BeAwareOf), &[&reason_text(prediction)])
+    ));
+    if let Some(tip) = &prediction.reason.tip {
+        res.push_str(&format!("{}\n", fill(tr(Message::SurvivalTip), &[tip])));
+    }
+    if let Some(card) = &prediction.card {
+        res.push_str(&format!(
+            "{}\n", fill(tr(Message::CardDrawn), &[&card.name, &card.meaning])
+        ));
+    }
+    res.push_str(&format!("Epitaph: {}\n", tombstone::epitaph(prediction)));
+    res.push_str(&format!(
+        "Last words: \"{}\"\n", tombstone::last_words(prediction.id)
+    ));
+    if let Some(birthday) = prediction.birthday {
+        res.push_str(&format!("Zodiac sign: {}\n", birthday.zodiac_sign()));
+    }
+
+    let days = prediction.days_left as u64;
+    let mut countdown = vec![
+        vec![String::from("Years left"), prediction.years_left.to_string()],
+        vec![String::from("Weeks left"), (days / 7).to_string()],
+        vec![String::from("Days left"), days.to_string()],
+        vec![String::from("Hours left"), (days * 24).to_string()],
+    ];
+    if let Some(age) = prediction.age_at_death {
+        countdown.push(vec![String::from("Age at death"), age.to_string()]);
+    }
+    for row in countdown.iter_mut() {
+        row[1] = paint(&row[1], Role::Countdown);
+    }
+    res.push_str(&format!(
+        "\n{}\n{}", paint("Countdown", Role::Headline), columns(&countdown)
+    ));
+
+    let risks: Vec<Vec<String>> = risks(prediction, reasons).into_iter()
+        .map(|risk| vec![
+            String::from(if risk.is_chosen { "*" } else { "" }),
+            risk.reason,
+            risk.category,
+            format!("{:.1}%", risk.chance * 100.0),
+        ])
+        .collect();
+    res.push_str(&format!(
+        "\n{}\n{}", paint("Risks", Role::Headline), columns(&risks)
+    ));
+
+    let timeline: Vec<Vec<String>> = timeline(prediction).into_iter()
+        .map(|(when, event)| vec![when, event])
+        .collect();
+    res.push_str(&format!(
+        "\n{}\n{}", paint("Timeline", Role::Headline), columns(&timeline)
+    ));
+    res
+}
+
+/// Returns the number with the unit, e.g. `1 day` or `2 days`.
+fn plural(n: u32, unit: &str) -> String {
+    match n {
+        1 => format!("1 {}", unit),
+        n => format!("{} {}s", n, unit),
+    }
+}
+
+/// Returns how much later the second date is than the first one, e.g.
+/// `2 years and 31 days`.
+fn difference(from: Date, to: Date) -> String {
+    let years = to.years_from(from);
+    let year = from.year() + years;
+    let day = from.day().min(Date::max_day_of(year, from.month()));
+    let anniversary = Date::build(year, from.month(), day).unwrap();
+    let days = to.days_from(anniversary);
+    match (years, days) {
+        (0, days) => plural(days, "day"),
+        (years, 0) => plural(years as u32, "year"),
+        (years, days) => format!(
+            "{} and {}", plural(years as u32, "year"), plural(days, "day")
+        ),
+    }
+}
+
+/// Returns who of the two outlives the other and by how much.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::date::Date;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("Alice");
+/// let alice = Prediction::new(&user, Algorithm::Linear, false);
+/// let mut bob = alice.clone();
+/// bob.name = String::from("Bob");
+/// bob.death_date = alice.death_date.add_days(366 + 365 + 3);
+///
+/// assert!(report::verdict(&alice, &bob).starts_with("Bob outlives Alice"));
+/// ```
+pub fn verdict(a: &Prediction, b: &Prediction) -> String {
+    let names = [who(a, "A"), who(b, "B")];
+    if a.death_date == b.death_date {
+        return format!("{} and {} die on the same day.", names[0], names[1]);
+    }
+    let (first, last) = match a.death_date < b.death_date {
+        true => ((names[0], a), (names[1], b)),
+        false => ((names[1], b), (names[0], a)),
+    };
+    format!(
+        "{} outlives {} by {}.", last.0, first.0,
+        difference(first.1.death_date, last.1.death_date)
+    )
+}
+
+/// Returns name of the person or the label if the name is unknown.
+fn who<'a>(prediction: &'a Prediction, label: &'a str) -> &'a str {
+    match prediction.name.as_str() {
+        "" => label,
+        name => name,
+    }
+}
+
+/// Returns the two predictions side by side and who of them outlives the
+/// other. (see [`verdict`])
+pub fn comparison(a: &Prediction, b: &Prediction) -> String {
+    let row = |title: &str, value: &dyn Fn(&Prediction) -> String| vec![
+        String::from(title), value(a), value(b),
+    ];
+    let age = |p: &Prediction| p.age_at_death.map(|a| a.to_string())
+        .unwrap_or_else(|| String::from("?"));
+    let mut rows = vec![
+        vec![String::new(), who(a, "A").to_string(), who(b, "B").to_string()],
+        row("Death date", &|p| p.death_date.to_string()),
+    ];
+    if a.death_time.is_some() || b.death_time.is_some() {
+        rows.push(row("Time", &|p| {
+            p.death_time.map(|t| t.to_string()).unwrap_or_default()
+        }));
+    }
+    rows.extend([
+        row("Reason", &|p| reason_text(p)),
+        row("Years left", &|p| p.years_left.to_string()),
+        row("Days left", &|p| p.days_left.to_string()),
+        row("Age at death", &age),
+    ]);
+    format!("{}\n{}\n", columns(&rows), verdict(a, b))
+}
+
+/// Returns the names separated by commas, the last one with `and`.
+fn join_names(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.to_string(),
+        [names @ .., last] => format!("{} and {}", names.join(", "), last),
+    }
+}
+
+/// Returns the predictions of a group ordered by death date, with the ones
+/// who die on the same day flagged, and the last one standing. People
+/// without names are called by their place in `predictions`, e.g. `#2`.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("Alice");
+/// let alice = Prediction::new(&user, Algorithm::Linear, false);
+/// let mut bob = alice.clone();
+/// bob.name = String::from("Bob");
+/// bob.death_date = alice.death_date.add_days(-1);
+/// let carol = Prediction { name: String::from("Carol"), ..bob.clone() };
+///
+/// let report = report::group(&[alice, bob, carol]);
+///
+/// assert!(report.contains("same day as Carol"));
+/// assert!(report.ends_with(
+///     "Alice is the last one standing, 1 day after Carol.\n"
+/// ));
+/// ```
+pub fn group(predictions: &[Prediction]) -> String {
+    let mut people: Vec<(String, &Prediction)> = predictions.iter()
+        .enumerate()
+        .map(|(i, p)| (who(p, &format!("#{}", i + 1)).to_string(), p))
+        .collect();
+    people.sort_by_key(|(_, p)| p.death_date);
+
+    let mut rows = vec![];
+    for (i, (name, prediction)) in people.iter().enumerate() {
+        let same_day: Vec<&str> = people.iter()
+            .enumerate()
+            .filter(|&(j, (_, p))| {
+                p.death_date == prediction.death_date && j != i
+            })
+            .map(|(_, (other, _))| other.as_str())
+            .collect();
+        let mut row = vec![
+            format!("{}.", i + 1),
+            name.clone(),
+            date_text(prediction),
+            reason_text(prediction),
+            format!("{} left", plural(prediction.years_left as u32, "year")),
+        ];
+        if !same_day.is_empty() {
+            row.push(format!("same day as {}", join_names(&same_day)));
+        }
+        rows.push(row);
+    }
+
+    let mut res = columns(&rows);
+    let last = match people.last() {
+        Some((_, p)) => p.death_date,
+        None => return res,
+    };
+    let (last_ones, others): (Vec<_>, Vec<_>) = people.iter()
+        .partition(|(_, p)| p.death_date == last);
+    let names: Vec<&str> = last_ones.iter().map(|(n, _)| n.as_str()).collect();
+    res.push_str(&match (names.as_slice(), others.last()) {
+        ([name], Some((other, p))) => format!(
+            "\n{} is the last one standing, {} after {}.\n", name,
+            difference(p.death_date, last), other
+        ),
+        ([name], None) => format!("\n{} is the last one standing.\n", name),
+        (names, _) => format!(
+            "\nNobody is the last one standing: {} die on the same day.\n",
+            join_names(names)
+        ),
+    });
+    res
+}
+
+/// Returns report of the prediction in Markdown: the date, the reason, the
+/// most likely death reasons of `reasons`, timeline of the life and the
+/// obituary.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons.clone());
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let report = report::markdown(&prediction, &reasons);
+///
+/// assert!(report.starts_with("# Death date of Alice\n"));
+/// assert!(report.contains("| **lego** |  | 100.0% |"));
+/// ```
+pub fn markdown(prediction: &Prediction, reasons: &ReasonSet) -> String {
+    let e = escape_markdown;
+    let mut res = format!("# {}\n\n", e(&headline(prediction)));
+    res.push_str(&format!(
+        "**{}**\n\n{}\n\n", e(&date_text(prediction)), most_likely(prediction)
+    ));
+    res.push_str(&format!(
+        "{}\n", e(&fill(tr(Message::BeAwareOf), &[&reason_text(prediction)]))
+    ));
+    if let Some(tip) = &prediction.reason.tip {
+        res.push_str(&format!(
+            "\n{}\n", e(&fill(tr(Message::SurvivalTip), &[tip]))
+        ));
+    }
+
+    res.push_str("\n## Risks\n\n| Reason | Category | Chance |\n");
+    res.push_str("| --- | --- | ---: |\n");
+    for risk in risks(prediction, reasons) {
+        let reason = match risk.is_chosen {
+            true => format!("**{}**", e(&risk.reason)),
+            false => e(&risk.reason),
+        };
+        res.push_str(&format!(
+            "| {} | {} | {:.1}% |\n", reason, e(&risk.category),
+            risk.chance * 100.0
+        ));
+    }
+
+    res.push_str("\n## Timeline\n\n| When | Event |\n| --- | --- |\n");
+    for (when, event) in timeline(prediction) {
+        res.push_str(&format!("| {} | {} |\n", e(&when), e(&event)));
+    }
+
+    res.push_str(&format!("\n## Obituary\n\n{}\n", e(&obituary(prediction))));
+    res
+}
+
+/// Returns text with characters that have meaning in Slack messages escaped.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Returns Slack message with the prediction as JSON payload of Block Kit
+/// blocks: the headline, the date and the reason, and the most likely
+/// years. The message is shown to everyone in the channel.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+/// use serde_json::Value;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("<Alice>");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let message: Value = serde_json::from_str(&report::slack(&prediction))
+///     .unwrap();
+///
+/// assert_eq!(message["response_type"], "in_channel");
+/// let blocks = &message["blocks"];
+/// assert_eq!(blocks[0]["text"]["text"], "Death date of <Alice>");
+/// assert!(blocks[1]["text"]["text"].as_str().unwrap().contains("lego"));
+/// ```
+pub fn slack(prediction: &Prediction) -> String {
+    let e = escape_slack;
+    let details = format!(
+        "*{}*\n{}", e(&date_text(prediction)),
+        e(&fill(tr(Message::BeAwareOf), &[&reason_text(prediction)]))
+    );
+    serde_json::json!({
+        "response_type": "in_channel",
+        "text": e(&prediction.oneline(true)),
+        "blocks": [
+            {
+                "type": "header",
+                "text": { "type": "plain_text", "text": headline(prediction) },
+            },
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": details },
+            },
+            {
+                "type": "context",
+                "elements": [
+                    { "type": "mrkdwn", "text": e(&most_likely(prediction)) },
+                ],
+            },
+        ],
+    }).to_string()
+}
+
+/// Returns report of the prediction in HTML, the same as in
+/// [`markdown`], without the page around it.
+fn html_article(prediction: &Prediction, reasons: &ReasonSet) -> String {
+    let e = escape_html;
+    let mut res = format!(
+        "<article>\n<h1>{}</h1>\n", e(&headline(prediction))
+    );
+    res.push_str(&format!(
+        "<p><strong>{}</strong></p>\n<p>{}</p>\n",
+        e(&date_text(prediction)), e(&most_likely(prediction))
+    ));
+    res.push_str(&format!(
+        "<p>{}</p>\n",
+        e(&fill(tr(Message::BeAwareOf), &[&reason_text(prediction)]))
+    ));
+    if let Some(tip) = &prediction.reason.tip {
+        res.push_str(&format!(
+            "<p>{}</p>\n", e(&fill(tr(Message::SurvivalTip), &[tip]))
+        ));
+    }
+
+    res.push_str("<h2>Risks</h2>\n<table>\n");
+    res.push_str("<tr><th>Reason</th><th>Category</th><th>Chance</th></tr>\n");
+    for risk in risks(prediction, reasons) {
+        let reason = match risk.is_chosen {
+            true => format!("<strong>{}</strong>", e(&risk.reason)),
+            false => e(&risk.reason),
+        };
+        res.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"chance\">{:.1}%</td></tr>\n",
+            reason, e(&risk.category), risk.chance * 100.0
+        ));
+    }
+    res.push_str("</table>\n");
+
+    res.push_str("<h2>Timeline</h2>\n<table>\n");
+    res.push_str("<tr><th>When</th><th>Event</th></tr>\n");
+    for (when, event) in timeline(prediction) {
+        res.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n", e(&when), e(&event)
+        ));
+    }
+    res.push_str("</table>\n");
+
+    res.push_str(&format!(
+        "<h2>Obituary</h2>\n<p>{}</p>\n</article>\n",
+        e(&obituary(prediction))
+    ));
+    res
+}
+
+/// Returns HTML page with reports of the predictions, the same as in
+/// [`markdown`].
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons.clone());
+/// user.set_name("Alice & Bob");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let page = report::html(&[prediction], &reasons);
+///
+/// assert!(page.starts_with("<!DOCTYPE html>"));
+/// assert!(page.contains("<h1>Death date of Alice &amp; Bob</h1>"));
+/// ```
+pub fn html(predictions: &[Prediction], reasons: &ReasonSet) -> String {
+    let title = match predictions {
+        [prediction] => headline(prediction),
+        _ => String::from("Death dates"),
+    };
+    let mut res = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape_html(&title), STYLE
+    );
+    for prediction in predictions {
+        res.push_str(&html_article(prediction, reasons));
+    }
+    res.push_str("</body>\n</html>\n");
+    res
+}
+
+const PAGE_STYLE: &str = ".tombstone { font-size: 1.2em; line-height: 1.1; \
+text-align: center; } #countdown { font-size: 1.5em; font-weight: bold; }";
+
+/// Updates `#countdown` once a second with time left until `data-death`.
+const COUNTDOWN_SCRIPT: &str = "const countdown = \
+document.getElementById(\"countdown\");
+const death = new Date(countdown.dataset.death);
+function tick() {
+  const left = Math.max(0, Math.floor((death - new Date()) / 1000));
+  const days = Math.floor(left / 86400);
+  const hours = Math.floor(left % 86400 / 3600);
+  const minutes = Math.floor(left % 3600 / 60);
+  const seconds = left % 60;
+  countdown.textContent = days + \" days, \" + hours + \" hours, \" +
+    minutes + \" minutes, \" + seconds + \" seconds left\";
+}
+tick();
+setInterval(tick, 1000);";
+
+/// Returns self-contained HTML page of the prediction to publish, e.g. on
+/// GitHub Pages: the tombstone, the report the same as in [`html`] and a
+/// countdown timer. Styles and the script of the timer are inside the page.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::report;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons.clone());
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let page = report::page(&prediction, &reasons);
+///
+/// assert!(page.contains("<pre class=\"tombstone\">"));
+/// assert!(page.contains("ALICE"));
+/// assert!(page.contains("id=\"countdown\""));
+/// assert!(page.contains("<script>"));
+/// ```
+pub fn page(prediction: &Prediction, reasons: &ReasonSet) -> String {
+    let (hour, minute) = prediction.death_time
+        .map(|t| (t.hour(), t.minute()))
+        .unwrap_or((0, 0));
+    let death = format!(
+        "{}T{:02}:{:02}:00", prediction.death_date.to_iso(), hour, minute
+    );
+    let mut res = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <meta name=\"viewport\" content=\"width=device-width\">\n\
+        <title>{}</title>\n<style>{} {}</style>\n</head>\n<body>\n",
+        escape_html(&headline(prediction)), STYLE, PAGE_STYLE
+    );
+    res.push_str(&format!(
+        "<pre class=\"tombstone\">{}</pre>\n",
+        escape_html(&tombstone::render(prediction, 40))
+    ));
+    res.push_str(&format!(
+        "<p id=\"countdown\" data-death=\"{}\"></p>\n", death
+    ));
+    res.push_str(&html_article(prediction, reasons));
+    res.push_str(&format!(
+        "<script>\n{}\n</script>\n</body>\n</html>\n", COUNTDOWN_SCRIPT
+    ));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Algorithm;
+    use crate::reasons::Reason;
+    use crate::user::User;
+
+    #[test]
+    fn risk_table() {
+        let mut reasons: Vec<Reason> = (0..12)
+            .map(|i| Reason::new(&format!("reason {}", i)))
+            .collect();
+        reasons[11].weight = 0;
+        reasons[0].weight = 5;
+        let reasons = ReasonSet::new(reasons).unwrap();
+        let user = User::new(11, 30, reasons.clone());
+        let mut prediction = Prediction::new(&user, Algorithm::Linear, false);
+        prediction.reason = Reason::new("reason 11");
+
+        let risks = risks(&prediction, &reasons);
+        assert_eq!(risks.len(), RISKS + 1);
+        assert_eq!(risks[0].reason, "reason 0");
+        assert!(risks[RISKS].is_chosen);
+        assert_eq!(risks[RISKS].chance, 0.0);
+
+        assert_eq!(escape_markdown("a|b*"), "a\\|b\\*");
+        let rows = [
+            vec![String::from("a"), String::from("b")],
+            vec![String::from("ccc"), String::new()],
+        ];
+        assert_eq!(columns(&rows), "  a    b\n  ccc\n");
+
+        let date = |y, m, d| Date::build(y, m, d).unwrap();
+        assert_eq!(difference(date(2020, 1, 1), date(2021, 1, 1)), "1 year");
+        assert_eq!(difference(date(2020, 1, 1), date(2020, 1, 2)), "1 day");
+        assert_eq!(
+            difference(date(2020, 3, 1), date(2022, 3, 4)),
+            "2 years and 3 days"
+        );
+        assert_eq!(escape_html("<i a=\"&\">"), "&lt;i a=&quot;&amp;&quot;&gt;");
+        assert_eq!(join_names(&["a", "b", "c"]), "a, b and c");
+        assert_eq!(join_names(&["a"]), "a");
+    }
+}
@@ -0,0 +1,1314 @@
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "tls")]
+use std::fs;
+use std::io::{Error, Read};
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use chrono::{Local, NaiveDate, NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tiny_http::{Header, Request as HttpRequest, Response, Server};
+#[cfg(feature = "tls")]
+use tiny_http::SslConfig;
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::{Role, WebSocket};
+use tungstenite::Message;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::algorithm::Algorithm;
+use crate::batch::Person;
+use crate::date::{self, Date, DateOrder};
+use crate::error::DeathError;
+use crate::prediction::Prediction;
+use crate::reasons::ReasonSet;
+use crate::report;
+#[cfg(feature = "watch")]
+use crate::watch::LiveReasons;
+
+/// Upper bounds of request duration histogram buckets in seconds.
+const LATENCY_BUCKETS: [f64; 8] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 1.0,
+];
+
+/// Number of client addresses to keep tokens of before forgetting the ones
+/// with full buckets, then the least recently seen ones.
+const MAX_BUCKETS: usize = 10_000;
+
+/// Max size of request body in bytes, larger ones are not read.
+const MAX_BODY: u64 = 64 * 1024;
+
+/// Max number of countdowns streamed at once, each one has its thread.
+const MAX_STREAMS: usize = 256;
+
+/// The only supported version of WebSocket protocol.
+const WEBSOCKET_VERSION: &str = "13";
+
+/// Environment variable with signing secret of Slack app, it takes
+/// precedence over `slack_secret` in config.
+pub const SLACK_SECRET_VAR: &str = "DEATH_SLACK_SECRET";
+
+/// Max difference in seconds between the time of Slack request and now,
+/// older requests may be replayed.
+const SLACK_MAX_SKEW: i64 = 5 * 60;
+
+/// Options of the server and every prediction it makes.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Options {
+    /// Death reasons to choose from
+    pub death_reasons: ReasonSet,
+    /// Max age of users
+    pub max_age: u16,
+    /// Order of day, month and year in birthdays
+    pub date_order: DateOrder,
+    /// Formula to calculate the date
+    pub algorithm: Algorithm,
+    /// Draw a tarot card to choose the death reason
+    pub tarot: bool,
+    /// Limit of requests from every client address, not limited if
+    /// [`None`]
+    pub rate_limit: Option<RateLimit>,
+    /// Number of responses with predictions to keep for repeated requests,
+    /// none are kept if zero
+    pub cache_size: usize,
+    /// Certificate to answer HTTPS requests with, HTTP is answered if
+    /// [`None`]. Needs `tls` feature
+    pub tls: Option<Tls>,
+    /// Signing secret of Slack app to verify slash commands with, they are
+    /// refused if [`None`]
+    pub slack_secret: Option<String>,
+}
+
+/// Certificate and private key to answer HTTPS requests with.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Tls {
+    /// PEM file with the certificate chain
+    pub certificate: PathBuf,
+    /// PEM file with PKCS#8 or RSA private key of the certificate
+    pub private_key: PathBuf,
+}
+
+/// Rate limit of requests from a client address, as token bucket: every
+/// request takes a token, tokens come back at the rate up to the burst.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct RateLimit {
+    /// Number of requests a minute
+    pub per_minute: u32,
+    /// Number of requests that can be made at once
+    pub burst: u32,
+}
+
+/// Tokens left to a client address.
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets of client addresses. (see [`RateLimit`])
+struct Limiter {
+    limit: RateLimit,
+    buckets: HashMap<IpAddr, Bucket>,
+}
+
+impl Limiter {
+    fn new(limit: RateLimit) -> Limiter {
+        Limiter { limit, buckets: HashMap::new() }
+    }
+
+    /// Returns tokens of the bucket at `now`, with ones that came back since
+    /// it was updated.
+    fn tokens(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated);
+        let refilled = elapsed.as_secs_f64() * self.limit.per_minute as f64
+            / 60.0;
+        (bucket.tokens + refilled).min(self.limit.burst as f64)
+    }
+
+    /// Takes a token of the address at `now`. Returns number of tokens left,
+    /// or number of seconds to wait for the next token if there are none.
+    fn take(&mut self, address: IpAddr, now: Instant) -> Result<u32, u64> {
+        if self.buckets.len() >= MAX_BUCKETS {
+            let burst = self.limit.burst as f64;
+            let buckets = std::mem::take(&mut self.buckets);
+            self.buckets = buckets.into_iter()
+                .filter(|(_, b)| self.tokens(b, now) < burst)
+                .collect();
+        }
+        if self.buckets.len() >= MAX_BUCKETS &&
+            !self.buckets.contains_key(&address) {
+            let oldest = self.buckets.iter()
+                .min_by_key(|(_, b)| b.updated)
+                .map(|(&a, _)| a);
+            if let Some(oldest) = oldest {
+                self.buckets.remove(&oldest);
+            }
+        }
+
+        let tokens = match self.buckets.get(&address) {
+            Some(bucket) => self.tokens(bucket, now),
+            None => self.limit.burst as f64,
+        };
+        if tokens < 1.0 {
+            let wait = (1.0 - tokens) * 60.0 / self.limit.per_minute as f64;
+            return Err(wait.ceil() as u64);
+        }
+        let tokens = tokens - 1.0;
+        self.buckets.insert(address, Bucket { tokens, updated: now });
+        Ok(tokens as u32)
+    }
+
+    /// Returns headers that tell the client about the limit.
+    fn headers(&self, remaining: u32, retry_after: Option<u64>)
+    -> Vec<Header> {
+        let header = |name: &str, value: String| {
+            Header::from_bytes(name, value).expect("header is valid")
+        };
+        let mut res = vec![
+            header("X-RateLimit-Limit", self.limit.per_minute.to_string()),
+            header("X-RateLimit-Remaining", remaining.to_string()),
+        ];
+        if let Some(seconds) = retry_after {
+            res.push(header("Retry-After", seconds.to_string()));
+        }
+        res
+    }
+}
+
+/// Person to predict for, as query of `GET /predict` or body of
+/// `POST /predict` request.
+#[derive(Deserialize)]
+#[derive(ToSchema, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+struct Request {
+    /// Name of the person
+    #[serde(default)]
+    name: String,
+    /// Birthday with day, month and year in the order the server is started
+    /// with, e.g. `31.12.1990`
+    #[serde(default)]
+    birthday: Option<String>,
+    /// Age if birthday is unknown
+    #[serde(default)]
+    age: Option<u8>,
+}
+
+/// Form of Slack slash command, only the used fields.
+#[derive(Default)]
+#[derive(ToSchema)]
+struct SlackCommand {
+    /// Name of the person and the birthday, e.g. `@alice 12/05/1990`
+    text: String,
+    /// Name of the one who sent the command
+    user_name: String,
+}
+
+impl SlackCommand {
+    /// Returns command from URL-encoded form, other fields are skipped.
+    fn parse(body: &str) -> SlackCommand {
+        let mut command = SlackCommand::default();
+        for (key, value) in form_urlencoded::parse(body.as_bytes()) {
+            match key.as_ref() {
+                "text" => command.text = value.into_owned(),
+                "user_name" => command.user_name = value.into_owned(),
+                _ => (),
+            }
+        }
+        command
+    }
+}
+
+/// Body of the response to invalid request.
+#[derive(Serialize)]
+#[derive(ToSchema)]
+struct ErrorResponse {
+    /// What is wrong with the request
+    error: String,
+}
+
+fn error(status: u16, message: &str) -> (u16, String) {
+    let body = ErrorResponse { error: message.to_string() };
+    (status, serde_json::to_string(&body).unwrap())
+}
+
+/// Counters of the server, answered at `GET /metrics`.
+#[derive(Default)]
+struct Metrics {
+    /// Number of predictions of every death reason
+    reasons: BTreeMap<String, u64>,
+    /// Number of requests answered in no longer than every bucket's bound
+    /// (see [`LATENCY_BUCKETS`])
+    latency: [u64; LATENCY_BUCKETS.len()],
+    /// Total time to answer the requests in seconds
+    latency_sum: f64,
+    requests: u64,
+    /// Number of times death reasons are read again
+    reloads: u64,
+    /// Number of predictions answered from the cache
+    cache_hits: u64,
+}
+
+impl Metrics {
+    fn add_prediction(&mut self, reason: &str) {
+        *self.reasons.entry(reason.to_string()).or_default() += 1;
+    }
+
+    fn add_request(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (count, &bound) in self.latency.iter_mut().zip(&LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *count += 1;
+            }
+        }
+        self.latency_sum += seconds;
+        self.requests += 1;
+    }
+
+    /// Returns the counters in Prometheus text format.
+    fn render(&self) -> String {
+        let mut res = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples| {
+            res.push_str(&format!(
+                "# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind
+            ));
+            for (labels, value) in samples {
+                res.push_str(&format!("{}{} {}\n", name, labels, value));
+            }
+        };
+
+        let total: u64 = self.reasons.values().sum();
+        metric(
+            "death_predictions_total", "counter", "Predictions served.",
+            vec![(String::new(), total.to_string())],
+        );
+        metric(
+            "death_predictions_by_reason_total", "counter",
+            "Predictions served by death reason.",
+            self.reasons.iter()
+                .map(|(reason, count)| (
+                    format!("{{reason=\"{}\"}}", escape_label(reason)),
+                    count.to_string(),
+                ))
+                .collect(),
+        );
+
+        let mut latency: Vec<(String, String)> = self.latency.iter()
+            .zip(LATENCY_BUCKETS)
+            .map(|(count, bound)| (
+                format!("_bucket{{le=\"{}\"}}", bound), count.to_string()
+            ))
+            .collect();
+        latency.push((
+            String::from("_bucket{le=\"+Inf\"}"), self.requests.to_string()
+        ));
+        latency.push((String::from("_sum"), self.latency_sum.to_string()));
+        latency.push((String::from("_count"), self.requests.to_string()));
+        metric(
+            "death_request_duration_seconds", "histogram",
+            "Time to answer requests.", latency,
+        );
+
+        metric(
+            "death_reasons_reloads_total", "counter",
+            "Times death reasons are read again after their files change.",
+            vec![(String::new(), self.reloads.to_string())],
+        );
+        metric(
+            "death_cache_hits_total", "counter",
+            "Predictions answered from the cache.",
+            vec![(String::new(), self.cache_hits.to_string())],
+        );
+        res
+    }
+}
+
+/// Format of the response with prediction.
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Clone, Copy)]
+enum Format {
+    /// Prediction as JSON object
+    Json,
+    /// Block Kit message for Slack (see [`report::slack`])
+    Slack,
+}
+
+/// Inputs the prediction is made from.
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Clone)]
+struct Key {
+    format: Format,
+    name: String,
+    birthday: Option<Date>,
+    age: Option<u8>,
+}
+
+impl Key {
+    /// Returns key of the response with prediction for the person. Birthday
+    /// is parsed, so the date written in different ways is the same key.
+    fn new(person: &Person, format: Format, options: &Options)
+    -> Result<Key, DeathError> {
+        let birthday = person.birthday.as_deref()
+            .map(|b| {
+                date::parse_birthday(b, options.max_age, options.date_order)
+            })
+            .transpose()?;
+        Ok(Key {
+            format,
+            name: person.name.clone(),
+            birthday,
+            // Age is not used if birthday is known
+            age: person.age.filter(|_| birthday.is_none()),
+        })
+    }
+}
+
+/// Cached response with prediction.
+#[derive(Clone)]
+struct Entry {
+    /// Text of the predicted death reason
+    reason: String,
+    body: String,
+}
+
+/// Least recently used responses with predictions. They are kept only for
+/// the day they are made on, as days left change every day.
+#[derive(Default)]
+struct Cache {
+    capacity: usize,
+    day: Option<Date>,
+    /// Entries with the time they are used last
+    entries: BTreeMap<Key, (u64, Entry)>,
+    /// Keys of the entries by the time they are used last
+    used: BTreeMap<u64, Key>,
+    time: u64,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Cache {
+        Cache { capacity, ..Default::default() }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.used.clear();
+    }
+
+    /// Returns the entry of the key if it is made `today` and marks it as
+    /// used.
+    fn get(&mut self, key: &Key, today: Date) -> Option<Entry> {
+        if self.day != Some(today) {
+            self.clear();
+            self.day = Some(today);
+        }
+        let (used, entry) = self.entries.get_mut(key)?;
+        self.time += 1;
+        self.used.remove(used);
+        self.used.insert(self.time, key.clone());
+        *used = self.time;
+        Some(entry.clone())
+    }
+
+    /// Adds the entry, removing the least recently used one if there are
+    /// too many.
+    fn insert(&mut self, key: Key, entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.used.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.time += 1;
+        self.used.insert(self.time, key.clone());
+        if let Some((used, _)) = self.entries.insert(key, (self.time, entry)) {
+            self.used.remove(&used);
+        }
+    }
+}
+
+/// What the server keeps between requests.
+#[derive(Default)]
+struct State {
+    metrics: Metrics,
+    cache: Cache,
+}
+
+/// Returns value of Prometheus label with backslashes, quotes and line
+/// breaks escaped.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Description of the API the server answers.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "death", description = "Predictions of death dates",
+        license(name = "Unlicense"),
+    ),
+    paths(predict_query, predict_body, handle_slack, metrics),
+    components(schemas(Prediction, Request, ErrorResponse)),
+)]
+struct ApiDoc;
+
+/// Returns OpenAPI description of the API as JSON, made from the types of
+/// requests and responses.
+pub fn openapi() -> String {
+    ApiDoc::openapi().to_json().expect("OpenAPI description is valid")
+}
+
+/// Returns person from query string like `name=Alice&birthday=01.02.1990`.
+fn parse_query(query: &str) -> Result<Person, DeathError> {
+    let mut person = Person::new("");
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "name" => person.name = value.into_owned(),
+            "birthday" => person.birthday = Some(value.into_owned()),
+            "age" => person.age = Some(
+                value.parse().map_err(|_| {
+                    DeathError::Invalid(format!("Invalid age: {}", value))
+                })?
+            ),
+            _ => return Err(
+                DeathError::Invalid(format!("Unknown parameter: {}", key))
+            ),
+        }
+    }
+    Ok(person)
+}
+
+/// Returns person from JSON object with `name`, `birthday` and `age`
+/// fields.
+fn parse_body(body: &str) -> Result<Person, DeathError> {
+    let request: Request = serde_json::from_str(body)
+        .map_err(|e| DeathError::Invalid(format!("Invalid JSON: {}", e)))?;
+    Ok(Person {
+        name: request.name, birthday: request.birthday, age: request.age,
+        id: None,
+    })
+}
+
+/// Returns name from Slack slash command argument, e.g. `@alice` or
+/// escaped mention `<@U123|alice>`.
+fn slack_name(text: &str) -> String {
+    let text = text.trim_start_matches('<').trim_end_matches('>');
+    let text = text.rsplit('|').next().unwrap_or(text);
+    text.trim_start_matches('@').to_string()
+}
+
+/// Returns person from Slack slash command form like
+/// `text=@alice 12/05/1990&user_name=bob`. The last word of the text is the
+/// birthday if it has digits, the other ones are the name. The one who sent
+/// the command is predicted for if no name is passed.
+fn parse_slack(body: &str) -> Person {
+    let command = SlackCommand::parse(body);
+    let mut words: Vec<&str> = command.text.split_whitespace().collect();
+    let birthday = match words.last() {
+        Some(w) if w.chars().any(|c| c.is_ascii_digit()) => words.pop(),
+        _ => None,
+    };
+    let name: Vec<String> = words.iter().map(|w| slack_name(w)).collect();
+    let mut person = match name.is_empty() {
+        true => Person::new(&command.user_name),
+        false => Person::new(&name.join(" ")),
+    };
+    person.birthday = birthday.map(String::from);
+    person
+}
+
+/// Returns value of the header, the name is case-insensitive.
+fn header<'a>(headers: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    headers.iter()
+        .find(|(field, _)| field.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+/// Checks that the request is sent by Slack app with the signing secret at
+/// `now` Unix time: `X-Slack-Signature` header must be HMAC-SHA256 of the
+/// version, `X-Slack-Request-Timestamp` header and the body. Returns HTTP
+/// status and JSON body of the error response if it's not.
+fn verify_slack(
+    body: &str, headers: &[(&str, &str)], secret: &str, now: i64,
+) -> Result<(), (u16, String)> {
+    let unauthorized = || error(401, "Invalid Slack signature");
+    let timestamp = header(headers, "X-Slack-Request-Timestamp")
+        .ok_or_else(unauthorized)?;
+    let is_recent = timestamp.parse::<i64>()
+        .is_ok_and(|t| (now - t).abs() <= SLACK_MAX_SKEW);
+    if !is_recent {
+        return Err(unauthorized());
+    }
+    let signature = header(headers, "X-Slack-Signature")
+        .and_then(|v| v.strip_prefix("v0="))
+        .and_then(decode_hex)
+        .ok_or_else(unauthorized)?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC takes key of any size");
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    mac.verify_slice(&signature).map_err(|_| unauthorized())
+}
+
+/// Returns bytes of the hex string, or [`None`] if it is not one.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Returns Slack message visible only to the one who sent the command.
+fn slack_error(message: &str) -> (u16, String) {
+    let body = serde_json::json!({
+        "response_type": "ephemeral", "text": message,
+    });
+    // Slack shows its own error instead of the message on other statuses
+    (200, body.to_string())
+}
+
+/// Returns HTTP status and JSON body of the response to the request.
+///
+/// `GET /predict?name=...&birthday=...` and `POST /predict` with JSON
+/// object of `name`, `birthday` and `age` return the prediction. Errors are
+/// JSON objects with `error` field.
+///
+/// `POST /slack` answers Slack slash commands like `/death @alice
+/// 12/05/1990` with Block Kit message. (see [`report::slack`]) They are
+/// verified with [`Options::slack_secret`] and `X-Slack-*` headers, so only
+/// [`serve`] answers them, here they get `401 Unauthorized`.
+///
+/// `GET /openapi.json` returns OpenAPI description of the API.
+/// (see [`openapi`])
+///
+/// `GET /countdown?name=...&birthday=...` only works with WebSocket
+/// connection. (see [`serve`])
+///
+/// `GET /metrics` returns counters of the server in Prometheus text format,
+/// all zeros unless the request is answered by [`serve`].
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::date::DateOrder;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::server::{self, Options};
+///
+/// let options = Options {
+///     death_reasons: ReasonSet::new(vec![Reason::new("lego")]).unwrap(),
+///     max_age: 120,
+///     date_order: DateOrder::Dmy,
+///     algorithm: Algorithm::Linear,
+///     tarot: false,
+///     rate_limit: None,
+///     cache_size: 0,
+///     tls: None,
+///     slack_secret: None,
+/// };
+///
+/// let (status, body) = server::handle(
+///     "GET", "/predict?name=Alice&birthday=01.02.1990", "", &options
+/// );
+///
+/// assert_eq!(status, 200);
+/// assert!(body.contains("\"name\":\"Alice\""));
+/// ```
+pub fn handle(method: &str, url: &str, body: &str, options: &Options)
+-> (u16, String) {
+    route(method, url, &[], body, options, &mut State::default())
+}
+
+/// Returns HTTP status and body of the response to the request with the
+/// headers and counts it in the metrics. (see [`handle`])
+fn route(
+    method: &str, url: &str, headers: &[(&str, &str)], body: &str,
+    options: &Options, state: &mut State,
+) -> (u16, String) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    match (method, path) {
+        ("GET", "/predict") => predict_query(query, options, state),
+        ("POST", "/predict") => predict_body(body, options, state),
+        ("POST", "/slack") => handle_slack(body, headers, options, state),
+        ("GET", "/openapi.json") => (200, openapi()),
+        ("GET", "/countdown") => error(426, "Connect with WebSocket"),
+        ("GET", "/metrics") => metrics(&state.metrics),
+        (
+            _,
+            "/predict" | "/slack" | "/openapi.json" | "/countdown" |
+            "/metrics"
+        ) => error(405, "Method not allowed"),
+        _ => error(404, "Not found"),
+    }
+}
+
+/// Returns HTTP status and JSON body of the response to `GET /predict`.
+#[utoipa::path(
+    get, path = "/predict", params(Request),
+    summary = "Predict for the person in the query",
+    responses(
+        (status = 200, description = "Prediction", body = Prediction),
+        (status = 400, description = "Invalid person", body = ErrorResponse),
+        (
+            status = 429, description = "Too many requests from the address",
+            body = ErrorResponse,
+        ),
+    ),
+)]
+fn predict_query(query: &str, options: &Options, state: &mut State)
+-> (u16, String) {
+    predict(parse_query(query), options, state)
+}
+
+/// Returns HTTP status and JSON body of the response to `POST /predict`.
+#[utoipa::path(
+    post, path = "/predict", request_body = Request,
+    summary = "Predict for the person in JSON body",
+    responses(
+        (status = 200, description = "Prediction", body = Prediction),
+        (status = 400, description = "Invalid person", body = ErrorResponse),
+        (
+            status = 413, description = "Body is too large",
+            body = ErrorResponse,
+        ),
+        (
+            status = 429, description = "Too many requests from the address",
+            body = ErrorResponse,
+        ),
+    ),
+)]
+fn predict_body(body: &str, options: &Options, state: &mut State)
+-> (u16, String) {
+    predict(parse_body(body), options, state)
+}
+
+/// Returns HTTP status and JSON body of the response with prediction for
+/// the person.
+fn predict(
+    person: Result<Person, DeathError>, options: &Options, state: &mut State,
+) -> (u16, String) {
+    match person.and_then(|p| answer(&p, Format::Json, options, state)) {
+        Ok(body) => (200, body),
+        Err(e) => error(400, &e.to_string()),
+    }
+}
+
+/// Returns body of the response with prediction for the person in the
+/// format, from the cache if the same person is predicted today.
+fn answer(
+    person: &Person, format: Format, options: &Options, state: &mut State,
+) -> Result<String, DeathError> {
+    let key = Key::new(person, format, options)?;
+    if let Some(entry) = state.cache.get(&key, Date::today()) {
+        state.metrics.add_prediction(&entry.reason);
+        state.metrics.cache_hits += 1;
+        return Ok(entry.body);
+    }
+
+    let user = person.to_user(
+        options.death_reasons.clone(), options.max_age, options.date_order
+    )?;
+    let prediction = Prediction::new(&user, options.algorithm, options.tarot);
+    let body = match format {
+        Format::Json => prediction.to_json(),
+        Format::Slack => report::slack(&prediction),
+    };
+    let reason = prediction.reason.text;
+    state.metrics.add_prediction(&reason);
+    state.cache.insert(key, Entry { reason, body: body.clone() });
+    Ok(body)
+}
+
+/// Returns HTTP status and JSON body of the response to Slack slash command.
+#[utoipa::path(
+    post, path = "/slack",
+    summary = "Answer Slack slash command",
+    request_body(
+        content = SlackCommand,
+        content_type = "application/x-www-form-urlencoded",
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Block Kit message with the prediction or the error",
+        ),
+        (
+            status = 401, description = "Invalid Slack signature",
+            body = ErrorResponse,
+        ),
+        (
+            status = 403, description = "Slack signing secret is not set",
+            body = ErrorResponse,
+        ),
+    ),
+)]
+fn handle_slack(
+    body: &str, headers: &[(&str, &str)], options: &Options,
+    state: &mut State,
+) -> (u16, String) {
+    let secret = match &options.slack_secret {
+        Some(v) => v,
+        None => return error(403, "Slack signing secret is not set"),
+    };
+    let now = Utc::now().timestamp();
+    if let Err(response) = verify_slack(body, headers, secret, now) {
+        return response;
+    }
+    match answer(&parse_slack(body), Format::Slack, options, state) {
+        Ok(body) => (200, body),
+        Err(e) => slack_error(&e.to_string()),
+    }
+}
+
+/// Returns HTTP status and body of the response to `GET /metrics`.
+#[utoipa::path(
+    get, path = "/metrics",
+    summary = "Counters of the server",
+    responses((
+        status = 200, content_type = "text/plain",
+        description = "Predictions served by death reason, time to answer \
+            requests and reloads of death reasons in Prometheus text format",
+    )),
+)]
+fn metrics(metrics: &Metrics) -> (u16, String) {
+    (200, metrics.render())
+}
+
+/// Returns number of seconds left until the predicted death date and time,
+/// or zero if the time is up.
+fn seconds_left(prediction: &Prediction, now: NaiveDateTime) -> i64 {
+    let date = prediction.death_date;
+    let (hour, minute) = prediction.death_time
+        .map(|t| (t.hour(), t.minute()))
+        .unwrap_or((0, 0));
+    let death = NaiveDate::from_ymd_opt(
+        date.year() as i32, date.month() as u32, date.day() as u32
+    ).and_then(|d| d.and_hms_opt(hour as u32, minute as u32, 0))
+        .expect("predicted date and time are valid");
+    (death - now).num_seconds().max(0)
+}
+
+/// Returns time left until the predicted death date and time as JSON
+/// object with `days`, `hours`, `minutes`, `seconds` and `total_seconds`.
+/// The death happens at midnight if its time is not predicted. Everything is
+/// zero when the time is up.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::date::Time;
+/// use death::prediction::Prediction;
+/// use death::reasons::ReasonSet;
+/// use death::server;
+/// use death::user::User;
+///
+/// let user = User::new(1234567890, 45, ReasonSet::default());
+/// let mut prediction = Prediction::new(&user, Algorithm::Linear, false);
+/// prediction.death_time = Time::build(0, 1);
+/// let date = prediction.death_date.add_days(-1);
+/// let now = chrono::NaiveDate::from_ymd_opt(
+///     date.year() as i32, date.month() as u32, date.day() as u32
+/// ).unwrap().and_hms_opt(23, 59, 0).unwrap();
+///
+/// assert_eq!(
+///     server::countdown(&prediction, now),
+///     r#"{"days":0,"hours":0,"minutes":2,"seconds":0,"total_seconds":120}"#
+/// );
+/// ```
+pub fn countdown(prediction: &Prediction, now: NaiveDateTime) -> String {
+    let total = seconds_left(prediction, now);
+    serde_json::json!({
+        "days": total / 86400,
+        "hours": total % 86400 / 3600,
+        "minutes": total % 3600 / 60,
+        "seconds": total % 60,
+        "total_seconds": total,
+    }).to_string()
+}
+
+/// Returns key of WebSocket handshake if the request asks to upgrade the
+/// connection to WebSocket, or HTTP status and JSON body of the error
+/// response if it asks for a version other than [`WEBSOCKET_VERSION`].
+fn websocket_key(request: &HttpRequest)
+-> Option<Result<String, (u16, String)>> {
+    let header = |name: &str| request.headers().iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str().trim().to_string());
+    let key = header("Upgrade")
+        .filter(|v| v.eq_ignore_ascii_case("websocket"))
+        .and(header("Sec-WebSocket-Key"))?;
+    match header("Sec-WebSocket-Version") {
+        Some(v) if v == WEBSOCKET_VERSION => Some(Ok(key)),
+        _ => Some(Err(error(426, "Unsupported WebSocket version"))),
+    }
+}
+
+/// Upgrades the connection to WebSocket and sends time left until the death
+/// (see [`countdown`]) once a second in a new thread, until the client is
+/// gone or the time is up. `streams` counts the threads still streaming.
+fn stream_countdown(
+    request: HttpRequest, key: &str, prediction: Prediction,
+    streams: &Arc<AtomicUsize>,
+) {
+    let response = Response::empty(101).with_header(
+        Header::from_bytes("Sec-WebSocket-Accept", derive_accept_key(
+            key.as_bytes()
+        )).expect("header is valid")
+    );
+    let stream = request.upgrade("websocket", response);
+    let streams = Arc::clone(streams);
+    streams.fetch_add(1, Ordering::SeqCst);
+    thread::spawn(move || {
+        let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+        loop {
+            let now = Local::now().naive_local();
+            let text = countdown(&prediction, now);
+            let is_over = seconds_left(&prediction, now) == 0;
+            if socket.send(Message::text(text)).is_err() || is_over {
+                break;
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+        // The client may be gone already
+        let _ = socket.close(None);
+        streams.fetch_sub(1, Ordering::SeqCst);
+    });
+}
+
+/// Listens on the address and answers requests (see [`handle`]) until the
+/// program is stopped.
+///
+/// WebSocket connections to `/countdown?name=...&birthday=...` get time
+/// left until the death once a second. (see [`countdown`]) At most 256 of
+/// them are streamed at once, others get `503 Service Unavailable`. Only
+/// version 13 of WebSocket is supported, others get `426 Upgrade Required`.
+///
+/// Requests over [`Options::rate_limit`] of the client address get `429 Too
+/// Many Requests` with `Retry-After` header. Other responses tell the limit
+/// in `X-RateLimit-Limit` and `X-RateLimit-Remaining` headers.
+///
+/// Requests with bodies over 64 KiB get `413 Payload Too Large`.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot listen on the address or read the
+/// certificate.
+pub fn serve(address: &str, options: &Options) -> Result<(), Error> {
+    run(address, options.clone(), &|| None)
+}
+
+/// Does the same as [`serve`], but predicts with the current death reasons,
+/// read again when their files change.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if cannot listen on the address or read the
+/// certificate.
+#[cfg(feature = "watch")]
+pub fn serve_live(address: &str, options: &Options, reasons: &LiveReasons)
+-> Result<(), Error> {
+    run(
+        address, options.clone(), &|| Some((reasons.reloads(), reasons.get()))
+    )
+}
+
+/// Returns server listening on the address, HTTPS one if `tls` is passed.
+#[cfg(feature = "tls")]
+fn listen(address: &str, tls: Option<&Tls>) -> Result<Server, Error> {
+    let tls = match tls {
+        Some(v) => v,
+        None => return Server::http(address).map_err(Error::other),
+    };
+    let read = |path: &PathBuf| fs::read(path).map_err(|e| {
+        Error::new(e.kind(), format!("{}: {}", path.display(), e))
+    });
+    let config = SslConfig {
+        certificate: read(&tls.certificate)?,
+        private_key: read(&tls.private_key)?,
+    };
+    Server::https(address, config).map_err(Error::other)
+}
+
+/// Returns server listening on the address, HTTPS one if `tls` is passed.
+#[cfg(not(feature = "tls"))]
+fn listen(address: &str, tls: Option<&Tls>) -> Result<Server, Error> {
+    match tls {
+        Some(_) => Err(Error::new(
+            std::io::ErrorKind::Unsupported,
+            "TLS is not enabled, build with `tls` feature",
+        )),
+        None => Server::http(address).map_err(Error::other),
+    }
+}
+
+/// Returns body of the request with the length from `Content-Length`, or
+/// HTTP status and JSON body of the error response if it's over
+/// [`MAX_BODY`] or not valid UTF-8.
+fn read_body(reader: &mut dyn Read, length: Option<usize>)
+-> Result<String, (u16, String)> {
+    let too_large = || error(413, "Body is too large");
+    if length.is_some_and(|len| len as u64 > MAX_BODY) {
+        return Err(too_large());
+    }
+    let mut body = vec![];
+    // Content-Length may be missing, so one more byte is read to tell
+    reader.take(MAX_BODY + 1).read_to_end(&mut body)
+        .map_err(|_| error(400, "Cannot read body"))?;
+    if body.len() as u64 > MAX_BODY {
+        return Err(too_large());
+    }
+    String::from_utf8(body).map_err(|_| error(400, "Body is not valid UTF-8"))
+}
+
+/// Listens on the address and answers requests. `reasons` returns number of
+/// times death reasons are read again and the current ones if they are
+/// watched.
+fn run(
+    address: &str, mut options: Options,
+    reasons: &dyn Fn() -> Option<(u64, Arc<ReasonSet>)>,
+) -> Result<(), Error> {
+    let server = listen(address, options.tls.as_ref())?;
+    let header = |value: &str| Header::from_bytes("Content-Type", value)
+        .expect("header is valid");
+    let json = header("application/json");
+    let text = header("text/plain; version=0.0.4");
+    let respond = |
+        request: HttpRequest, (status, body): (u16, String),
+        headers: Vec<Header>,
+    | {
+        let is_text = status == 200 && request.url() == "/metrics";
+        let mut response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(if is_text { text.clone() } else { json.clone() });
+        for header in headers {
+            response.add_header(header);
+        }
+        // The client is gone, nobody to tell about it
+        let _ = request.respond(response);
+    };
+    let mut state = State {
+        metrics: Metrics::default(),
+        cache: Cache::new(options.cache_size),
+    };
+    let mut limiter = options.rate_limit.map(Limiter::new);
+    let streams = Arc::new(AtomicUsize::new(0));
+
+    for mut request in server.incoming_requests() {
+        let started = Instant::now();
+        if let Some((reloads, current)) = reasons() {
+            if reloads != state.metrics.reloads {
+                options.death_reasons = ReasonSet::clone(&current);
+                state.metrics.reloads = reloads;
+                state.cache.clear();
+            }
+        }
+
+        let mut headers = vec![];
+        if let (Some(limiter), Some(client)) = (
+            &mut limiter, request.remote_addr()
+        ) {
+            match limiter.take(client.ip(), started) {
+                Ok(remaining) => headers = limiter.headers(remaining, None),
+                Err(wait) => {
+                    let headers = limiter.headers(0, Some(wait));
+                    respond(request, error(429, "Too many requests"), headers);
+                    state.metrics.add_request(started.elapsed());
+                    continue;
+                },
+            }
+        }
+
+        let (path, query) = request.url().split_once('?')
+            .unwrap_or((request.url(), ""));
+        if let ("/countdown", Some(key)) = (path, websocket_key(&request)) {
+            let user = parse_query(query).and_then(|p| p.to_user(
+                options.death_reasons.clone(), options.max_age,
+                options.date_order
+            ));
+            match (key, user) {
+                (Err(response), _) => {
+                    headers.push(Header::from_bytes(
+                        "Sec-WebSocket-Version", WEBSOCKET_VERSION
+                    ).expect("header is valid"));
+                    respond(request, response, headers)
+                },
+                _ if streams.load(Ordering::SeqCst) >= MAX_STREAMS => respond(
+                    request, error(503, "Too many countdowns at once"),
+                    headers
+                ),
+                (Ok(key), Ok(user)) => {
+                    let mut prediction = Prediction::new(
+                        &user, options.algorithm, options.tarot
+                    );
+                    prediction.death_time = Some(user.get_death_time());
+                    state.metrics.add_prediction(&prediction.reason.text);
+                    stream_countdown(request, &key, prediction, &streams);
+                },
+                (Ok(_), Err(e)) => {
+                    respond(request, error(400, &e.to_string()), headers)
+                },
+            }
+            state.metrics.add_request(started.elapsed());
+            continue;
+        }
+
+        let fields: Vec<(String, String)> = request.headers().iter()
+            .map(|h| (h.field.to_string(), h.value.to_string()))
+            .collect();
+        let fields: Vec<(&str, &str)> = fields.iter()
+            .map(|(field, value)| (field.as_str(), value.as_str()))
+            .collect();
+        let length = request.body_length();
+        let response = match read_body(request.as_reader(), length) {
+            Ok(body) => route(
+                request.method().as_str(), request.url(), &fields, &body,
+                &options, &mut state
+            ),
+            Err(response) => response,
+        };
+        respond(request, response, headers);
+        state.metrics.add_request(started.elapsed());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reasons::Reason;
+
+    fn options() -> Options {
+        Options {
+            death_reasons: ReasonSet::new(vec![Reason::new("lego")]).unwrap(),
+            max_age: 120,
+            date_order: DateOrder::Dmy,
+            algorithm: Algorithm::Linear,
+            tarot: false,
+            rate_limit: None,
+            cache_size: 2,
+            tls: None,
+            slack_secret: Some("8f742231b10e8888abcd99yyyzzz85a5".to_string()),
+        }
+    }
+
+    #[test]
+    fn predict() {
+        let options = options();
+        let get = |url| handle("GET", url, "", &options);
+
+        let (status, body) = get("/predict?name=Bob%20Smith&age=30");
+        assert_eq!(status, 200);
+        assert!(body.contains("\"name\":\"Bob Smith\""));
+        let (status, post) = handle(
+            "POST", "/predict", r#"{"name": "Bob Smith", "age": 30}"#,
+            &options
+        );
+        assert_eq!(status, 200);
+        assert_eq!(post, body);
+
+        assert_eq!(get("/predict?birthday=31.02.1990").0, 400);
+        assert_eq!(get("/predict?age=old").0, 400);
+        assert_eq!(get("/predict?nmae=Bob").0, 400);
+        assert_eq!(handle("POST", "/predict", "name", &options).0, 400);
+        let body = r#"{"nmae": "Bob"}"#;
+        assert_eq!(handle("POST", "/predict", body, &options).0, 400);
+        assert_eq!(handle("DELETE", "/predict", "", &options).0, 405);
+        assert_eq!(get("/").0, 404);
+        assert_eq!(get("/countdown?name=Bob").0, 426);
+    }
+
+    #[test]
+    fn slack() {
+        let person = parse_slack("text=%3C%40U123%7Calice%3E+12%2F05%2F1990");
+        assert_eq!(person.name, "alice");
+        assert_eq!(person.birthday.as_deref(), Some("12/05/1990"));
+        let person = parse_slack("text=%40Bob+Smith&user_name=carol");
+        assert_eq!(person.name, "Bob Smith");
+        assert_eq!(person.birthday, None);
+        assert_eq!(parse_slack("text=&user_name=carol").name, "carol");
+
+        let options = options();
+        let slack = |body: &str| {
+            let timestamp = Utc::now().timestamp().to_string();
+            let secret = options.slack_secret.as_ref().unwrap();
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .unwrap();
+            mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+            let signature: String = mac.finalize().into_bytes().iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let signature = format!("v0={}", signature);
+            let headers = [
+                ("X-Slack-Request-Timestamp", timestamp.as_str()),
+                ("x-slack-signature", signature.as_str()),
+            ];
+            route(
+                "POST", "/slack", &headers, body, &options,
+                &mut State::default()
+            )
+        };
+        let body = "command=%2Fdeath&text=%40alice+31.12.1990";
+        let (status, message) = slack(body);
+        assert_eq!(status, 200);
+        assert!(message.contains("\"in_channel\""));
+        let (status, message) = slack("text=a+1");
+        assert_eq!(status, 200);
+        assert!(message.contains("\"ephemeral\""));
+        assert_eq!(handle("POST", "/slack", body, &options).0, 401);
+        let no_secret = Options { slack_secret: None, ..options.clone() };
+        assert_eq!(handle("POST", "/slack", body, &no_secret).0, 403);
+        assert_eq!(handle("GET", "/slack", "", &options).0, 405);
+    }
+
+    #[test]
+    fn cache() {
+        let options = options();
+        let mut state = State {
+            cache: Cache::new(options.cache_size),
+            ..State::default()
+        };
+        let mut cached = |url| route("GET", url, &[], "", &options, &mut state);
+        let first = cached("/predict?name=Bob&birthday=01.02.1990");
+        assert_eq!(cached("/predict?name=Bob&birthday=1.2.1990"), first);
+        cached("/predict?name=Bob");
+        cached("/predict?name=Carol");
+        // Least recently used Bob with birthday is gone
+        cached("/predict?name=Bob&birthday=01.02.1990");
+        cached("/predict?name=Carol");
+        assert_eq!(state.metrics.cache_hits, 2);
+    }
+
+    #[test]
+    fn metrics() {
+        let options = options();
+        let mut state = State {
+            cache: Cache::new(options.cache_size),
+            ..State::default()
+        };
+        let mut get = |url| route("GET", url, &[], "", &options, &mut state);
+        get("/predict?name=Bob");
+        get("/predict?name=Bob");
+        get("/predict?name=Carol");
+        state.metrics.add_request(Duration::from_millis(3));
+        let (status, text) = route(
+            "GET", "/metrics", &[], "", &options, &mut state
+        );
+        assert_eq!(status, 200);
+        assert!(text.contains("\ndeath_predictions_total 3\n"));
+        assert!(text.contains("_by_reason_total{reason=\"lego\"} 3\n"));
+        assert!(text.contains("\ndeath_cache_hits_total 1\n"));
+        assert!(text.contains("_seconds_bucket{le=\"0.0025\"} 0\n"));
+        assert!(text.contains("_seconds_bucket{le=\"0.005\"} 1\n"));
+        assert!(text.contains("_seconds_count 1\n"));
+        assert_eq!(escape_label("a\"b\\"), "a\\\"b\\\\");
+    }
+
+    #[test]
+    fn rate_limit() {
+        let limit = RateLimit { per_minute: 60, burst: 2 };
+        let mut limiter = Limiter::new(limit);
+        let client = IpAddr::from([127, 0, 0, 1]);
+        let now = Instant::now();
+        assert_eq!(limiter.take(client, now), Ok(1));
+        assert_eq!(limiter.take(client, now), Ok(0));
+        assert_eq!(limiter.take(client, now), Err(1));
+        assert_eq!(limiter.take(IpAddr::from([10, 0, 0, 1]), now), Ok(1));
+        let later = now + Duration::from_millis(1500);
+        assert_eq!(limiter.take(client, later), Ok(0));
+        assert_eq!(limiter.take(client, later), Err(1));
+    }
+
+    #[test]
+    fn openapi_spec() {
+        let (status, spec) = handle("GET", "/openapi.json", "", &options());
+        assert_eq!(status, 200);
+        let spec: serde_json::Value = serde_json::from_str(&spec).unwrap();
+        assert!(spec["paths"]["/predict"]["post"].is_object());
+        let schemas = &spec["components"]["schemas"];
+        let fields = &schemas["Prediction"]["allOf"][1]["properties"];
+        assert_eq!(fields["death_date"]["format"], "date");
+        assert!(schemas["Reason"]["properties"]["reason"].is_object());
+    }
+
+    #[test]
+    fn body_size() {
+        let read = |body: &[u8], length| {
+            read_body(&mut std::io::Cursor::new(body), length)
+        };
+        assert_eq!(read(b"name=Bob", Some(8)), Ok("name=Bob".to_string()));
+
+        let large = vec![b'a'; MAX_BODY as usize + 1];
+        assert_eq!(read(&large, None).unwrap_err().0, 413);
+        assert_eq!(read(b"", Some(large.len())).unwrap_err().0, 413);
+        assert_eq!(read(&large[1..], None).unwrap().len(), large.len() - 1);
+        assert_eq!(read(b"\xff", None).unwrap_err().0, 400);
+    }
+
+    #[test]
+    fn full_limiter() {
+        let mut limiter = Limiter::new(RateLimit { per_minute: 1, burst: 1 });
+        let now = Instant::now();
+        for i in 0..MAX_BUCKETS as u32 {
+            let later = now + Duration::from_millis(i as u64);
+            let client = IpAddr::from(i.to_be_bytes());
+            assert_eq!(limiter.take(client, later), Ok(0));
+        }
+        let later = now + Duration::from_secs(1);
+        let client = IpAddr::from([255, 0, 0, 0]);
+        assert_eq!(limiter.take(client, later), Ok(0));
+        assert_eq!(limiter.buckets.len(), MAX_BUCKETS);
+        assert!(!limiter.buckets.contains_key(&IpAddr::from([0, 0, 0, 0])));
+        assert_eq!(limiter.take(IpAddr::from([0, 0, 0, 1]), later), Err(60));
+    }
+
+    #[test]
+    fn slack_signature() {
+        // Example from Slack documentation
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let body = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&\
+            team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&\
+            user_id=U2CERLKJA&user_name=roadrunner&\
+            command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2F\
+            hooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F\
+            96rGlfmibIGlgcZRskXaIFfN&\
+            trigger_id=398738663015.47445629121.\
+            803a0bc887a14d10d2c447fce8b6703c";
+        let signature = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27\
+            519666489c69b503";
+        let headers = [
+            ("X-Slack-Request-Timestamp", "1531420618"),
+            ("X-Slack-Signature", signature),
+        ];
+        let now = 1531420618;
+        assert_eq!(verify_slack(body, &headers, secret, now), Ok(()));
+        assert_eq!(verify_slack(body, &headers, secret, now + 60), Ok(()));
+
+        let replayed = verify_slack(body, &headers, secret, now + 3600);
+        assert_eq!(replayed.unwrap_err().0, 401);
+        let changed = verify_slack("text=bob", &headers, secret, now);
+        assert_eq!(changed.unwrap_err().0, 401);
+        let wrong = verify_slack(body, &headers, "secret", now);
+        assert_eq!(wrong.unwrap_err().0, 401);
+        let unsigned = verify_slack(body, &headers[..1], secret, now);
+        assert_eq!(unsigned.unwrap_err().0, 401);
+        assert_eq!(decode_hex("0aff"), Some(vec![10, 255]));
+        assert_eq!(decode_hex("0g"), None);
+        assert_eq!(decode_hex("abc"), None);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use serde::Serialize;
+
+use crate::algorithm::Algorithm;
+use crate::chart;
+use crate::reasons::ReasonSet;
+use crate::report;
+use crate::user::User;
+
+/// Number of years in a row of years left distribution shown in text.
+const BUCKET_YEARS: u16 = 10;
+
+/// Death reason with the share of samples it is chosen in.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct ReasonShare {
+    /// Death reason, e.g. `cars`
+    pub reason: String,
+    /// Number of samples with the reason
+    pub count: u64,
+    /// Share of samples with the reason in percent
+    pub percent: f64,
+    /// Share the reason's weight promises in percent
+    /// (see [`ReasonSet::chance`])
+    pub expected_percent: f64,
+}
+
+/// Results of predictions for generated users.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct Simulation {
+    /// Number of generated users
+    pub samples: u64,
+    /// Formula the years are calculated with
+    pub algorithm: Algorithm,
+    /// Number of samples with every number of years left, the smallest first
+    pub years_left: BTreeMap<u16, u64>,
+    /// Average number of years left
+    pub average_years_left: f64,
+    /// Death reasons in order of the set
+    pub reasons: Vec<ReasonShare>,
+}
+
+/// Returns generated id and age of the sample. Ages are from 0 to
+/// `max_age - 1` unless `age` is passed.
+fn sample(index: u64, max_age: u16, age: Option<u8>) -> (u64, u8) {
+    let mut s = DefaultHasher::new();
+    index.hash(&mut s);
+    let id = s.finish();
+    let age = age.unwrap_or_else(|| {
+        let mut s = DefaultHasher::new();
+        (index, "age").hash(&mut s);
+        (s.finish() % max_age as u64) as u8
+    });
+    (id, age)
+}
+
+impl Simulation {
+    /// Returns results of the algorithm for `samples` users with generated
+    /// ids and ages. The same arguments give the same results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::reasons::ReasonSet;
+    /// use death::simulate::Simulation;
+    ///
+    /// let sim = Simulation::run(
+    ///     10000, Algorithm::Linear, ReasonSet::default(), 100, Some(0)
+    /// );
+    ///
+    /// assert_eq!(sim.years_left.values().sum::<u64>(), 10000);
+    /// assert!((sim.average_years_left - 50.5).abs() < 1.0);
+    /// for reason in &sim.reasons {
+    ///     assert!((reason.percent - reason.expected_percent).abs() < 1.0);
+    /// }
+    /// ```
+    pub fn run(
+        samples: u64,
+        algorithm: Algorithm,
+        death_reasons: ReasonSet,
+        max_age: u16,
+        age: Option<u8>,
+    ) -> Simulation {
+        let mut years_left = BTreeMap::new();
+        let mut counts = vec![0; death_reasons.len()];
+        let mut total: u64 = 0;
+
+        let mut user = User::new(0, 0, death_reasons);
+        user.set_max_age(max_age);
+        for i in 0..samples {
+            let (id, age) = sample(i, max_age, age);
+            user.set_id(id);
+            user.set_age(age);
+            let years = user.get_years_left(algorithm);
+            *years_left.entry(years as u16).or_insert(0) += 1;
+            total += years as u64;
+            counts[user.death_reasons().pick_index(id)] += 1;
+        }
+
+        let share = |count: u64| match samples {
+            0 => 0.0,
+            _ => count as f64 * 100.0 / samples as f64,
+        };
+        let reasons = user.death_reasons().iter()
+            .zip(counts)
+            .enumerate()
+            .map(|(i, (reason, count))| ReasonShare {
+                reason: reason.text.clone(),
+                count,
+                percent: share(count),
+                expected_percent: user.death_reasons().chance(i) * 100.0,
+            })
+            .collect();
+
+        Simulation {
+            samples,
+            algorithm,
+            years_left,
+            average_years_left: match samples {
+                0 => 0.0,
+                _ => total as f64 / samples as f64,
+            },
+            reasons,
+        }
+    }
+
+    /// Returns number of samples in every [`BUCKET_YEARS`] years of years
+    /// left, e.g. `(10, 20)` for 20 samples with 10 to 19 years left.
+    pub fn buckets(&self) -> Vec<(u16, u64)> {
+        let mut res: Vec<(u16, u64)> = vec![];
+        for (&years, &count) in &self.years_left {
+            let bucket = years / BUCKET_YEARS * BUCKET_YEARS;
+            match res.last_mut() {
+                Some((b, c)) if *b == bucket => *c += count,
+                _ => res.push((bucket, count)),
+            }
+        }
+        res
+    }
+
+    /// Returns the results as text with years left and reasons drawn as bar
+    /// charts no wider than `width` characters. (see [`chart::bars`])
+    pub fn chart(&self, width: usize) -> String {
+        let mut res = String::new();
+        self.write(&mut res, Some(width)).unwrap();
+        res
+    }
+
+    /// Writes the results as text, with bar charts if `chart` width is
+    /// passed, or with tables otherwise.
+    fn write(&self, f: &mut dyn fmt::Write, chart: Option<usize>)
+    -> fmt::Result {
+        writeln!(f, "Samples: {}", self.samples)?;
+        let (min, max) = match (
+            self.years_left.keys().next(), self.years_left.keys().last()
+        ) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return Ok(()),
+        };
+        writeln!(
+            f, "Years left: {} to {}, {:.1} on average", min, max,
+            self.average_years_left
+        )?;
+
+        let percent = |count: u64| count as f64 * 100.0 / self.samples as f64;
+        let buckets = self.buckets();
+        let width = buckets.iter()
+            .map(|(_, count)| count.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let years: Vec<(String, f64, String)> = buckets.iter()
+            .map(|&(bucket, count)| (
+                format!("{}-{}", bucket, bucket + BUCKET_YEARS - 1),
+                count as f64,
+                format!("{:<width$}  {:.1}%", count, percent(count)),
+            ))
+            .collect();
+        write!(f, "\nYears left\n{}", chart::table(&years, chart))?;
+
+        let reasons = match chart {
+            Some(width) => chart::bars(&self.reasons.iter()
+                .map(|r| (
+                    r.reason.clone(),
+                    r.percent,
+                    format!(
+                        "{:.2}%, expected {:.2}%", r.percent, r.expected_percent
+                    ),
+                ))
+                .collect::<Vec<_>>(), width),
+            None => {
+                let mut rows = vec![vec![
+                    String::from("Reason"), String::from("Count"),
+                    String::from("Share"), String::from("Expected"),
+                ]];
+                rows.extend(self.reasons.iter().map(|r| vec![
+                    r.reason.clone(),
+                    r.count.to_string(),
+                    format!("{:.2}%", r.percent),
+                    format!("{:.2}%", r.expected_percent),
+                ]));
+                report::columns(&rows)
+            },
+        };
+        write!(f, "\nReasons\n{}", reasons)
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fairness() {
+        assert_eq!(sample(7, 100, None), sample(7, 100, None));
+        assert!((0..1000).all(|i| sample(i, 100, None).1 < 100));
+        assert_eq!(sample(7, 100, Some(30)).1, 30);
+
+        let sim = Simulation::run(
+            100000, Algorithm::Exponential, ReasonSet::default(), 120, None
+        );
+        let smallest = sim.years_left.iter().next().unwrap();
+        assert!(sim.years_left.values().all(|c| c <= smallest.1));
+        assert!(sim.reasons.iter()
+            .all(|r| (r.percent - r.expected_percent).abs() < 0.5));
+
+        let text = sim.to_string();
+        assert!(text.starts_with("Samples: 100000\nYears left: 1 to "));
+        assert!(text.contains("\n  0-9 "));
+        let chart = sim.chart(60);
+        assert!(chart.contains(chart::BAR));
+        assert_eq!(chart.lines().count(), text.lines().count() - 1);
+        assert_eq!(Simulation::run(
+            0, Algorithm::Linear, ReasonSet::default(), 120, None
+        ).to_string(), "Samples: 0\n");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::date;
+
+/// Species of the one whose death date is predicted.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Species {
+    #[default]
+    Human,
+    Dog,
+    Cat,
+    Hamster,
+    Rabbit,
+    Parrot,
+    Goldfish,
+    Tortoise,
+}
+
+impl Species {
+    /// Every species.
+    pub const ALL: [Species; 8] = [
+        Species::Human, Species::Dog, Species::Cat, Species::Hamster,
+        Species::Rabbit, Species::Parrot, Species::Goldfish, Species::Tortoise,
+    ];
+
+    /// Returns name of the species in lowercase, e.g. `dog`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Species::Human => "human",
+            Species::Dog => "dog",
+            Species::Cat => "cat",
+            Species::Hamster => "hamster",
+            Species::Rabbit => "rabbit",
+            Species::Parrot => "parrot",
+            Species::Goldfish => "goldfish",
+            Species::Tortoise => "tortoise",
+        }
+    }
+
+    /// Returns max age of the species.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::date;
+    /// use death::species::Species;
+    ///
+    /// assert_eq!(Species::Human.max_age(), date::MAX_AGE);
+    /// assert_eq!(Species::Hamster.max_age(), 4);
+    /// ```
+    pub fn max_age(&self) -> u16 {
+        match self {
+            Species::Human => date::MAX_AGE,
+            Species::Dog => 20,
+            Species::Cat => 25,
+            Species::Hamster => 4,
+            Species::Rabbit => 12,
+            Species::Parrot => 80,
+            Species::Goldfish => 30,
+            Species::Tortoise => 150,
+        }
+    }
+
+    /// Returns default death reasons of the species.
+    pub fn default_death_reasons(&self) -> Vec<String> {
+        let reasons = match self {
+            Species::Human => return crate::default_death_reasons(),
+            Species::Dog => vec![
+                "chocolate", "cars", "squirrel chase", "bee stings",
+                "overeating", "tennis balls", "chewed cables",
+            ],
+            Species::Cat => vec![
+                "curiosity", "balcony", "hairballs", "cars",
+                "neighbour's dog", "lilies", "washing machine",
+            ],
+            Species::Hamster => vec![
+                "cat", "escape", "exercise wheel", "cold", "overfeeding",
+                "children",
+            ],
+            Species::Rabbit => vec![
+                "foxes", "heat", "chewed cables", "fright", "teeth",
+            ],
+            Species::Parrot => vec![
+                "teflon fumes", "open windows", "avocado", "ceiling fans",
+                "boredom", "mirrors",
+            ],
+            Species::Goldfish => vec![
+                "dirty water", "overfeeding", "cat", "toilet", "jumping out",
+                "children",
+            ],
+            Species::Tortoise => vec![
+                "hibernation", "foxes", "lawnmower", "falling on its back",
+                "eagles",
+            ],
+        };
+        reasons.into_iter().map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_species() {
+        for species in Species::ALL {
+            assert!(species.max_age() > 0);
+            assert!(!species.default_death_reasons().is_empty());
+        }
+        #[cfg(feature = "cli")]
+        for species in Species::ALL {
+            use clap::ValueEnum;
+            let value = species.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), species.name());
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::chart;
+use crate::prediction::Prediction;
+
+/// Number of the most common death reasons shown in text.
+const TOP_REASONS: usize = 10;
+
+/// Death reason with the number of predictions it is chosen in.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct ReasonCount {
+    /// Death reason, e.g. `cars`
+    pub reason: String,
+    /// Number of predictions with the reason
+    pub count: usize,
+}
+
+/// Aggregate statistics of predictions.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+pub struct Stats {
+    /// Number of predictions
+    pub count: usize,
+    /// Number of deaths in every year, the earliest year first
+    pub years: BTreeMap<u16, usize>,
+    /// Death reasons, the most common first
+    pub reasons: Vec<ReasonCount>,
+    /// Average number of full years left
+    pub average_years_left: f64,
+    /// Prediction with the earliest death date
+    pub first_to_die: Option<Prediction>,
+    /// Prediction with the latest death date
+    pub last_to_die: Option<Prediction>,
+}
+
+impl Stats {
+    /// Returns statistics of the predictions. Reasons chosen the same
+    /// number of times are in order of their first prediction, so are the
+    /// first and the last to die on the same day.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::algorithm::Algorithm;
+    /// use death::prediction::Prediction;
+    /// use death::reasons::ReasonSet;
+    /// use death::stats::Stats;
+    /// use death::user::User;
+    ///
+    /// let predictions: Vec<Prediction> = (0..100)
+    ///     .map(|id| User::new(id, 30, ReasonSet::default()))
+    ///     .map(|user| Prediction::new(&user, Algorithm::Linear, false))
+    ///     .collect();
+    ///
+    /// let stats = Stats::new(&predictions);
+    ///
+    /// assert_eq!(stats.count, 100);
+    /// assert_eq!(stats.years.values().sum::<usize>(), 100);
+    /// assert!(stats.reasons[0].count >= stats.reasons[1].count);
+    /// ```
+    pub fn new(predictions: &[Prediction]) -> Stats {
+        let mut years = BTreeMap::new();
+        let mut reasons: Vec<ReasonCount> = vec![];
+        for prediction in predictions {
+            *years.entry(prediction.death_date.year()).or_insert(0) += 1;
+            let text = &prediction.reason.text;
+            match reasons.iter_mut().find(|r| &r.reason == text) {
+                Some(r) => r.count += 1,
+                None => reasons.push(ReasonCount {
+                    reason: text.clone(), count: 1,
+                }),
+            }
+        }
+        reasons.sort_by_key(|r| Reverse(r.count));
+
+        let total: u64 = predictions.iter()
+            .map(|p| p.years_left as u64)
+            .sum();
+        let average_years_left = match predictions.len() {
+            0 => 0.0,
+            len => total as f64 / len as f64,
+        };
+
+        Stats {
+            count: predictions.len(),
+            years,
+            reasons,
+            average_years_left,
+            first_to_die: predictions.iter()
+                .min_by_key(|p| p.death_date)
+                .cloned(),
+            last_to_die: predictions.iter()
+                .rev()
+                .max_by_key(|p| p.death_date)
+                .cloned(),
+        }
+    }
+
+    /// Returns the share of predictions with the reason in percent.
+    pub fn percent(&self, reason: &ReasonCount) -> f64 {
+        match self.count {
+            0 => 0.0,
+            count => reason.count as f64 * 100.0 / count as f64,
+        }
+    }
+
+    /// Returns the statistics as text with death years and reasons drawn
+    /// as bar charts no wider than `width` characters.
+    /// (see [`chart::bars`])
+    pub fn chart(&self, width: usize) -> String {
+        let mut res = String::new();
+        self.write(&mut res, Some(width)).unwrap();
+        res
+    }
+
+    /// Writes the statistics as text, with bar charts if `chart` width is
+    /// passed, or with tables otherwise.
+    fn write(&self, f: &mut dyn fmt::Write, chart: Option<usize>)
+    -> fmt::Result {
+        writeln!(f, "Predictions: {}", self.count)?;
+        let (first, last) = match (&self.first_to_die, &self.last_to_die) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Ok(()),
+        };
+        writeln!(
+            f, "Years left: {} to {}, {:.1} on average", first.years_left,
+            last.years_left, self.average_years_left
+        )?;
+        let who = |p: &Prediction| match p.name.as_str() {
+            "" => p.death_date.to_string(),
+            name => format!("{}, {}", name, p.death_date),
+        };
+        writeln!(f, "First to die: {}", who(first))?;
+        writeln!(f, "Last to die: {}", who(last))?;
+
+        let years: Vec<(String, f64, String)> = self.years.iter()
+            .map(|(year, &count)| {
+                (year.to_string(), count as f64, count.to_string())
+            })
+            .collect();
+        write!(f, "\nDeath years\n{}", chart::table(&years, chart))?;
+
+        let top = &self.reasons[..self.reasons.len().min(TOP_REASONS)];
+        let width = top.iter()
+            .map(|r| r.count.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let reasons: Vec<(String, f64, String)> = top.iter()
+            .map(|r| (
+                r.reason.clone(),
+                r.count as f64,
+                format!("{:<width$}  {:.1}%", r.count, self.percent(r)),
+            ))
+            .collect();
+        write!(f, "\nMost common reasons\n{}", chart::table(&reasons, chart))
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Algorithm;
+    use crate::reasons::ReasonSet;
+    use crate::user::User;
+
+    #[test]
+    fn aggregate() {
+        let predictions: Vec<Prediction> = ["Alice", "Bob", "Carol"].iter()
+            .map(|name| {
+                let mut user = User::new(0, 30, ReasonSet::default());
+                user.set_name(name);
+                Prediction::new(&user, Algorithm::Linear, false)
+            })
+            .collect();
+        let stats = Stats::new(&predictions);
+
+        let first = stats.first_to_die.as_ref().unwrap();
+        let last = stats.last_to_die.as_ref().unwrap();
+        assert!(first.death_date <= last.death_date);
+        assert_eq!(stats.reasons.iter().map(|r| r.count).sum::<usize>(), 3);
+        let sum: u16 = predictions.iter().map(|p| p.years_left).sum();
+        assert_eq!(stats.average_years_left, sum as f64 / 3.0);
+
+        let text = stats.to_string();
+        assert!(text.starts_with("Predictions: 3\n"));
+        assert!(text.contains("\nMost common reasons\n"));
+
+        let chart = stats.chart(60);
+        assert!(chart.contains(chart::BAR));
+        assert_eq!(chart.lines().count(), text.lines().count());
+
+        let empty = Stats::new(&[]);
+        assert_eq!(empty.to_string(), "Predictions: 0\n");
+    }
+}
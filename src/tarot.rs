@@ -0,0 +1,439 @@
+use crate::locale::{self, fill, Language, Message};
+use crate::reasons::Reason;
+
+use serde::Serialize;
+
+/// Names and meanings of the cards in a language.
+struct Texts {
+    /// Major arcana: name and meaning
+    major: [(&'static str, &'static str); 22],
+    /// Minor arcana suits: name and domain
+    suits: [(&'static str, &'static str); 4],
+    /// Minor arcana ranks: name and meaning
+    ranks: [(&'static str, &'static str); 14],
+}
+
+const TEXTS_EN: Texts = Texts {
+    major: [
+        ("The Fool", "a reckless first step"),
+        ("The Magician", "power in your hands"),
+        ("The High Priestess", "hidden secrets"),
+        ("The Empress", "abundance"),
+        ("The Emperor", "rigid structure"),
+        ("The Hierophant", "old traditions"),
+        ("The Lovers", "a burning choice"),
+        ("The Chariot", "unstoppable drive"),
+        ("Strength", "taming the beast"),
+        ("The Hermit", "solitude"),
+        ("Wheel of Fortune", "fate in motion"),
+        ("Justice", "cause and effect"),
+        ("The Hanged Man", "suspension"),
+        ("Death", "an ending"),
+        ("Temperance", "mixing of waters"),
+        ("The Devil", "excess"),
+        ("The Tower", "sudden upheaval"),
+        ("The Star", "a spark of hope"),
+        ("The Moon", "illusion and fear"),
+        ("The Sun", "scorching vitality"),
+        ("Judgement", "the final call"),
+        ("The World", "completion"),
+    ],
+    suits: [
+        ("Wands", "passion"),
+        ("Cups", "emotions"),
+        ("Swords", "conflict"),
+        ("Pentacles", "material things"),
+    ],
+    ranks: [
+        ("Ace", "a new start"),
+        ("Two", "a choice"),
+        ("Three", "growth"),
+        ("Four", "stability"),
+        ("Five", "loss"),
+        ("Six", "harmony"),
+        ("Seven", "a test"),
+        ("Eight", "movement"),
+        ("Nine", "anxiety"),
+        ("Ten", "a burden"),
+        ("Page", "a message"),
+        ("Knight", "haste"),
+        ("Queen", "care"),
+        ("King", "mastery"),
+    ],
+};
+
+const TEXTS_RU: Texts = Texts {
+    major: [
+        ("Шут", "безрассудный первый шаг"),
+        ("Маг", "сила в твоих руках"),
+        ("Верховная Жрица", "скрытые тайны"),
+        ("Императрица", "изобилие"),
+        ("Император", "жёсткий порядок"),
+        ("Иерофант", "старые традиции"),
+        ("Влюблённые", "жгучий выбор"),
+        ("Колесница", "неудержимый порыв"),
+        ("Сила", "укрощение зверя"),
+        ("Отшельник", "одиночество"),
+        ("Колесо Фортуны", "судьба в движении"),
+        ("Справедливость", "причина и следствие"),
+        ("Повешенный", "подвешенность"),
+        ("Смерть", "конец"),
+        ("Умеренность", "смешение вод"),
+        ("Дьявол", "излишество"),
+        ("Башня", "внезапный переворот"),
+        ("Звезда", "искра надежды"),
+        ("Луна", "иллюзии и страх"),
+        ("Солнце", "палящая жизненная сила"),
+        ("Суд", "последний зов"),
+        ("Мир", "завершённость"),
+    ],
+    suits: [
+        ("Жезлов", "страсти"),
+        ("Кубков", "чувствах"),
+        ("Мечей", "конфликте"),
+        ("Пентаклей", "материальном"),
+    ],
+    ranks: [
+        ("Туз", "новое начало"),
+        ("Двойка", "выбор"),
+        ("Тройка", "рост"),
+        ("Четвёрка", "стабильность"),
+        ("Пятёрка", "потеря"),
+        ("Шестёрка", "гармония"),
+        ("Семёрка", "испытание"),
+        ("Восьмёрка", "движение"),
+        ("Девятка", "тревога"),
+        ("Десятка", "бремя"),
+        ("Паж", "весть"),
+        ("Рыцарь", "спешка"),
+        ("Королева", "забота"),
+        ("Король", "мастерство"),
+    ],
+};
+
+const TEXTS_DE: Texts = Texts {
+    major: [
+        ("Der Narr", "ein leichtsinniger erster Schritt"),
+        ("Der Magier", "Macht in deinen Händen"),
+        ("Die Hohepriesterin", "verborgene Geheimnisse"),
+        ("Die Herrscherin", "Überfluss"),
+        ("Der Herrscher", "starre Ordnung"),
+        ("Der Hierophant", "alte Traditionen"),
+        ("Die Liebenden", "eine brennende Wahl"),
+        ("Der Wagen", "unaufhaltsamer Antrieb"),
+        ("Die Kraft", "die Zähmung des Tieres"),
+        ("Der Eremit", "Einsamkeit"),
+        ("Das Rad des Schicksals", "Schicksal in Bewegung"),
+        ("Die Gerechtigkeit", "Ursache und Wirkung"),
+        ("Der Gehängte", "Schwebezustand"),
+        ("Der Tod", "ein Ende"),
+        ("Die Mäßigkeit", "Mischung der Wasser"),
+        ("Der Teufel", "Maßlosigkeit"),
+        ("Der Turm", "plötzlicher Umbruch"),
+        ("Der Stern", "ein Funke Hoffnung"),
+        ("Der Mond", "Illusion und Angst"),
+        ("Die Sonne", "sengende Lebenskraft"),
+        ("Das Gericht", "der letzte Ruf"),
+        ("Die Welt", "Vollendung"),
+    ],
+    suits: [
+        ("Stäbe", "Leidenschaft"),
+        ("Kelche", "Gefühlen"),
+        ("Schwerter", "Konflikten"),
+        ("Münzen", "materiellen Dingen"),
+    ],
+    ranks: [
+        ("Ass", "ein Neuanfang"),
+        ("Zwei", "eine Wahl"),
+        ("Drei", "Wachstum"),
+        ("Vier", "Stabilität"),
+        ("Fünf", "Verlust"),
+        ("Sechs", "Harmonie"),
+        ("Sieben", "eine Prüfung"),
+        ("Acht", "Bewegung"),
+        ("Neun", "Sorge"),
+        ("Zehn", "eine Last"),
+        ("Bube", "eine Botschaft"),
+        ("Ritter", "Eile"),
+        ("Königin", "Fürsorge"),
+        ("König", "Meisterschaft"),
+    ],
+};
+
+const TEXTS_ES: Texts = Texts {
+    major: [
+        ("El Loco", "un primer paso imprudente"),
+        ("El Mago", "poder en tus manos"),
+        ("La Sacerdotisa", "secretos ocultos"),
+        ("La Emperatriz", "abundancia"),
+        ("El Emperador", "estructura rígida"),
+        ("El Hierofante", "viejas tradiciones"),
+        ("Los Enamorados", "una elección ardiente"),
+        ("El Carro", "impulso imparable"),
+        ("La Fuerza", "domar a la bestia"),
+        ("El Ermitaño", "soledad"),
+        ("La Rueda de la Fortuna", "el destino en movimiento"),
+        ("La Justicia", "causa y efecto"),
+        ("El Colgado", "suspensión"),
+        ("La Muerte", "un final"),
+        ("La Templanza", "mezcla de aguas"),
+        ("El Diablo", "exceso"),
+        ("La Torre", "agitación repentina"),
+        ("La Estrella", "una chispa de esperanza"),
+        ("La Luna", "ilusión y miedo"),
+        ("El Sol", "vitalidad abrasadora"),
+        ("El Juicio", "la llamada final"),
+        ("El Mundo", "plenitud"),
+    ],
+    suits: [
+        ("Bastos", "la pasión"),
+        ("Copas", "las emociones"),
+        ("Espadas", "el conflicto"),
+        ("Oros", "las cosas materiales"),
+    ],
+    ranks: [
+        ("As", "un nuevo comienzo"),
+        ("Dos", "una elección"),
+        ("Tres", "crecimiento"),
+        ("Cuatro", "estabilidad"),
+        ("Cinco", "pérdida"),
+        ("Seis", "armonía"),
+        ("Siete", "una prueba"),
+        ("Ocho", "movimiento"),
+        ("Nueve", "ansiedad"),
+        ("Diez", "una carga"),
+        ("Sota", "un mensaje"),
+        ("Caballero", "prisa"),
+        ("Reina", "cuidado"),
+        ("Rey", "maestría"),
+    ],
+};
+
+const TEXTS_FR: Texts = Texts {
+    major: [
+        ("Le Mat", "un premier pas imprudent"),
+        ("Le Bateleur", "le pouvoir entre vos mains"),
+        ("La Papesse", "des secrets cachés"),
+        ("L'Impératrice", "l'abondance"),
+        ("L'Empereur", "une structure rigide"),
+        ("Le Pape", "les vieilles traditions"),
+        ("L'Amoureux", "un choix brûlant"),
+        ("Le Chariot", "un élan irrésistible"),
+        ("La Force", "dompter la bête"),
+        ("L'Hermite", "la solitude"),
+        ("La Roue de Fortune", "le destin en mouvement"),
+        ("La Justice", "cause et effet"),
+        ("Le Pendu", "la suspension"),
+        ("La Mort", "une fin"),
+        ("Tempérance", "le mélange des eaux"),
+        ("Le Diable", "l'excès"),
+        ("La Maison Dieu", "un bouleversement soudain"),
+        ("L'Étoile", "une étincelle d'espoir"),
+        ("La Lune", "l'illusion et la peur"),
+        ("Le Soleil", "une vitalité brûlante"),
+        ("Le Jugement", "le dernier appel"),
+        ("Le Monde", "l'accomplissement"),
+    ],
+    suits: [
+        ("Bâtons", "la passion"),
+        ("Coupes", "les émotions"),
+        ("Épées", "le conflit"),
+        ("Deniers", "les choses matérielles"),
+    ],
+    ranks: [
+        ("As", "un nouveau départ"),
+        ("Deux", "un choix"),
+        ("Trois", "la croissance"),
+        ("Quatre", "la stabilité"),
+        ("Cinq", "la perte"),
+        ("Six", "l'harmonie"),
+        ("Sept", "une épreuve"),
+        ("Huit", "le mouvement"),
+        ("Neuf", "l'anxiété"),
+        ("Dix", "un fardeau"),
+        ("Valet", "un message"),
+        ("Cavalier", "la hâte"),
+        ("Reine", "le soin"),
+        ("Roi", "la maîtrise"),
+    ],
+};
+
+/// Categories of death reasons major arcana points at, in the same order.
+/// (see [`crate::reasons::DEFAULT_CATEGORIES`])
+const MAJOR_CATEGORIES: [&str; 22] = [
+    "accident", "mystery", "mystery", "natural", "accident", "natural",
+    "violence", "accident", "violence", "mystery", "accident", "violence",
+    "accident", "natural", "natural", "violence", "accident", "mystery",
+    "mystery", "natural", "mystery", "natural",
+];
+
+/// Categories of death reasons minor arcana suits point at, in the same
+/// order.
+const SUIT_CATEGORIES: [&str; 4] = [
+    "accident", "natural", "violence", "mystery",
+];
+
+/// Number of cards in the deck.
+pub const DECK_SIZE: usize = MAJOR_CATEGORIES.len() +
+    SUIT_CATEGORIES.len() * TEXTS_EN.ranks.len();
+
+/// Tarot card.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Card {
+    /// Position of the card in the deck
+    #[serde(skip)]
+    pub index: usize,
+    /// Card name, e.g. `The Tower` or `Ace of Cups`
+    pub name: String,
+    /// Short meaning of the card
+    pub meaning: String,
+    /// Category of death reasons the card points at, e.g. `accident`
+    pub category: String,
+}
+
+fn texts(language: Language) -> &'static Texts {
+    match language {
+        Language::En => &TEXTS_EN,
+        Language::Ru => &TEXTS_RU,
+        Language::De => &TEXTS_DE,
+        Language::Es => &TEXTS_ES,
+        Language::Fr => &TEXTS_FR,
+    }
+}
+
+/// Returns card by its position in the deck in language of messages.
+/// Major arcana goes first, then minor arcana suit by suit.
+///
+/// # Panics
+///
+/// Panics if `index` is not less than [`DECK_SIZE`].
+///
+/// # Example
+///
+/// ```
+/// use death::tarot;
+///
+/// assert_eq!(tarot::card(16).name, "The Tower");
+/// assert_eq!(tarot::card(22).name, "Ace of Wands");
+/// assert_eq!(tarot::card(77).name, "King of Pentacles");
+/// ```
+pub fn card(index: usize) -> Card {
+    card_in(index, locale::language())
+}
+
+/// Returns card by its position in the deck translated to the language.
+/// (see [`card`])
+///
+/// # Panics
+///
+/// Panics if `index` is not less than [`DECK_SIZE`].
+///
+/// # Example
+///
+/// ```
+/// use death::locale::Language;
+/// use death::tarot;
+///
+/// assert_eq!(tarot::card_in(16, Language::De).name, "Der Turm");
+/// assert_eq!(tarot::card_in(77, Language::Ru).name, "Король Пентаклей");
+/// ```
+pub fn card_in(index: usize, language: Language) -> Card {
+    let texts = texts(language);
+    if index < MAJOR_CATEGORIES.len() {
+        let (name, meaning) = texts.major[index];
+        return Card {
+            index,
+            name: name.to_string(),
+            meaning: meaning.to_string(),
+            category: MAJOR_CATEGORIES[index].to_string(),
+        };
+    }
+
+    let minor = index - MAJOR_CATEGORIES.len();
+    let suit = minor / texts.ranks.len();
+    let (suit_name, domain) = texts.suits[suit];
+    let (rank, meaning) = texts.ranks[minor % texts.ranks.len()];
+
+    Card {
+        index,
+        name: fill(
+            language.message(Message::MinorArcana), &[&rank, &suit_name]
+        ),
+        meaning: fill(
+            language.message(Message::MinorArcanaMeaning), &[&meaning, &domain]
+        ),
+        category: SUIT_CATEGORIES[suit].to_string(),
+    }
+}
+
+/// Returns all cards of the deck.
+pub fn deck() -> Vec<Card> {
+    (0..DECK_SIZE).map(card).collect()
+}
+
+/// Draws a card from the deck using user's id.
+pub fn draw(id: u64) -> Card {
+    // Use other bits than the reason and the date do
+    card((id.rotate_right(32) % DECK_SIZE as u64) as usize)
+}
+
+/// Returns index of the reason the card points at: one of the reasons of
+/// the card's category, chosen by card's position in the deck.
+///
+/// If there is no reason of the category in the list, card's position is
+/// used to choose from all reasons instead.
+pub fn reason_index(card: &Card, death_reasons: &[Reason]) -> usize {
+    let matching: Vec<usize> = death_reasons.iter().enumerate()
+        .filter(|(_, r)| r.category.as_ref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(&card.category)))
+        .map(|(i, _)| i)
+        .collect();
+    if matching.is_empty() {
+        return card.index % death_reasons.len();
+    }
+    matching[card.index % matching.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deck_is_complete() {
+        assert_eq!(deck().len(), 78);
+
+        for language in Language::ALL {
+            let deck: Vec<Card> = (0..DECK_SIZE)
+                .map(|i| card_in(i, language))
+                .collect();
+            for (i, a) in deck.iter().enumerate() {
+                assert_eq!(a.index, i);
+                for b in deck.iter().skip(i + 1) {
+                    assert_ne!(a.name, b.name, "{:?}", language);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reason_mapping() {
+        // The Tower points at accidents
+        let tower = card_in(16, Language::En);
+        let mut reasons = vec![Reason::new("cancer"), Reason::new("cars"),
+            Reason::new("fire"), Reason::new("flood")];
+        reasons[0].category = Some("natural".to_string());
+        reasons[1].category = Some("Accident".to_string());
+        reasons[2].category = Some("accident".to_string());
+        reasons[3].category = Some("accident".to_string());
+        assert_eq!(reason_index(&tower, &reasons), 1 + 16 % 3);
+
+        // No reasons of the category, position 16 in the deck is used
+        let reasons = vec![Reason::new("lego"), Reason::new("cars"),
+            Reason::new("pets")];
+        assert_eq!(reason_index(&tower, &reasons), 16 % 3);
+    }
+}
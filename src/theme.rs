@@ -0,0 +1,55 @@
+/// Built-in death reasons pack. (see [`crate::default_death_reasons_for`])
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Theme {
+    /// Everyday dangers, same as default death reasons
+    #[default]
+    Classic,
+    /// Ridiculous accidents
+    Silly,
+    /// Space and future technology
+    SciFi,
+    /// Knights, plagues and dragons
+    Medieval,
+}
+
+impl Theme {
+    /// Every theme.
+    pub const ALL: [Theme; 4] = [
+        Theme::Classic, Theme::Silly, Theme::SciFi, Theme::Medieval,
+    ];
+
+    /// Returns name of the theme, e.g. `sci-fi`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Classic => "classic",
+            Theme::Silly => "silly",
+            Theme::SciFi => "sci-fi",
+            Theme::Medieval => "medieval",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_death_reasons_for;
+
+    #[test]
+    fn all_themes() {
+        for theme in Theme::ALL {
+            let reasons = default_death_reasons_for(theme);
+            assert!(!reasons.is_empty());
+            assert!(reasons.iter().all(|r| !r.trim().is_empty()));
+        }
+        #[cfg(feature = "cli")]
+        for theme in Theme::ALL {
+            use clap::ValueEnum;
+            let value = theme.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), theme.name());
+        }
+    }
+}
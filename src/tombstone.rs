@@ -0,0 +1,183 @@
+use std::env;
+
+#[cfg(feature = "cli")]
+use terminal_size::{terminal_size, Width};
+
+use crate::prediction::Prediction;
+
+/// Narrowest tombstone in characters, unless the terminal is narrower.
+pub const MIN_WIDTH: usize = 24;
+
+/// Width of terminal if it is not known.
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Last words to choose from. (see [`last_words`])
+pub const LAST_WORDS: [&str; 12] = [
+    "I told you I was ill.",
+    "Hold my drink.",
+    "What does this button do?",
+    "It's probably nothing.",
+    "I've never felt better.",
+    "Trust me, I've done this before.",
+    "Is it supposed to make that noise?",
+    "Don't worry, it's not loaded.",
+    "I'll just take a shortcut.",
+    "Remember to water the plants.",
+    "Wait, I have one more thing to say.",
+    "So that's what's on the other side.",
+];
+
+/// Returns width of the terminal: the real one if stdout is a terminal,
+/// `COLUMNS` environment variable or 80 otherwise. The real width is known
+/// only with `cli` feature.
+pub fn terminal_width() -> usize {
+    #[cfg(feature = "cli")]
+    if let Some((Width(width), _)) = terminal_size() {
+        return width as usize;
+    }
+    env::var("COLUMNS").ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+/// Returns lines of the text no longer than `width` characters. Words
+/// longer than `width` are split.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+
+        let length = line.chars().count();
+        if length > 0 && length + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns the text centered in `width` characters.
+fn center(text: &str, width: usize) -> String {
+    let length = text.chars().count();
+    let left = (width - length) / 2;
+    format!(
+        "{}{}{}", " ".repeat(left), text, " ".repeat(width - length - left)
+    )
+}
+
+/// Returns epitaph of the prediction, e.g. `Taken by lego`.
+pub fn epitaph(prediction: &Prediction) -> String {
+    format!("Taken by {}", prediction.reason)
+}
+
+/// Returns last words of someone with the id, picked from [`LAST_WORDS`].
+///
+/// # Example
+///
+/// ```
+/// use death::tombstone::{self, LAST_WORDS};
+///
+/// assert!(LAST_WORDS.contains(&tombstone::last_words(1234567890)));
+/// ```
+pub fn last_words(id: u64) -> &'static str {
+    // Other bits of id than the date and the reason are made of
+    LAST_WORDS[(id.rotate_left(29) % LAST_WORDS.len() as u64) as usize]
+}
+
+/// Returns the prediction drawn as a tombstone with the name, years of life
+/// and the epitaph, no wider than `width` characters.
+///
+/// # Example
+///
+/// ```
+/// use death::algorithm::Algorithm;
+/// use death::prediction::Prediction;
+/// use death::reasons::{Reason, ReasonSet};
+/// use death::tombstone;
+/// use death::user::User;
+///
+/// let reasons = ReasonSet::new(vec![Reason::new("lego")]).unwrap();
+/// let mut user = User::new(1234567890, 45, reasons);
+/// user.set_name("Alice");
+/// let prediction = Prediction::new(&user, Algorithm::Linear, false);
+///
+/// let tombstone = tombstone::render(&prediction, 30);
+///
+/// assert!(tombstone.contains("ALICE"));
+/// assert!(tombstone.contains("Taken by lego"));
+/// assert!(tombstone.lines().all(|l| l.chars().count() <= 30));
+/// ```
+pub fn render(prediction: &Prediction, width: usize) -> String {
+    let birth_year = match prediction.birthday {
+        Some(birthday) => birthday.year().to_string(),
+        None => String::from("?"),
+    };
+    let years = format!("{} - {}", birth_year, prediction.death_date.year());
+    let epitaph = epitaph(prediction);
+    let name = prediction.name.to_uppercase();
+
+    let longest = [&name, &years, &epitaph].iter()
+        .map(|t| t.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    // Border and spaces around the text take 6 characters, the base sticks
+    // out by 1 character on each side
+    let width = (longest + 8).max(MIN_WIDTH).min(width).max(12);
+    let stone = width - 2;
+    let inner = stone - 2;
+
+    let mut lines = vec![
+        format!("   {}", "_".repeat(stone - 4)),
+        format!("  /{}\\", " ".repeat(stone - 4)),
+        format!(" /{}\\", " ".repeat(stone - 2)),
+    ];
+    let paragraphs = [vec!["R.I.P."], vec![&name, &years], vec![&epitaph]];
+    for paragraph in paragraphs {
+        for text in paragraph {
+            for line in wrap(text, inner - 4) {
+                lines.push(format!(" |{}|", center(&line, inner)));
+            }
+        }
+        lines.push(format!(" |{}|", " ".repeat(inner)));
+    }
+    lines.push(format!("_|{}|_", "_".repeat(inner)));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_text() {
+        assert_eq!(wrap("taken by a falling piano", 10), vec![
+            "taken by a", "falling", "piano",
+        ]);
+        assert_eq!(wrap("a verylongword", 4), vec![
+            "a", "very", "long", "word",
+        ]);
+        assert!(wrap("   ", 4).is_empty());
+        assert_eq!(center("ab", 6), "  ab  ");
+    }
+}
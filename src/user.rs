@@ -1,58 +1,237 @@
-use crate::date::{Date, self};
-use crate::cli;
+use crate::date::{Date, Time, self};
+use crate::algorithm::Algorithm;
+use crate::species::Species;
+use crate::locale::Language;
+use crate::theme::Theme;
+use crate::reasons::{self, Reason, ReasonSet};
+use crate::{numerology, tarot};
+#[cfg(feature = "cli")]
+use crate::{cli, locale, paths};
+#[cfg(feature = "cli")]
+use crate::error::DeathError;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+#[cfg(feature = "cli")]
+use std::io::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where death reasons come from.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum ReasonsSource {
+    /// Files passed in arguments
+    Files(Vec<PathBuf>),
+    /// `DEATH_REASONS` environment variable
+    Env,
+    /// Built-in pack of the theme
+    Theme(Theme),
+    /// Default death reasons of the species
+    Species(Species),
+    /// User's default file and directory
+    UserFiles(Vec<PathBuf>),
+    /// Default death reasons in the language
+    Defaults(Language),
+}
+
+impl fmt::Display for ReasonsSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let paths = |files: &[PathBuf]| files.iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self {
+            ReasonsSource::Files(files) => write!(f, "{}", paths(files)),
+            ReasonsSource::Env => write!(f, "{}", reasons::ENV_VAR),
+            ReasonsSource::Theme(theme) => {
+                write!(f, "built-in {} pack", theme.name())
+            },
+            ReasonsSource::Species(species) => {
+                write!(f, "built-in {} reasons", species.name())
+            },
+            ReasonsSource::UserFiles(files) => write!(f, "{}", paths(files)),
+            ReasonsSource::Defaults(language) => {
+                write!(f, "built-in reasons ({})", language.code())
+            },
+        }
+    }
+}
+
+/// Returns death reasons from files passed in arguments, `DEATH_REASONS`
+/// environment variable, built-in pack of the theme, user's default file or
+/// default ones, whichever comes first.
+#[cfg(feature = "cli")]
+fn load_death_reasons(args: &cli::ReasonsArgs)
+-> Result<(ReasonSet, ReasonsSource), Error> {
+    if !args.death_reasons.is_empty() {
+        let res = reasons::read_many_limited(
+            &args.death_reasons, args.max_reasons
+        )?;
+        return Ok((res, ReasonsSource::Files(args.death_reasons.clone())));
+    }
+
+    if let Some(result) = reasons::read_env() {
+        return Ok((result?, ReasonsSource::Env));
+    }
+
+    if let Some(theme) = args.theme {
+        if theme != Theme::Classic {
+            let res = ReasonSet::from_strings(
+                crate::default_death_reasons_for(theme)
+            )?;
+            return Ok((res, ReasonsSource::Theme(theme)));
+        }
+    }
+
+    let species = args.pet.unwrap_or_default();
+    if species != Species::Human {
+        let res = ReasonSet::from_strings(species.default_death_reasons())?;
+        return Ok((res, ReasonsSource::Species(species)));
+    }
+
+    let files = paths::reasons_files();
+    if !files.is_empty() {
+        let res = reasons::read_many_limited(&files, args.max_reasons)?;
+        return Ok((res, ReasonsSource::UserFiles(files)));
+    }
+
+    let language = locale::language();
+    Ok((ReasonSet::defaults(language), ReasonsSource::Defaults(language)))
+}
+
+/// Returns death reasons chosen by command-line arguments and where they
+/// come from.
+///
+/// If death reasons files are not passed, `DEATH_REASONS` environment
+/// variable is used (see [`reasons::read_env`]), then built-in pack of the
+/// theme, default death reasons of the species, and user's default file and
+/// directory for humans if they exist (see [`paths::reasons_files`]). Default
+/// death reasons are in language of messages (see [`locale::language`]).
+///
+/// Death reasons from `--extra-reasons` files are added to them. Repeated
+/// reasons are collapsed (see [`reasons::dedup`]), excluded ones
+/// are removed and only reasons of the category are left.
+///
+/// # Errors
+///
+/// Returns [`DeathError::Reasons`] if some file can't be read or parsed, or
+/// no reasons are left.
+#[cfg(feature = "cli")]
+pub fn death_reasons_from(args: &cli::ReasonsArgs)
+-> Result<(ReasonSet, ReasonsSource), DeathError> {
+    let (death_reasons, source) = load_death_reasons(args)
+        .map_err(DeathError::Reasons)?;
+    let metadata = death_reasons.metadata().clone();
+    let mut death_reasons: Vec<Reason> = death_reasons.into();
+    if !args.extra_reasons.is_empty() {
+        let extra = reasons::read_many(&args.extra_reasons)
+            .map_err(DeathError::Reasons)?;
+        death_reasons.extend(Vec::from(extra));
+    }
+    let (death_reasons, collapsed) = reasons::dedup(
+        death_reasons, args.dedup
+    );
+    for (reason, count) in collapsed {
+        cli::print_warning(
+            format!("Collapsed {} repeated reasons \"{}\"", count, reason)
+        );
+    }
+    let mut excluded = vec![];
+    for value in args.exclude_reasons.iter() {
+        let reasons = reasons::read_file_or_list(value).map_err(|e| {
+            DeathError::Reasons(
+                Error::new(e.kind(), format!("{}: {}", value, e))
+            )
+        })?;
+        excluded.extend(reasons);
+    }
+    let death_reasons = ReasonSet::new(
+        reasons::exclude(&death_reasons, &excluded)
+    ).map_err(|e| DeathError::Reasons(
+        Error::new(e.kind(), "All death reasons are excluded")
+    ))?.with_metadata(metadata);
+    let death_reasons = match &args.category {
+        Some(category) => death_reasons.filter(category)
+            .map_err(|e| DeathError::Reasons(Error::new(
+                e.kind(), format!("No death reasons in category `{}`", category)
+            )))?,
+        None => death_reasons,
+    };
+
+    Ok((death_reasons, source))
+}
 
 #[derive(Debug)]
 pub struct User {
     id: u64,
     age: u8,
-    death_reasons: Vec<String>,
+    death_reasons: ReasonSet,
+    name: String,
+    birthday: Option<Date>,
+    max_age: u16,
 }
 
 impl User {
     /// Returns a new user.
-    pub fn new(id: u64, age: u8, death_reasons: Vec<String>) -> User {
-        User { id, age, death_reasons }
+    pub fn new(id: u64, age: u8, death_reasons: ReasonSet) -> User {
+        User {
+            id, age, death_reasons, name: String::new(), birthday: None,
+            max_age: date::MAX_AGE,
+        }
     }
 
     /// Returns a new user from command-line arguments.
     ///
-    /// If some argument was empty, default value will be used.
+    /// If some argument was empty, default value will be used. Age is taken
+    /// from the birthday or passed directly. Max age and default death
+    /// reasons depend on the species unless max age is passed
+    /// (see [`cli::PredictionSettings::max_age`]). Death reasons are chosen
+    /// by [`death_reasons_from`]. Id is the seed if it is passed, or the
+    /// name's hash otherwise.
     ///
     /// # Errors
     ///
-    /// If some argument is invalid, program will close immediately.
-    pub fn from(args: &cli::Cli) -> User {
-        let empty = String::from("01/01/1970");
-        let birthday_string = match &args.birthday {
-            Some(v) => v,
-            None => &empty,
-        };
-        let birthday = cli::parse_birthday(&birthday_string);
-        let age = match birthday {
-            Ok(v) => v.years_from(Date::today()) as u8,
-            Err(e) => {
-                cli::print_error(e, 1);
-                0
-            }
-        };
+    /// Returns [`DeathError`] if birthday, age or death reasons are invalid.
+    #[cfg(feature = "cli")]
+    pub fn from(args: &cli::PredictArgs) -> Result<User, DeathError> {
+        let settings = &args.options.settings;
+        let max_age = settings.max_age();
+        let order = settings.date_order();
 
-        let death_reasons = match crate::read_death_reasons(
-            &args.death_reasons
-        ) {
-            Ok(v) => v,
-            Err(e) => {
-                cli::print_error(e, 1);
-                vec![]
+        let birthday = args.birthday.as_ref()
+            .map(|s| cli::parse_birthday(s, max_age, order))
+            .transpose()?;
+        let age = match (birthday, args.age) {
+            (Some(v), _) => v.years_from(Date::today()) as u8,
+            (None, Some(age)) if age as u16 >= max_age => {
+                let age = age as u16;
+                return Err(DeathError::AgeTooBig { age, max_age });
             },
+            (None, Some(age)) => age,
+            (None, None) => 0,
         };
 
-        let id = User::get_id_from_string(
-            &args.name.as_deref().unwrap_or("").to_string()
-        );
+        let (death_reasons, _) = death_reasons_from(&settings.reasons)?;
+
+        let name = args.all_names().first().copied().unwrap_or("")
+            .to_string();
+        let id = args.seed
+            .unwrap_or_else(|| User::get_id_from_string(&name));
+
+        Ok(User { id, age, death_reasons, name, birthday, max_age })
+    }
 
-        User { id, age, death_reasons }
+    /// Returns a new user of the species with its max age and default death
+    /// reasons.
+    pub fn new_of(species: Species, id: u64, age: u8) -> User {
+        let death_reasons = ReasonSet::from_strings(
+            species.default_death_reasons()
+        ).expect("every species has default death reasons");
+        let mut user = User::new(id, age, death_reasons);
+        user.set_max_age(species.max_age());
+        user
     }
 
     /// Set an id for user.
@@ -60,10 +239,77 @@ impl User {
         self.id = id;
     }
 
+    /// Mixes the salt into user's id, so the prediction is different, but
+    /// the same for the same id and salt.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use death::reasons::ReasonSet;
+    /// use death::user::User;
+    ///
+    /// let mut user = User::new(1234567890, 30, ReasonSet::default());
+    /// user.reroll(1);
+    /// let rerolled = user.id();
+    ///
+    /// user.set_id(1234567890);
+    /// user.reroll(1);
+    ///
+    /// assert_ne!(rerolled, 1234567890);
+    /// assert_eq!(user.id(), rerolled);
+    /// ```
+    pub fn reroll(&mut self, salt: u64) {
+        let mut s = DefaultHasher::new();
+        (self.id, salt).hash(&mut s);
+        self.id = s.finish();
+    }
+
     /// Set an age for user.
     pub fn set_age(&mut self, age: u8) {
         self.age = age;
     }
+
+    /// Set a max age for user.
+    pub fn set_max_age(&mut self, max_age: u16) {
+        self.max_age = max_age;
+    }
+
+    /// Returns user's id.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns user's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns user's age.
+    pub fn age(&self) -> u8 {
+        self.age
+    }
+
+    /// Returns user's birthday if it is known.
+    pub fn birthday(&self) -> Option<Date> {
+        self.birthday
+    }
+
+    /// Returns user's max age.
+    pub fn max_age(&self) -> u16 {
+        self.max_age
+    }
+
+    /// Set a name for user. Id is set from the name's hash.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+        self.id = User::get_id_from_string(&self.name);
+    }
+
+    /// Set a birthday for user. Age is calculated from the birthday.
+    pub fn set_birthday(&mut self, birthday: Date) {
+        self.birthday = Some(birthday);
+        self.age = birthday.years_from(Date::today()) as u8;
+    }
     
     /// Get an id from string's hash.
     pub fn get_id_from_string(string: &String) -> u64 {
@@ -72,16 +318,26 @@ impl User {
         s.finish()
     }
 
-    fn get_years_left(&self, linear: bool) -> u8 {
-        if linear {
-            let max_age: u64 = (date::MAX_AGE - self.age as u16) as u64;
+    /// Returns number of years left calculated by the formula, from 1 to
+    /// the difference between max age and age.
+    pub fn get_years_left(&self, algorithm: Algorithm) -> u8 {
+        if algorithm == Algorithm::Linear {
+            let max_age: u64 = (self.max_age - self.age as u16) as u64;
             return (self.id % max_age + 1) as u8;
         }
 
+        if algorithm == Algorithm::Numerology {
+            if let Some(birthday) = &self.birthday {
+                return numerology::years_left(
+                    &self.name, birthday, self.age, self.max_age
+                );
+            }
+        }
+
         // Returns smaller values more often than larger values
 
         // Max y
-        let max_age: f64 = (date::MAX_AGE - self.age as u16) as f64;
+        let max_age: f64 = (self.max_age - self.age as u16) as f64;
 
         // Stretch the graph horizontally to make the result more accurate
         let k: f64 = 100.0;
@@ -96,22 +352,186 @@ impl User {
         base.powf(x) as u8
     }
 
-    /// Returns user's predicted death reason.
-    pub fn get_death_reason(&self) -> &String {
-        &self.death_reasons[
-            (self.id % (self.death_reasons.len() as u64)) as usize
-        ]
+    /// Set death reasons for user.
+    pub fn set_death_reasons(&mut self, death_reasons: ReasonSet) {
+        self.death_reasons = death_reasons;
+    }
+
+    /// Returns user's death reasons.
+    pub fn death_reasons(&self) -> &ReasonSet {
+        &self.death_reasons
+    }
+
+    /// Returns user's predicted death reason. Reasons with greater weight are
+    /// predicted more often.
+    pub fn get_death_reason(&self) -> &Reason {
+        self.death_reasons.pick(self.id)
+    }
+
+    /// Draws a tarot card and returns it with the death reason it points at.
+    /// (see [`tarot::reason_index`])
+    pub fn get_tarot_reason(&self) -> (tarot::Card, &Reason) {
+        let card = tarot::draw(self.id);
+        let i = tarot::reason_index(&card, &self.death_reasons);
+        (card, &self.death_reasons[i])
+    }
+
+    /// Returns time of day the user dies at.
+    pub fn get_death_time(&self) -> Time {
+        // Other bits of id than the date and the reason are made of
+        let minutes = self.id.rotate_left(17) % (24 * 60);
+        Time::build((minutes / 60) as u8, (minutes % 60) as u8).unwrap()
     }
 
     /// Returns calculated death date of current user.
-    pub fn get_death_date(&self, linear: bool) -> Date {
-        let year = Date::today().year() + self.get_years_left(linear) as u16;
+    ///
+    /// [`Algorithm::Numerology`] needs a birthday, exponential formula is used
+    /// if it is unknown.
+    pub fn get_death_date(&self, algorithm: Algorithm) -> Date {
+        let year = Date::today().year() + self.get_years_left(algorithm) as u16;
         let month = (self.id % 12 + 1) as u8;
         let day = 1;
         let date0 = Date::build(year, month, day).unwrap();
         let day = (self.id % date0.get_max_day() as u64 + 1) as u8;
-        let date = Date::build(year, month, day).unwrap();
+        Date::build(year, month, day).unwrap()
+    }
+
+    /// Returns explanation how the prediction is made: the id, every step of
+    /// the formula and how the death reason is picked, line by line.
+    pub fn trace(&self, algorithm: Algorithm, tarot: bool) -> Vec<String> {
+        let mut res = vec![
+            format!("id = {}", self.id),
+            format!(
+                "age = {}, max age = {}, years range = {}",
+                self.age, self.max_age, self.max_age - self.age as u16
+            ),
+        ];
+        let range = (self.max_age - self.age as u16) as u64;
+        let years_left = self.get_years_left(algorithm);
+
+        match (algorithm, &self.birthday) {
+            (Algorithm::Linear, _) => res.push(format!(
+                "linear: years left = id % {} + 1 = {}", range, years_left
+            )),
+            (Algorithm::Numerology, Some(birthday)) => {
+                let candidates = numerology::death_age_candidates(
+                    &self.name, birthday, self.age, self.max_age
+                );
+                res.push(format!(
+                    "numerology: expression number = {}, life path number = \
+                    {}, target number = {}",
+                    numerology::expression_number(&self.name),
+                    numerology::life_path_number(birthday),
+                    numerology::target_number(&self.name, birthday)
+                ));
+                res.push(format!(
+                    "numerology: candidate ages = {:?}, name sum = {}, \
+                    years left = {}",
+                    candidates, numerology::name_sum(&self.name), years_left
+                ));
+            },
+            (algorithm, _) => {
+                if algorithm == Algorithm::Numerology {
+                    res.push(String::from(
+                        "numerology: birthday is unknown, exponential is used"
+                    ));
+                }
+                let k = 100.0;
+                let max_x = range as f64 * k;
+                let base = (range as f64).powf(1.0 / max_x);
+                res.push(format!(
+                    "exponential: k = {}, base = {}^(1/{}) = {:.6}",
+                    k, range, max_x, base
+                ));
+                res.push(format!(
+                    "exponential: x = id % {} = {}, years left = base^x = {}",
+                    max_x, self.id % max_x as u64, years_left
+                ));
+            },
+        }
+
+        let date = self.get_death_date(algorithm);
+        res.push(format!(
+            "date: year = {} + {}, month = id % 12 + 1 = {}, day = id % {} + 1 \
+            = {}",
+            Date::today().year(), years_left, date.month(), date.get_max_day(),
+            date.day()
+        ));
+        res.push(format!(
+            "range: spread = (id >> 8) % 5 + 1 = {}", self.spread()
+        ));
+
+        let reasons = &self.death_reasons;
+        if tarot {
+            let card = tarot::draw(self.id);
+            let i = tarot::reason_index(&card, reasons);
+            res.push(format!(
+                "tarot: card = {} (points at `{}`), reason index = {} ({})",
+                card.name, card.category, i, reasons[i]
+            ));
+        } else {
+            let total = reasons.total_weight();
+            let i = reasons.pick_index(self.id);
+            res.push(format!(
+                "reason: {} reasons, total weight {}, id % {} = {}, index = {} \
+                ({})",
+                reasons.len(), total, total, self.id % total.max(1), i,
+                reasons[i]
+            ));
+        }
+
+        res
+    }
+
+    /// Returns number of years the confidence range spreads around the
+    /// death year, from 1 to 5. (see [`User::get_confidence_range`])
+    fn spread(&self) -> u16 {
+        ((self.id >> 8) % 5 + 1) as u16
+    }
+
+    /// Returns range of years the death most likely happens in.
+    ///
+    /// The range is from 1 to 5 years in both directions from the year of
+    /// [`User::get_death_date`], but never before this year and never after
+    /// the user reaches max age.
+    pub fn get_confidence_range(&self, algorithm: Algorithm)
+    -> RangeInclusive<u16> {
+        let this_year = Date::today().year();
+        let year = this_year + self.get_years_left(algorithm) as u16;
+        let spread = self.spread();
+        let last_year = this_year + (self.max_age - self.age as u16);
+
+        (year - spread).max(this_year)..=(year + spread).min(last_year)
+    }
+}
 
-        date
+#[cfg(test)]
+#[cfg(feature = "cli")]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn exclude_reasons() {
+        let reasons_of = |args: &[&str]| {
+            let args = [&["death", "--death-reasons", "tests/read_file.txt"],
+                args].concat();
+            let cli = cli::Cli::try_parse_from(args).unwrap();
+            let settings = &cli.predict.options.settings;
+            death_reasons_from(&settings.reasons).map(|r| r.0)
+        };
+
+        let reasons = reasons_of(&["--exclude-reasons", "sample1, 123"])
+            .unwrap();
+        assert_eq!(reasons.len(), 6);
+
+        match reasons_of(&["--exclude-reasons", "tests/missing.txt"]) {
+            Err(DeathError::Reasons(e)) => {
+                assert_eq!(e.kind(), ErrorKind::NotFound);
+                assert!(e.to_string().starts_with("tests/missing.txt: "));
+            },
+            res => panic!("Unexpected result: {:?}", res.map(|r| r.len())),
+        }
     }
 }
@@ -0,0 +1,425 @@
+use std::fmt;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::reasons::{self, Format};
+
+/// Max length of a death reason in characters.
+pub const MAX_REASON_LENGTH: usize = 100;
+
+/// Place of an issue in death reasons file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Location {
+    /// Whole file
+    File,
+    /// Line number of plain text and CSV files, starting from 1
+    Line(usize),
+    /// Entry number of JSON, YAML and TOML files, starting from 1
+    Entry(usize),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::File => write!(f, "file"),
+            Location::Line(n) => write!(f, "line {}", n),
+            Location::Entry(n) => write!(f, "entry {}", n),
+        }
+    }
+}
+
+/// Kind of an issue in death reasons file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum IssueKind {
+    /// File cannot be read or parsed
+    Invalid(String),
+    /// File has no reasons
+    NoReasons,
+    /// Line contains only spaces or reason is empty
+    Empty,
+    /// Reason is longer than [`MAX_REASON_LENGTH`]
+    TooLong(usize),
+    /// Reason is the same as the one in the location
+    Duplicate(Location),
+    /// Weight is not a positive number
+    InvalidWeight(String),
+}
+
+/// Issue in death reasons file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ReasonIssue {
+    pub location: Location,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for ReasonIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.location)?;
+        match &self.kind {
+            IssueKind::Invalid(e) => write!(f, "{}", e),
+            IssueKind::NoReasons => write!(f, "no reasons found"),
+            IssueKind::Empty => write!(f, "empty reason"),
+            IssueKind::TooLong(len) => write!(
+                f, "reason is too long ({} > {} characters)",
+                len, MAX_REASON_LENGTH
+            ),
+            IssueKind::Duplicate(first) => write!(f, "duplicate of {}", first),
+            IssueKind::InvalidWeight(w) => write!(f, "invalid weight `{}`", w),
+        }
+    }
+}
+
+/// Statistics of valid death reasons file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ReasonStats {
+    /// Number of reasons
+    pub count: usize,
+    /// Sum of all weights
+    pub total_weight: u64,
+    /// Number of different categories
+    pub categories: usize,
+    /// Length of the longest reason in characters
+    pub max_length: usize,
+}
+
+impl fmt::Display for ReasonStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{} reasons, total weight {}, {} categories, longest is {} \
+            characters", self.count, self.total_weight, self.categories,
+            self.max_length
+        )
+    }
+}
+
+/// Reason as it is written in the file, before any checks.
+struct Entry {
+    location: Location,
+    text: String,
+    weight: Result<u32, String>,
+    category: Option<String>,
+}
+
+fn text_entries(contents: &str) -> Vec<Entry> {
+    let mut res = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let text = reasons::strip_comment(line);
+        // Empty lines and comments are fine, but not lines with spaces only
+        let spaces_only = !line.is_empty() && line.trim().is_empty();
+        if text.trim().is_empty() && !spaces_only {
+            continue;
+        }
+        res.push(Entry {
+            location: Location::Line(i + 1),
+            text: text.trim().replace("\\#", "#"),
+            weight: Ok(1),
+            category: None,
+        });
+    }
+    res
+}
+
+fn csv_entries(contents: &str) -> Result<Vec<Entry>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+
+    let mut res = vec![];
+
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| e.to_string())?;
+        let line = record.position().map_or(i + 1, |p| p.line() as usize);
+
+        let text = record.get(0).unwrap_or("");
+        if i == 0 && reasons::is_csv_header(text) {
+            continue;
+        }
+
+        let weight = match record.get(1) {
+            None | Some("") => Ok(1),
+            Some(w) => match w.parse() {
+                Ok(w) => Ok(w),
+                Err(_) if i == 0 => continue,
+                Err(_) => Err(w.to_string()),
+            },
+        };
+
+        res.push(Entry {
+            location: Location::Line(line),
+            text: text.to_string(),
+            weight,
+            category: record.get(2)
+                .filter(|c| !c.is_empty())
+                .map(String::from),
+        });
+    }
+
+    Ok(res)
+}
+
+fn value_entries(value: &Value) -> Result<Vec<Entry>, String> {
+    let items = reasons::value_items(value)
+        .ok_or_else(|| String::from("must be an array of reasons"))?;
+
+    let mut res = vec![];
+
+    for (i, item) in items.iter().enumerate() {
+        let location = Location::Entry(i + 1);
+        let entry = match item {
+            Value::String(s) => Entry {
+                location, text: s.trim().to_string(), weight: Ok(1),
+                category: None,
+            },
+            Value::Object(map) => Entry {
+                location,
+                text: map.get("reason").or_else(|| map.get("text"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+                weight: match map.get("weight") {
+                    None => Ok(1),
+                    Some(w) => w.as_u64()
+                        .and_then(|w| u32::try_from(w).ok())
+                        .ok_or_else(|| w.to_string()),
+                },
+                category: map.get("category")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+            },
+            _ => return Err(format!("entry {}: expected string or object",
+                i + 1)),
+        };
+        res.push(entry);
+    }
+
+    Ok(res)
+}
+
+fn entries(contents: &str, format: Format) -> Result<Vec<Entry>, String> {
+    match format {
+        Format::Text => Ok(text_entries(contents)),
+        Format::Csv => csv_entries(contents),
+        Format::Json => {
+            let value: Value = serde_json::from_str(contents)
+                .map_err(|e| format!("invalid JSON: {}", e))?;
+            value_entries(&value)
+        },
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let value: Value = serde_yaml::from_str(contents)
+                .map_err(|e| format!("invalid YAML: {}", e))?;
+            value_entries(&value)
+        },
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(String::from(
+            "YAML support is not enabled, build with `yaml` feature"
+        )),
+        Format::Toml => {
+            let table: toml::Table = toml::from_str(contents)
+                .map_err(|e| format!("invalid TOML: {}", e))?;
+            let reasons = table.get("reason")
+                .cloned()
+                .unwrap_or(toml::Value::Array(vec![]));
+            let value = serde_json::to_value(reasons)
+                .map_err(|e| e.to_string())?;
+            value_entries(&value)
+        },
+    }
+}
+
+/// Returns statistics of death reasons in given format or all issues found.
+/// (see [`validate_reasons`])
+///
+/// # Example
+///
+/// ```
+/// use death::reasons::Format;
+/// use death::validate::{validate, IssueKind, Location};
+///
+/// let stats = validate("cars\nfire\n", Format::Text).unwrap();
+/// assert_eq!(stats.count, 2);
+///
+/// let issues = validate("cars\nfire\ncars\n", Format::Text).unwrap_err();
+/// assert_eq!(issues[0].location, Location::Line(3));
+/// assert_eq!(issues[0].kind, IssueKind::Duplicate(Location::Line(1)));
+/// ```
+pub fn validate(contents: &str, format: Format)
+-> Result<ReasonStats, Vec<ReasonIssue>> {
+    let entries = match entries(contents, format) {
+        Ok(v) => v,
+        Err(e) => return Err(vec![ReasonIssue {
+            location: Location::File,
+            kind: IssueKind::Invalid(e),
+        }]),
+    };
+
+    let mut issues = vec![];
+    let mut seen: Vec<(String, Location)> = vec![];
+    let mut categories: Vec<&str> = vec![];
+    let mut stats = ReasonStats {
+        count: 0, total_weight: 0, categories: 0, max_length: 0,
+    };
+
+    for entry in entries.iter() {
+        let issue = |kind| ReasonIssue { location: entry.location, kind };
+
+        if entry.text.is_empty() {
+            issues.push(issue(IssueKind::Empty));
+            continue;
+        }
+
+        let length = entry.text.chars().count();
+        if length > MAX_REASON_LENGTH {
+            issues.push(issue(IssueKind::TooLong(length)));
+        }
+
+        let key = entry.text.to_lowercase();
+        match seen.iter().find(|(text, _)| *text == key) {
+            Some((_, first)) => {
+                issues.push(issue(IssueKind::Duplicate(*first)));
+            },
+            None => seen.push((key, entry.location)),
+        }
+
+        match &entry.weight {
+            Ok(0) => issues.push(issue(IssueKind::InvalidWeight(
+                String::from("0")
+            ))),
+            Ok(w) => stats.total_weight += *w as u64,
+            Err(w) => issues.push(issue(IssueKind::InvalidWeight(w.clone()))),
+        }
+
+        if let Some(category) = &entry.category {
+            if !categories.contains(&category.as_str()) {
+                categories.push(category);
+            }
+        }
+
+        stats.count += 1;
+        stats.max_length = stats.max_length.max(length);
+    }
+
+    stats.categories = categories.len();
+
+    if stats.count == 0 && issues.is_empty() {
+        issues.push(ReasonIssue {
+            location: Location::File,
+            kind: IssueKind::NoReasons,
+        });
+    }
+
+    if issues.is_empty() {
+        Ok(stats)
+    } else {
+        Err(issues)
+    }
+}
+
+/// Returns statistics of death reasons file or all issues found: lines with
+/// spaces only, empty and too long reasons, duplicates and invalid weights.
+///
+/// Format is detected the same way as in [`reasons::read_contents`].
+pub fn validate_reasons(file_path: &Path)
+-> Result<ReasonStats, Vec<ReasonIssue>> {
+    match reasons::read_contents(file_path) {
+        Ok((contents, format)) => validate(&contents, format),
+        Err(e) => Err(vec![ReasonIssue {
+            location: Location::File,
+            kind: IssueKind::Invalid(e.to_string()),
+        }]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_files() {
+        let stats = validate_reasons(Path::new("tests/comments.txt")).unwrap();
+        assert_eq!(stats.count, 4);
+
+        let stats = validate_reasons(Path::new("tests/read_file.csv"))
+            .unwrap();
+        assert_eq!(stats, ReasonStats {
+            count: 3, total_weight: 8, categories: 1, max_length: 14,
+        });
+
+        let stats = validate_reasons(Path::new("tests/one_column.csv"))
+            .unwrap();
+        assert_eq!(stats.count, 2);
+    }
+
+    #[test]
+    fn invalid_text() {
+        let issues = validate_reasons(Path::new("tests/read_file.txt"))
+            .unwrap_err();
+        let locations: Vec<Location> = issues.iter()
+            .map(|i| i.location)
+            .collect();
+        assert_eq!(locations, vec![
+            Location::Line(4), Location::Line(5), Location::Line(7),
+            Location::Line(8),
+        ]);
+        assert!(issues.iter().all(|i| i.kind == IssueKind::Empty));
+
+        let issues = validate_reasons(Path::new("tests/empty.txt"))
+            .unwrap_err();
+        assert_eq!(issues[0].kind, IssueKind::NoReasons);
+    }
+
+    #[test]
+    fn invalid_entries() {
+        let long = "a".repeat(MAX_REASON_LENGTH + 1);
+        let json = format!(
+            r#"["cars", "", {{"reason": "{}"}}, {{"reason": "CARS"}},
+            {{"reason": "fire", "weight": -1}},
+            {{"reason": "x", "weight": 0}}]"#,
+            long
+        );
+        let issues = validate(&json, Format::Json).unwrap_err();
+
+        assert_eq!(issues, vec![
+            ReasonIssue {
+                location: Location::Entry(2),
+                kind: IssueKind::Empty,
+            },
+            ReasonIssue {
+                location: Location::Entry(3),
+                kind: IssueKind::TooLong(MAX_REASON_LENGTH + 1),
+            },
+            ReasonIssue {
+                location: Location::Entry(4),
+                kind: IssueKind::Duplicate(Location::Entry(1)),
+            },
+            ReasonIssue {
+                location: Location::Entry(5),
+                kind: IssueKind::InvalidWeight(String::from("-1")),
+            },
+            ReasonIssue {
+                location: Location::Entry(6),
+                kind: IssueKind::InvalidWeight(String::from("0")),
+            },
+        ]);
+
+        let issues = validate("reason,weight\ncars,x\n", Format::Csv)
+            .unwrap_err();
+        assert_eq!(issues[0].to_string(), "line 2: invalid weight `x`");
+
+        let issues = validate("[[reason]]\ntext = 5", Format::Toml)
+            .unwrap_err();
+        assert_eq!(issues[0].to_string(), "entry 1: empty reason");
+    }
+}
@@ -0,0 +1,84 @@
+use wasm_bindgen::prelude::*;
+
+use crate::algorithm::Algorithm;
+use crate::batch::Person;
+use crate::date::{self, DateOrder};
+use crate::error::DeathError;
+use crate::locale::Language;
+use crate::prediction::Prediction;
+use crate::reasons::ReasonSet;
+
+/// Returns prediction for the name and the birthday with day, month and
+/// year, the same as `death --name <NAME> --birthday <BIRTHDAY>` with
+/// default settings makes. Empty birthday is unknown.
+///
+/// # Errors
+///
+/// Returns [`DeathError`] if the birthday is invalid.
+///
+/// # Example
+///
+/// ```
+/// use death::wasm::prediction;
+///
+/// let res = prediction("Alice", "01.02.1990").unwrap();
+///
+/// assert_eq!(res.name, "Alice");
+/// assert!(prediction("Alice", "31.02.1990").is_err());
+/// ```
+pub fn prediction(name: &str, birthday: &str)
+-> Result<Prediction, DeathError> {
+    let person = Person {
+        name: name.to_string(),
+        birthday: Some(birthday.to_string()).filter(|b| !b.is_empty()),
+        age: None,
+        id: None,
+    };
+    let death_reasons = ReasonSet::defaults(Language::En);
+    let user = person.to_user(death_reasons, date::MAX_AGE, DateOrder::Dmy)?;
+    Ok(Prediction::new(&user, Algorithm::default(), false))
+}
+
+/// Returns prediction for the name and the birthday as object with the
+/// same fields as `death --output json` prints. (see [`prediction`])
+///
+/// ```js
+/// import init, { predict } from "./pkg/death.js";
+///
+/// await init();
+/// const prediction = predict("Alice", "01.02.1990");
+/// console.log(prediction.death_date, prediction.reason);
+/// ```
+///
+/// # Errors
+///
+/// Throws `Error` with the message if the birthday is invalid.
+#[wasm_bindgen]
+pub fn predict(name: &str, birthday: &str) -> Result<JsValue, JsError> {
+    let prediction = prediction(name, birthday)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    js_sys::JSON::parse(&prediction.to_json())
+        .map_err(|_| JsError::new("Prediction is not valid JSON"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::User;
+
+    #[test]
+    fn same_as_cli() {
+        let res = prediction("Alice", "01.02.1990").unwrap();
+        let mut user = User::new(0, 0, ReasonSet::defaults(Language::En));
+        user.set_name("Alice");
+        user.set_birthday(date::Date::build(1990, 2, 1).unwrap());
+        let cli = Prediction::new(&user, Algorithm::Exponential, false);
+        assert_eq!(res.to_json(), cli.to_json());
+
+        assert!(prediction("Bob", "").unwrap().birthday.is_none());
+        let next_year = format!("01.02.{}", date::Date::today().year() + 1);
+        assert!(matches!(
+            prediction("Bob", &next_year), Err(DeathError::BirthdayInFuture)
+        ));
+    }
+}
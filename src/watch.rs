@@ -0,0 +1,141 @@
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::reasons::{self, ReasonSet};
+
+/// Death reasons that are read again when their files change, so long-running
+/// modes don't need a restart to use an updated pack.
+///
+/// The set is swapped atomically: readers get either the old set or the new
+/// one. If the changed files can't be read or parsed, e.g. while the file is
+/// half-written, the old set is kept.
+pub struct LiveReasons {
+    current: Arc<RwLock<Arc<ReasonSet>>>,
+    reloads: Arc<AtomicU64>,
+    _watcher: RecommendedWatcher,
+}
+
+impl LiveReasons {
+    /// Reads at most `limit` death reasons from the files and starts watching
+    /// them. (see [`reasons::read_many_limited`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if cannot read or parse the files, or
+    /// cannot watch them.
+    pub fn watch(paths: &[PathBuf], limit: Option<usize>)
+    -> Result<LiveReasons, Error> {
+        let current = Arc::new(RwLock::new(Arc::new(
+            reasons::read_many_limited(paths, limit)?
+        )));
+
+        let reloads = Arc::new(AtomicU64::new(0));
+
+        let shared = Arc::clone(&current);
+        let counter = Arc::clone(&reloads);
+        let watched = paths.to_vec();
+        let mut watcher = notify::recommended_watcher(
+            move |event: notify::Result<Event>| {
+                let changed = event.is_ok_and(|e| {
+                    !e.kind.is_access() &&
+                    e.paths.iter().any(|p| is_watched(p, &watched))
+                });
+                if !changed {
+                    return;
+                }
+                if let Ok(v) = reasons::read_many_limited(&watched, limit) {
+                    *shared.write().unwrap() = Arc::new(v);
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        ).map_err(Error::other)?;
+
+        // Editors often replace the file instead of writing to it, so watch
+        // the directory the file is in
+        for path in paths {
+            let target = match path.parent() {
+                Some(p) if path.is_file() && p != Path::new("") => p,
+                Some(_) if path.is_file() => Path::new("."),
+                _ => path.as_path(),
+            };
+            watcher.watch(target, RecursiveMode::NonRecursive)
+                .map_err(Error::other)?;
+        }
+
+        Ok(LiveReasons { current, reloads, _watcher: watcher })
+    }
+
+    /// Returns current death reasons.
+    pub fn get(&self) -> Arc<ReasonSet> {
+        Arc::clone(&self.current.read().unwrap())
+    }
+
+    /// Returns how many times the death reasons are read again since
+    /// watching started.
+    pub fn reloads(&self) -> u64 {
+        self.reloads.load(Ordering::Relaxed)
+    }
+}
+
+/// Returns `true` if the path is one of the watched files or inside one of
+/// the watched directories.
+fn is_watched(path: &Path, watched: &[PathBuf]) -> bool {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or(p.to_path_buf());
+
+    // The file itself may be removed already, so resolve its directory only
+    let path = match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => canonical(dir).join(name),
+        _ => path.to_path_buf(),
+    };
+
+    watched.iter().map(|w| canonical(w))
+        .any(|w| path == w || path.parent() == Some(w.as_path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn reload() {
+        let dir = std::env::temp_dir()
+            .join(format!("death-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("reasons.txt");
+        fs::write(&file, "cars\n").unwrap();
+
+        let live = LiveReasons::watch(std::slice::from_ref(&file), None)
+            .unwrap();
+        assert_eq!(live.get()[0].text, "cars");
+        assert_eq!(live.reloads(), 0);
+
+        fs::write(&file, "fire\nwater\n").unwrap();
+
+        let mut reloaded = false;
+        for _ in 0..50 {
+            if live.get().len() == 2 {
+                reloaded = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        // Broken file keeps the old reasons
+        fs::write(&file, "").unwrap();
+        thread::sleep(Duration::from_millis(300));
+        let len = live.get().len();
+        let reloads = live.reloads();
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(reloaded);
+        assert_eq!(len, 2);
+        assert!(reloads >= 1);
+    }
+}